@@ -174,7 +174,7 @@ mod tests {
 
     #[test]
     fn test_json_from_reader() {
-        let yaml = r#"openapi: "3"
+        let yaml = r#"openapi: "3.1.0"
 paths: {}
 info:
   title: Test API
@@ -187,7 +187,7 @@ components:
       items: { type: integer }"#;
 
         let json = r#"{
-  "openapi": "3",
+  "openapi": "3.1.0",
   "paths": {},
   "info": {
     "title": "Test API",
@@ -211,4 +211,30 @@ components:
             from_reader(yaml.as_bytes()).unwrap()
         );
     }
+
+    #[test]
+    fn test_json_round_trip() {
+        for entry in fs::read_dir("data/oas-samples").unwrap() {
+            let path = entry.unwrap().path();
+
+            let spec = from_path(&path).unwrap();
+            let json = to_json(&spec).unwrap();
+
+            let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+            assert!(
+                value["openapi"].is_string(),
+                "missing version in {:?}",
+                path
+            );
+            assert!(value["info"].is_object(), "missing info in {:?}", path);
+            assert!(value["paths"].is_object(), "missing paths in {:?}", path);
+
+            assert_eq!(
+                from_reader(json.as_bytes()).unwrap(),
+                spec,
+                "round trip was lossy for {:?}",
+                path
+            );
+        }
+    }
 }
@@ -0,0 +1,376 @@
+//! Naming convention consistency checks.
+
+use std::collections::BTreeSet;
+
+use derive_more::Display;
+
+use super::{ObjectOrReference, Schema, Spec};
+
+/// Casing style of a name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+pub enum CasingStyle {
+    /// E.g. `listPets`.
+    #[display(fmt = "camelCase")]
+    CamelCase,
+
+    /// E.g. `ListPets`.
+    #[display(fmt = "PascalCase")]
+    PascalCase,
+
+    /// E.g. `list_pets`.
+    #[display(fmt = "snake_case")]
+    SnakeCase,
+
+    /// E.g. `list-pets`.
+    #[display(fmt = "kebab-case")]
+    KebabCase,
+}
+
+impl CasingStyle {
+    const ALL: [Self; 4] = [
+        Self::CamelCase,
+        Self::PascalCase,
+        Self::SnakeCase,
+        Self::KebabCase,
+    ];
+
+    /// Detects the casing style of a name.
+    ///
+    /// Returns `None` for names that do not follow any style, and for single lowercase words
+    /// such as `pets`, which follow camel, snake and kebab case alike.
+    pub fn detect(name: &str) -> Option<Self> {
+        let style = Self::ALL.into_iter().find(|style| style.matches(name))?;
+
+        // a single lowercase word is also snake and kebab case
+        let ambiguous =
+            style == Self::CamelCase && !name.contains(|c: char| c.is_ascii_uppercase());
+
+        (!ambiguous).then_some(style)
+    }
+
+    /// Returns true if the name follows this style.
+    pub fn matches(&self, name: &str) -> bool {
+        let mut chars = name.chars();
+
+        let first = match chars.next() {
+            Some(first) => first,
+            None => return false,
+        };
+
+        match self {
+            Self::CamelCase => {
+                first.is_ascii_lowercase() && chars.all(|c| c.is_ascii_alphanumeric())
+            }
+            Self::PascalCase => {
+                first.is_ascii_uppercase() && chars.all(|c| c.is_ascii_alphanumeric())
+            }
+            Self::SnakeCase | Self::KebabCase => {
+                let separator = if *self == Self::SnakeCase { '_' } else { '-' };
+
+                first.is_ascii_lowercase()
+                    && name.split(separator).all(|word| {
+                        !word.is_empty()
+                            && word
+                                .chars()
+                                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+                    })
+            }
+        }
+    }
+}
+
+/// Kind of name checked by [`Spec::check_for_inconsistent_casing`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+pub enum NameCategory {
+    #[display(fmt = "operation ID")]
+    OperationId,
+
+    #[display(fmt = "schema name")]
+    SchemaName,
+
+    #[display(fmt = "property name")]
+    PropertyName,
+
+    #[display(fmt = "tag name")]
+    TagName,
+}
+
+/// A name whose casing differs from the majority of names in its category.
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display(fmt = "{} `{}` is not {} like most others", category, name, expected)]
+pub struct CasingWarning {
+    pub category: NameCategory,
+
+    pub name: String,
+
+    /// Detected style of the name, if it follows one.
+    pub style: Option<CasingStyle>,
+
+    /// Style followed by most names in the category.
+    pub expected: CasingStyle,
+}
+
+impl Spec {
+    /// Reports operation IDs, component schema names, property names and tag names whose casing
+    /// differs from the [`CasingStyle`] followed by a majority of names in their category.
+    ///
+    /// Each distinct name is considered once. Property names are collected from component schemas
+    /// and their inline subschemas, and tag names from both the declared tags and those used by
+    /// operations. Categories where no style is followed by more than half of the names with a
+    /// detectable style are not checked; see
+    /// [`check_for_inconsistent_casing_with_threshold`](Self::check_for_inconsistent_casing_with_threshold)
+    /// for requiring a larger share.
+    pub fn check_for_inconsistent_casing(&self) -> Vec<CasingWarning> {
+        self.check_for_inconsistent_casing_with_threshold(0.5)
+    }
+
+    /// Like [`check_for_inconsistent_casing`](Self::check_for_inconsistent_casing), but only
+    /// checks categories where a style is followed by more than `threshold` (between 0 and 1) of
+    /// the names with a detectable style.
+    pub fn check_for_inconsistent_casing_with_threshold(
+        &self,
+        threshold: f64,
+    ) -> Vec<CasingWarning> {
+        let operation_ids = self
+            .operation_refs()
+            .filter_map(|op| op.operation.operation_id.as_deref())
+            .collect();
+
+        let mut schema_names = BTreeSet::new();
+        let mut property_names = BTreeSet::new();
+
+        for (name, schema) in self.components.iter().flat_map(|c| &c.schemas) {
+            schema_names.insert(name.as_str());
+
+            if let ObjectOrReference::Object(schema)
+            | ObjectOrReference::SchemaRef {
+                overrides: schema, ..
+            } = schema
+            {
+                collect_property_names(schema, &mut property_names);
+            }
+        }
+
+        let tag_names = self
+            .tags
+            .iter()
+            .map(|tag| tag.name.as_str())
+            .chain(
+                self.operation_refs()
+                    .flat_map(|op| op.operation.tags.iter().map(String::as_str)),
+            )
+            .collect();
+
+        [
+            (NameCategory::OperationId, operation_ids),
+            (NameCategory::SchemaName, schema_names),
+            (NameCategory::PropertyName, property_names),
+            (NameCategory::TagName, tag_names),
+        ]
+        .into_iter()
+        .flat_map(|(category, names)| casing_outliers(category, &names, threshold))
+        .collect()
+    }
+}
+
+fn collect_property_names<'a>(schema: &'a Schema, names: &mut BTreeSet<&'a str>) {
+    names.extend(schema.properties.keys().map(String::as_str));
+
+    for sub in schema.subschemas() {
+        if let ObjectOrReference::Object(sub)
+        | ObjectOrReference::SchemaRef { overrides: sub, .. } = sub
+        {
+            collect_property_names(sub, names);
+        }
+    }
+}
+
+fn casing_outliers(
+    category: NameCategory,
+    names: &BTreeSet<&str>,
+    threshold: f64,
+) -> Vec<CasingWarning> {
+    let mut counts = [0; CasingStyle::ALL.len()];
+
+    for style in names.iter().filter_map(|name| CasingStyle::detect(name)) {
+        counts[style as usize] += 1;
+    }
+
+    let detected = counts.iter().sum::<usize>();
+    let max = counts.iter().copied().max().unwrap_or(0);
+
+    if max == 0 || max as f64 <= threshold * detected as f64 {
+        return vec![];
+    }
+
+    let expected = match counts.iter().position(|&count| count == max) {
+        Some(index) if counts.iter().filter(|&&count| count == max).count() == 1 => {
+            CasingStyle::ALL[index]
+        }
+        _ => return vec![],
+    };
+
+    names
+        .iter()
+        .filter(|name| !expected.matches(name))
+        .map(|name| CasingWarning {
+            category,
+            name: (*name).to_owned(),
+            style: CasingStyle::detect(name),
+            expected,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casing_style_detection() {
+        assert_eq!(
+            CasingStyle::detect("listPets"),
+            Some(CasingStyle::CamelCase)
+        );
+        assert_eq!(
+            CasingStyle::detect("ListPets"),
+            Some(CasingStyle::PascalCase)
+        );
+        assert_eq!(
+            CasingStyle::detect("list_pets"),
+            Some(CasingStyle::SnakeCase)
+        );
+        assert_eq!(
+            CasingStyle::detect("list-pets2"),
+            Some(CasingStyle::KebabCase)
+        );
+        assert_eq!(CasingStyle::detect("pets"), None);
+        assert_eq!(CasingStyle::detect("LIST_PETS"), None);
+        assert_eq!(CasingStyle::detect("list__pets"), None);
+        assert_eq!(CasingStyle::detect(""), None);
+
+        assert!(CasingStyle::CamelCase.matches("pets"));
+        assert!(CasingStyle::SnakeCase.matches("pets"));
+        assert!(!CasingStyle::PascalCase.matches("pets"));
+    }
+
+    #[test]
+    fn inconsistent_casing() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+tags: [{ name: pets }, { name: Stores }]
+paths:
+  /pets:
+    get: { operationId: listPets, tags: [pets], responses: {} }
+    post: { operationId: create_pet, responses: {} }
+  /pets/{id}:
+    get: { operationId: getPet, responses: {} }
+    delete: { operationId: deletePet, responses: {} }
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name: { type: string }
+        birthDate: { type: string }
+        owner:
+          type: object
+          properties: { first_name: { type: string }, lastName: { type: string } }
+    pet_list: { type: array, items: { $ref: '#/components/schemas/Pet' } }
+    Error: { type: object }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let warnings = spec.check_for_inconsistent_casing();
+        assert_eq!(
+            warnings,
+            [
+                CasingWarning {
+                    category: NameCategory::OperationId,
+                    name: "create_pet".to_owned(),
+                    style: Some(CasingStyle::SnakeCase),
+                    expected: CasingStyle::CamelCase,
+                },
+                CasingWarning {
+                    category: NameCategory::SchemaName,
+                    name: "pet_list".to_owned(),
+                    style: Some(CasingStyle::SnakeCase),
+                    expected: CasingStyle::PascalCase,
+                },
+                CasingWarning {
+                    category: NameCategory::PropertyName,
+                    name: "first_name".to_owned(),
+                    style: Some(CasingStyle::SnakeCase),
+                    expected: CasingStyle::CamelCase,
+                },
+                CasingWarning {
+                    category: NameCategory::TagName,
+                    name: "pets".to_owned(),
+                    style: None,
+                    expected: CasingStyle::PascalCase,
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "operation ID `create_pet` is not camelCase like most others"
+        );
+    }
+
+    #[test]
+    fn casing_requires_majority() {
+        let spec = |operation_ids: &[&str]| {
+            let paths = operation_ids
+                .iter()
+                .map(|id| {
+                    format!(
+                        "  /{}:\n    get: {{ operationId: {}, responses: {{}} }}\n",
+                        id, id
+                    )
+                })
+                .collect::<String>();
+
+            crate::from_reader(
+                format!(
+                    "openapi: \"3.1.0\"\ninfo: {{ title: Pets, version: \"1\" }}\npaths:\n{}",
+                    paths
+                )
+                .as_bytes(),
+            )
+            .unwrap()
+        };
+
+        // no style is followed by more than half of the names
+        let split = spec(&[
+            "listPets",
+            "getPet",
+            "deletePet",
+            "create_pet",
+            "update_pet",
+            "ReplacePet",
+            "PatchPet",
+        ]);
+        assert!(split.check_for_inconsistent_casing().is_empty());
+
+        let split = spec(&[
+            "listPets",
+            "getPet",
+            "deletePet",
+            "create_pet",
+            "update_pet",
+        ]);
+        assert!(split
+            .check_for_inconsistent_casing_with_threshold(2.0 / 3.0)
+            .is_empty());
+
+        let names = split
+            .check_for_inconsistent_casing()
+            .into_iter()
+            .map(|warning| warning.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["create_pet", "update_pet"]);
+    }
+}
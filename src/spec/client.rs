@@ -0,0 +1,885 @@
+//! Client interface stubs generated from specs.
+
+use std::fmt::Write as _;
+
+use super::{ObjectOrReference, OperationRef, ParamLoc, Ref, RefType, Schema, SchemaType, Spec};
+
+/// Target language of [`Spec::generate_client_interface`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientLang {
+    /// A trait with one method per operation.
+    Rust,
+
+    /// A `fetch`-based class with one async method per operation.
+    Typescript,
+}
+
+/// A method parameter of a generated client.
+struct ClientParam {
+    /// Name in the spec, e.g. `X-Request-Id`.
+    name: String,
+    location: ParamLoc,
+    schema: Option<ObjectOrReference<Schema>>,
+    required: bool,
+}
+
+/// An operation, as described to a generated client.
+struct ClientMethod<'a> {
+    op: OperationRef<'a>,
+
+    /// Operation ID, or else the method and path, split into words.
+    words: Vec<String>,
+
+    params: Vec<ClientParam>,
+
+    /// Media types and schemas of the request body.
+    body: Vec<(String, Option<ObjectOrReference<Schema>>)>,
+    body_required: bool,
+
+    /// Schema of the first success response with content.
+    response: Option<ObjectOrReference<Schema>>,
+}
+
+impl Spec {
+    /// Generates the interface of a client for this API.
+    ///
+    /// This is not a full client, but the shape of one for engineers to implement against.
+    /// Methods are named after operation IDs, or else their method and path, and take the
+    /// operation's parameters and request body. They return the schema of the first success
+    /// response with content. Component schemas are referred to by name and are not generated.
+    ///
+    /// Request bodies with several media types are taken as an enum in Rust, and through
+    /// overloads taking the media type in TypeScript. Unresolvable parameters, request bodies and
+    /// responses are skipped.
+    pub fn generate_client_interface(&self, lang: ClientLang) -> String {
+        let name = format!("{}Client", pascal_case(&split_words(&self.info.title)));
+        let methods = self.client_methods();
+
+        match lang {
+            ClientLang::Rust => rust_interface(&name, &methods, self),
+            ClientLang::Typescript => typescript_interface(&name, &methods, self),
+        }
+    }
+
+    fn client_methods(&self) -> Vec<ClientMethod<'_>> {
+        self.operation_refs()
+            .map(|op| {
+                let words = match &op.operation.operation_id {
+                    Some(id) => split_words(id),
+                    None => split_words(&format!("{} {}", op.method, op.path)),
+                };
+
+                let params = op
+                    .path_item
+                    .effective_parameters(op.operation, self)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|param| ClientParam {
+                        required: param.location == ParamLoc::Path
+                            || param.required.unwrap_or(false),
+                        name: param.name,
+                        location: param.location,
+                        schema: param.schema,
+                    })
+                    .collect::<Vec<_>>();
+
+                let body = op
+                    .operation
+                    .request_body
+                    .as_ref()
+                    .and_then(|body| body.resolve(self).ok());
+                let body_required = body
+                    .as_ref()
+                    .map_or(false, |body| body.required.unwrap_or(false));
+                let body = body
+                    .into_iter()
+                    .flat_map(|body| body.content)
+                    .map(|(media_type, content)| (media_type, content.schema))
+                    .collect();
+
+                let response = op
+                    .operation
+                    .responses(self)
+                    .into_iter()
+                    .filter(|(code, _)| code.starts_with('2'))
+                    .find_map(|(_, response)| {
+                        response
+                            .content
+                            .into_values()
+                            .find_map(|content| content.schema)
+                    });
+
+                ClientMethod {
+                    op,
+                    words,
+                    params,
+                    body,
+                    body_required,
+                    response,
+                }
+            })
+            .collect()
+    }
+}
+
+fn rust_interface(name: &str, methods: &[ClientMethod<'_>], spec: &Spec) -> String {
+    let mut out = String::new();
+    let mut body_enums = String::new();
+
+    writeln!(out, "pub trait {} {{", name).unwrap();
+    writeln!(out, "    type Error;").unwrap();
+
+    for method in methods {
+        let fn_name = rust_ident(&snake_case(&method.words));
+
+        let mut args = vec!["&self".to_owned()];
+
+        for param in required_first(method) {
+            match param {
+                Arg::Param(param) => {
+                    let ty = param
+                        .schema
+                        .as_ref()
+                        .map_or_else(|| "String".to_owned(), |schema| rust_oor_type(schema, spec));
+                    let ty = if param.required {
+                        ty
+                    } else {
+                        format!("Option<{}>", ty)
+                    };
+
+                    args.push(format!("{}: {}", rust_param_ident(&param.name), ty));
+                }
+                Arg::Body => {
+                    let ty = match method.body.as_slice() {
+                        [(_, schema)] => rust_type_of(schema.as_ref(), spec),
+                        body => {
+                            let enum_name = format!("{}Body", pascal_case(&method.words));
+
+                            writeln!(body_enums, "\npub enum {} {{", enum_name).unwrap();
+                            let mut variants = Vec::<String>::new();
+                            for (index, (media_type, schema)) in body.iter().enumerate() {
+                                // media types without words (e.g. `*/*`) are numbered, and
+                                // colliding ones are told apart by their position
+                                let mut variant = pascal_case(&split_words(media_type));
+                                if variant.is_empty() {
+                                    variant = format!("Variant{}", index + 1);
+                                }
+                                while variants.contains(&variant) {
+                                    variant = format!("{}{}", variant, index + 1);
+                                }
+
+                                writeln!(
+                                    body_enums,
+                                    "    {}({}),",
+                                    variant,
+                                    rust_type_of(schema.as_ref(), spec)
+                                )
+                                .unwrap();
+                                variants.push(variant);
+                            }
+                            writeln!(body_enums, "}}").unwrap();
+
+                            enum_name
+                        }
+                    };
+                    let ty = if method.body_required {
+                        ty
+                    } else {
+                        format!("Option<{}>", ty)
+                    };
+
+                    args.push(format!("body: {}", ty));
+                }
+                Arg::ContentType => {}
+            }
+        }
+
+        let ret = method
+            .response
+            .as_ref()
+            .map_or_else(|| "()".to_owned(), |schema| rust_oor_type(schema, spec));
+
+        writeln!(out).unwrap();
+        if let Some(summary) = &method.op.operation.summary {
+            writeln!(out, "    /// {}", summary).unwrap();
+        }
+        writeln!(
+            out,
+            "    fn {}({}) -> Result<{}, Self::Error>;",
+            fn_name,
+            args.join(", "),
+            ret
+        )
+        .unwrap();
+    }
+
+    writeln!(out, "}}").unwrap();
+    out.push_str(&body_enums);
+
+    out
+}
+
+fn typescript_interface(name: &str, methods: &[ClientMethod<'_>], spec: &Spec) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "export class {} {{", name).unwrap();
+    writeln!(out, "  constructor(private readonly baseUrl: string) {{}}").unwrap();
+
+    for method in methods {
+        let fn_name = leading_letter(camel_case(&method.words));
+
+        let body_type = |schema: Option<&ObjectOrReference<Schema>>| {
+            schema.map_or_else(|| "unknown".to_owned(), |schema| ts_oor_type(schema, spec))
+        };
+        let optional = |required: bool| if required { "" } else { "?" };
+
+        // each overload's arguments, along with the combined arguments of the implementation
+        let mut overloads = match method.body.len() {
+            0 | 1 => vec![],
+            len => vec![vec![]; len],
+        };
+        let mut args = vec![];
+
+        for arg in required_first(method) {
+            match arg {
+                Arg::Param(param) => {
+                    let ty = param
+                        .schema
+                        .as_ref()
+                        .map_or_else(|| "string".to_owned(), |schema| ts_oor_type(schema, spec));
+                    let arg = format!(
+                        "{}{}: {}",
+                        ts_param_ident(&param.name),
+                        optional(param.required),
+                        ty
+                    );
+
+                    for overload in &mut overloads {
+                        overload.push(arg.clone());
+                    }
+                    args.push(arg);
+                }
+                Arg::ContentType => {
+                    for (overload, (media_type, _)) in overloads.iter_mut().zip(&method.body) {
+                        overload.push(format!("contentType: \"{}\"", media_type));
+                    }
+
+                    let media_types = method
+                        .body
+                        .iter()
+                        .map(|(media_type, _)| format!("\"{}\"", media_type))
+                        .collect::<Vec<_>>();
+                    args.push(format!("contentType: {}", media_types.join(" | ")));
+                }
+                Arg::Body => {
+                    let optional = optional(method.body_required);
+
+                    for (overload, (_, schema)) in overloads.iter_mut().zip(&method.body) {
+                        overload.push(format!("body{}: {}", optional, body_type(schema.as_ref())));
+                    }
+
+                    let mut types = method
+                        .body
+                        .iter()
+                        .map(|(_, schema)| body_type(schema.as_ref()))
+                        .collect::<Vec<_>>();
+                    types.dedup();
+                    args.push(format!("body{}: {}", optional, types.join(" | ")));
+                }
+            }
+        }
+
+        let ret = method
+            .response
+            .as_ref()
+            .map_or_else(|| "void".to_owned(), |schema| ts_oor_type(schema, spec));
+
+        writeln!(out).unwrap();
+        if let Some(summary) = &method.op.operation.summary {
+            writeln!(out, "  /** {} */", summary).unwrap();
+        }
+        for overload in &overloads {
+            writeln!(
+                out,
+                "  {}({}): Promise<{}>;",
+                fn_name,
+                overload.join(", "),
+                ret
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "  async {}({}): Promise<{}> {{",
+            fn_name,
+            args.join(", "),
+            ret
+        )
+        .unwrap();
+
+        let mut path = method.op.path.to_owned();
+        let mut setup = vec![];
+
+        // locals are prefixed with `_` so as not to collide with parameters
+        for param in &method.params {
+            let arg = ts_param_ident(&param.name);
+
+            let set = match param.location {
+                ParamLoc::Path => {
+                    path = path.replace(
+                        &format!("{{{}}}", param.name),
+                        &format!("${{encodeURIComponent(String({}))}}", arg),
+                    );
+                    continue;
+                }
+                ParamLoc::Query => format!("_query.set(\"{}\", String({}));", param.name, arg),
+                ParamLoc::Header => format!("_headers[\"{}\"] = String({});", param.name, arg),
+                ParamLoc::Cookie => format!("_cookies.push(`{}=${{{}}}`);", param.name, arg),
+            };
+
+            if param.required {
+                setup.push(set);
+            } else {
+                setup.push(format!("if ({} !== undefined) {}", arg, set));
+            }
+        }
+
+        writeln!(out, "    const _query = new URLSearchParams();").unwrap();
+        writeln!(out, "    const _headers: Record<string, string> = {{}};").unwrap();
+        let has_cookies = method
+            .params
+            .iter()
+            .any(|param| param.location == ParamLoc::Cookie);
+        if has_cookies {
+            writeln!(out, "    const _cookies: string[] = [];").unwrap();
+        }
+        for line in setup {
+            writeln!(out, "    {}", line).unwrap();
+        }
+        if has_cookies {
+            writeln!(
+                out,
+                "    if (_cookies.length > 0) _headers[\"Cookie\"] = _cookies.join(\"; \");"
+            )
+            .unwrap();
+        }
+
+        let body = match method.body.as_slice() {
+            [] => "",
+            [(media_type, _)] => {
+                writeln!(out, "    _headers[\"Content-Type\"] = \"{}\";", media_type).unwrap();
+                writeln!(
+                    out,
+                    "    const _payload = this.encode(body, \"{}\");",
+                    media_type
+                )
+                .unwrap();
+                ", body: _payload"
+            }
+            _ => {
+                writeln!(out, "    _headers[\"Content-Type\"] = contentType;").unwrap();
+                writeln!(out, "    const _payload = this.encode(body, contentType);").unwrap();
+                ", body: _payload"
+            }
+        };
+
+        writeln!(
+            out,
+            "    const _response = await fetch(`${{this.baseUrl}}{}?${{_query}}`, {{ method: \"{}\", headers: _headers{} }});",
+            path, method.op.method, body
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    if (!_response.ok) throw new Error(`{} {} failed: ${{_response.status}}`);",
+            method.op.method, method.op.path
+        )
+        .unwrap();
+
+        if method.response.is_some() {
+            writeln!(out, "    return _response.json();").unwrap();
+        }
+
+        writeln!(out, "  }}").unwrap();
+    }
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "  private encode(body: unknown, contentType: string): BodyInit | undefined {{"
+    )
+    .unwrap();
+    writeln!(out, "    if (body === undefined) return undefined;").unwrap();
+    writeln!(
+        out,
+        "    return contentType.includes(\"json\") ? JSON.stringify(body) : (body as BodyInit);"
+    )
+    .unwrap();
+    writeln!(out, "  }}").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    out
+}
+
+/// An argument of a generated client method.
+enum Arg<'a> {
+    Param(&'a ClientParam),
+
+    /// The media type of a request body with several media types.
+    ContentType,
+
+    Body,
+}
+
+/// Orders the arguments of a method so that optional ones come last.
+fn required_first<'a>(method: &'a ClientMethod<'_>) -> Vec<Arg<'a>> {
+    let (required, optional) = method
+        .params
+        .iter()
+        .partition::<Vec<_>, _>(|param| param.required);
+
+    let mut args = required.into_iter().map(Arg::Param).collect::<Vec<_>>();
+
+    if method.body.len() > 1 {
+        args.push(Arg::ContentType);
+    }
+
+    if !method.body.is_empty() {
+        args.push(Arg::Body);
+    }
+
+    args.extend(optional.into_iter().map(Arg::Param));
+    args
+}
+
+/// Returns the type name of the component schema a reference points to, if it does.
+fn schema_ref_name(ref_path: &str) -> Option<String> {
+    match ref_path.parse::<Ref>() {
+        Ok(r) if r.kind() == RefType::Schema => Some(pascal_case(&split_words(r.name()))),
+        _ => None,
+    }
+}
+
+fn rust_type_of(schema: Option<&ObjectOrReference<Schema>>, spec: &Spec) -> String {
+    schema.map_or_else(
+        || "serde_json::Value".to_owned(),
+        |schema| rust_oor_type(schema, spec),
+    )
+}
+
+fn rust_oor_type(oor: &ObjectOrReference<Schema>, spec: &Spec) -> String {
+    let ref_path = match oor {
+        ObjectOrReference::Object(schema) => return rust_type(schema, spec),
+        ObjectOrReference::Ref { ref_path } | ObjectOrReference::SchemaRef { ref_path, .. } => {
+            ref_path
+        }
+    };
+
+    if let Some(name) = schema_ref_name(ref_path) {
+        return name;
+    }
+
+    match oor.resolve(spec) {
+        Ok(schema) => rust_type(&schema, spec),
+        Err(_) => "serde_json::Value".to_owned(),
+    }
+}
+
+fn rust_type(schema: &Schema, spec: &Spec) -> String {
+    if let Some(ty) = schema.as_rust_primitive_type() {
+        return ty.to_owned();
+    }
+
+    match schema.schema_type {
+        Some(SchemaType::String) => "String".to_owned(),
+        Some(SchemaType::Array) => format!("Vec<{}>", rust_type_of(schema.items.as_deref(), spec)),
+        Some(SchemaType::Null) => "()".to_owned(),
+        _ => "serde_json::Value".to_owned(),
+    }
+}
+
+fn ts_oor_type(oor: &ObjectOrReference<Schema>, spec: &Spec) -> String {
+    let ref_path = match oor {
+        ObjectOrReference::Object(schema) => return ts_type(schema, spec),
+        ObjectOrReference::Ref { ref_path } | ObjectOrReference::SchemaRef { ref_path, .. } => {
+            ref_path
+        }
+    };
+
+    if let Some(name) = schema_ref_name(ref_path) {
+        return name;
+    }
+
+    match oor.resolve(spec) {
+        Ok(schema) => ts_type(&schema, spec),
+        Err(_) => "unknown".to_owned(),
+    }
+}
+
+fn ts_type(schema: &Schema, spec: &Spec) -> String {
+    match schema.schema_type {
+        Some(SchemaType::Boolean) => "boolean".to_owned(),
+        Some(SchemaType::Integer | SchemaType::Number) => "number".to_owned(),
+        Some(SchemaType::String) => "string".to_owned(),
+        Some(SchemaType::Array) => match &schema.items {
+            Some(items) => format!("{}[]", ts_oor_type(items, spec)),
+            None => "unknown[]".to_owned(),
+        },
+        Some(SchemaType::Object) => "Record<string, unknown>".to_owned(),
+        Some(SchemaType::Null) => "null".to_owned(),
+        None => "unknown".to_owned(),
+    }
+}
+
+/// Splits a name into lowercase words at non-alphanumeric characters and lower-to-upper case
+/// changes, e.g. `getPetById` or `GET /pets/{id}`.
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut prev_lower = false;
+
+    for c in name.chars() {
+        let boundary = !c.is_ascii_alphanumeric() || (prev_lower && c.is_ascii_uppercase());
+
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+
+        if c.is_ascii_alphanumeric() {
+            word.push(c.to_ascii_lowercase());
+        }
+
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn snake_case(words: &[String]) -> String {
+    words.join("_")
+}
+
+fn pascal_case(words: &[String]) -> String {
+    words
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn camel_case(words: &[String]) -> String {
+    match words.split_first() {
+        Some((first, rest)) => first.clone() + &pascal_case(rest),
+        None => String::new(),
+    }
+}
+
+/// Prefixes names that do not start with a letter, e.g. `2fa`, with `_`.
+fn leading_letter(name: String) -> String {
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("_{}", name)
+    }
+}
+
+/// Escapes Rust keywords, which are likely parameter names, as raw identifiers. Keywords that
+/// cannot be raw identifiers get a `_` suffix.
+fn rust_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+    let name = leading_letter(name.to_owned());
+
+    if NON_RAW_KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+/// Returns the Rust argument name of a parameter, which must not collide with `body`.
+fn rust_param_ident(name: &str) -> String {
+    let ident = rust_ident(&snake_case(&split_words(name)));
+
+    if ident == "body" {
+        format!("{}_", ident)
+    } else {
+        ident
+    }
+}
+
+/// Returns the TypeScript argument name of a parameter, with a `_` suffix for reserved words
+/// and names used by generated methods.
+fn ts_param_ident(name: &str) -> String {
+    const RESERVED: &[&str] = &[
+        "arguments",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "debugger",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "eval",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "typeof",
+        "var",
+        "void",
+        "while",
+        "with",
+        "yield",
+        // arguments and globals of generated methods
+        "body",
+        "contentType",
+        "encodeURIComponent",
+        "fetch",
+    ];
+
+    let ident = leading_letter(camel_case(&split_words(name)));
+
+    if RESERVED.contains(&ident.as_str()) {
+        format!("{}_", ident)
+    } else {
+        ident
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"openapi: "3.1.0"
+info: { title: Pet Store, version: "1" }
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      summary: Lists pets.
+      parameters:
+      - { name: limit, in: query, schema: { type: integer, format: int32 } }
+      - { name: type, in: query, required: true, schema: { type: string } }
+      responses:
+        "200":
+          description: Pets.
+          content:
+            application/json:
+              schema: { type: array, items: { $ref: '#/components/schemas/Pet' } }
+    post:
+      operationId: createPet
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: { $ref: '#/components/schemas/Pet' }
+          text/plain:
+            schema: { type: string }
+      responses:
+        "201": { description: Created }
+  /pets/{petId}:
+    delete:
+      parameters:
+      - { name: petId, in: path, required: true, schema: { type: integer } }
+      - { name: X-Request-Id, in: header, schema: { type: string } }
+      responses:
+        "204": { description: Deleted }
+components:
+  schemas:
+    Pet: { type: object }
+"#;
+
+    #[test]
+    fn rust_client_interface() {
+        let spec = crate::from_reader(SPEC.as_bytes()).unwrap();
+
+        assert_eq!(
+            spec.generate_client_interface(ClientLang::Rust),
+            "pub trait PetStoreClient {
+    type Error;
+
+    /// Lists pets.
+    fn list_pets(&self, r#type: String, limit: Option<i32>) -> Result<Vec<Pet>, Self::Error>;
+
+    fn create_pet(&self, body: CreatePetBody) -> Result<(), Self::Error>;
+
+    fn delete_pets_pet_id(&self, pet_id: i64, x_request_id: Option<String>) -> Result<(), Self::Error>;
+}
+
+pub enum CreatePetBody {
+    ApplicationJson(Pet),
+    TextPlain(String),
+}
+"
+        );
+    }
+
+    #[test]
+    fn typescript_client_interface() {
+        let spec = crate::from_reader(SPEC.as_bytes()).unwrap();
+
+        let ts = spec.generate_client_interface(ClientLang::Typescript);
+        assert!(ts.starts_with(
+            "export class PetStoreClient {
+  constructor(private readonly baseUrl: string) {}
+
+  /** Lists pets. */
+  async listPets(type: string, limit?: number): Promise<Pet[]> {
+    const _query = new URLSearchParams();
+    const _headers: Record<string, string> = {};
+    if (limit !== undefined) _query.set(\"limit\", String(limit));
+    _query.set(\"type\", String(type));
+"
+        ));
+        assert!(ts.contains(
+            "
+  createPet(contentType: \"application/json\", body: Pet): Promise<void>;
+  createPet(contentType: \"text/plain\", body: string): Promise<void>;
+  async createPet(contentType: \"application/json\" | \"text/plain\", body: Pet | string): Promise<void> {
+"
+        ));
+        assert!(ts.contains(
+            "
+  async deletePetsPetId(petId: number, xRequestId?: string): Promise<void> {
+"
+        ));
+        assert!(ts.contains(
+            "fetch(`${this.baseUrl}/pets/${encodeURIComponent(String(petId))}?${_query}`, { method: \"DELETE\", headers: _headers });"
+        ));
+    }
+
+    #[test]
+    fn word_splitting() {
+        assert_eq!(split_words("getPetByID"), ["get", "pet", "by", "id"]);
+        assert_eq!(
+            split_words("GET /pets/{petId}"),
+            ["get", "pets", "pet", "id"]
+        );
+        assert_eq!(split_words("X-Request-Id"), ["x", "request", "id"]);
+        assert_eq!(camel_case(&split_words("list_pets")), "listPets");
+    }
+
+    #[test]
+    fn colliding_parameter_names() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Api, version: "1" }
+paths:
+  /items:
+    post:
+      operationId: createItem
+      parameters:
+      - { name: query, in: query, required: true, schema: { type: string } }
+      - { name: headers, in: query, required: true, schema: { type: string } }
+      - { name: body, in: query, required: true, schema: { type: string } }
+      - { name: return, in: query, required: true, schema: { type: string } }
+      - { name: self, in: query, required: true, schema: { type: string } }
+      - { name: class, in: query, required: true, schema: { type: string } }
+      - { name: 1st, in: query, required: true, schema: { type: string } }
+      requestBody:
+        required: true
+        content:
+          application/json: { schema: { type: string } }
+      responses:
+        "204": { description: Created }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert!(spec.generate_client_interface(ClientLang::Rust).contains(
+            "fn create_item(&self, query: String, headers: String, body_: String, \
+                 r#return: String, self_: String, class: String, _1st: String, body: String)"
+        ));
+
+        let ts = spec.generate_client_interface(ClientLang::Typescript);
+        assert!(ts.contains(
+            "async createItem(query: string, headers: string, body_: string, return_: string, \
+             self: string, class_: string, _1st: string, body: string): Promise<void> {"
+        ));
+        assert!(ts.contains("_query.set(\"query\", String(query));"));
+        assert!(ts.contains("_query.set(\"1st\", String(_1st));"));
+        assert!(ts.contains("const _payload = this.encode(body, \"application/json\");"));
+    }
+
+    #[test]
+    fn body_variant_names() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Api, version: "1" }
+paths:
+  /files:
+    put:
+      operationId: uploadFile
+      requestBody:
+        content:
+          application/vnd.api+json: { schema: { type: object } }
+          application/vnd-api+json: { schema: { type: object } }
+          "*/*": { schema: { type: string } }
+      responses:
+        "204": { description: Uploaded }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert!(spec.generate_client_interface(ClientLang::Rust).contains(
+            "
+pub enum UploadFileBody {
+    Variant1(String),
+    ApplicationVndApiJson(serde_json::Value),
+    ApplicationVndApiJson3(serde_json::Value),
+}
+"
+        ));
+    }
+}
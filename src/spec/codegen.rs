@@ -0,0 +1,204 @@
+//! Helpers for generating Rust code from schemas.
+
+use super::{KnownFormat, Schema, SchemaType};
+
+/// Describes how a schema is used as a struct field, for [`Schema::to_serde_derive_attrs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SerdeAttrConfig {
+    /// Name of the property in JSON, e.g. `createdAt`.
+    pub json_name: String,
+
+    /// Whether the property is listed in the parent schema's `required`.
+    pub required: bool,
+
+    /// Other JSON names to accept when deserializing.
+    pub aliases: Vec<String>,
+
+    /// Path of a function returning the schema's `default`, e.g. `default_page_size`.
+    pub default_fn: Option<String>,
+}
+
+impl SerdeAttrConfig {
+    /// Creates a config for an optional property with the given JSON name.
+    pub fn new(json_name: impl Into<String>) -> Self {
+        Self {
+            json_name: json_name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets whether the property is required.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Adds a JSON name to accept when deserializing.
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Sets the path of a function returning the schema's `default`.
+    pub fn default_fn(mut self, default_fn: impl Into<String>) -> Self {
+        self.default_fn = Some(default_fn.into());
+        self
+    }
+}
+
+impl Schema {
+    /// Returns the `#[serde(...)]` attributes for a struct field named `field_name` that holds
+    /// values of this schema.
+    ///
+    /// A `rename` is added if the field name differs from the JSON name (e.g., `created_at` for
+    /// `createdAt`), ignoring a raw identifier's `r#` prefix. Optional properties are expected
+    /// to be `Option` fields, which skip serializing `None`, unless the schema has a `default`
+    /// and the config a [`default_fn`](SerdeAttrConfig::default_fn), in which case they are
+    /// expected to be plain fields filled by that function when missing.
+    pub fn to_serde_derive_attrs(&self, field_name: &str, config: &SerdeAttrConfig) -> Vec<String> {
+        let mut attrs = vec![];
+
+        let field_name = field_name.strip_prefix("r#").unwrap_or(field_name);
+        if field_name != config.json_name {
+            attrs.push(format!("#[serde(rename = {:?})]", config.json_name));
+        }
+
+        for alias in &config.aliases {
+            attrs.push(format!("#[serde(alias = {:?})]", alias));
+        }
+
+        if !config.required {
+            match (&self.default, &config.default_fn) {
+                (Some(_), Some(default_fn)) => {
+                    attrs.push(format!("#[serde(default = {:?})]", default_fn));
+                }
+                _ => {
+                    attrs.push("#[serde(skip_serializing_if = \"Option::is_none\")]".to_owned());
+                }
+            }
+        }
+
+        attrs
+    }
+
+    /// Returns the Rust primitive type best suited to values of this schema, based on its `type`
+    /// and `format`, e.g. `i32` for `int32` integers.
+    ///
+    /// Integers and numbers without a format map to `i64` and `f64`. Returns `None` for strings,
+    /// arrays, objects and formats without a primitive equivalent, such as `date-time`, which
+    /// code generators have to handle themselves. Nullability is not taken into account.
+    pub fn as_rust_primitive_type(&self) -> Option<&'static str> {
+        let ty = match (self.schema_type?, &self.format) {
+            (SchemaType::Boolean, None) => "bool",
+            (SchemaType::Integer, None | Some(KnownFormat::Int64)) => "i64",
+            (SchemaType::Integer, Some(KnownFormat::Int32)) => "i32",
+            (SchemaType::Number, None | Some(KnownFormat::Double)) => "f64",
+            (SchemaType::Number, Some(KnownFormat::Float)) => "f32",
+            _ => return None,
+        };
+
+        Some(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn serde_attrs() {
+        let string = Schema::string();
+
+        assert_eq!(
+            string.to_serde_derive_attrs("created_at", &SerdeAttrConfig::new("createdAt")),
+            [
+                "#[serde(rename = \"createdAt\")]",
+                "#[serde(skip_serializing_if = \"Option::is_none\")]",
+            ]
+        );
+
+        assert!(string
+            .to_serde_derive_attrs("name", &SerdeAttrConfig::new("name").required(true))
+            .is_empty());
+
+        assert_eq!(
+            string.to_serde_derive_attrs(
+                "r#type",
+                &SerdeAttrConfig::new("type").required(true).alias("kind")
+            ),
+            ["#[serde(alias = \"kind\")]"]
+        );
+
+        let with_default = Schema {
+            default: Some(json!(10)),
+            ..Schema::integer()
+        };
+        assert_eq!(
+            with_default.to_serde_derive_attrs(
+                "page_size",
+                &SerdeAttrConfig::new("pageSize").default_fn("default_page_size")
+            ),
+            [
+                "#[serde(rename = \"pageSize\")]",
+                "#[serde(default = \"default_page_size\")]",
+            ]
+        );
+        assert_eq!(
+            with_default.to_serde_derive_attrs("page_size", &SerdeAttrConfig::new("page_size")),
+            ["#[serde(skip_serializing_if = \"Option::is_none\")]"]
+        );
+
+        assert_eq!(
+            string.to_serde_derive_attrs(
+                "quote",
+                &SerdeAttrConfig::new(r#"say "hi"\"#).required(true)
+            ),
+            [r#"#[serde(rename = "say \"hi\"\\")]"#]
+        );
+    }
+
+    #[test]
+    fn rust_primitive_types() {
+        let with_format = |schema: Schema, format: &str| Schema {
+            format: Some(KnownFormat::from(format.to_owned())),
+            ..schema
+        };
+
+        assert_eq!(Schema::boolean().as_rust_primitive_type(), Some("bool"));
+        assert_eq!(Schema::integer().as_rust_primitive_type(), Some("i64"));
+        assert_eq!(
+            with_format(Schema::integer(), "int32").as_rust_primitive_type(),
+            Some("i32")
+        );
+        assert_eq!(
+            with_format(Schema::integer(), "int64").as_rust_primitive_type(),
+            Some("i64")
+        );
+        assert_eq!(
+            with_format(Schema::number(), "float").as_rust_primitive_type(),
+            Some("f32")
+        );
+        assert_eq!(
+            with_format(Schema::number(), "double").as_rust_primitive_type(),
+            Some("f64")
+        );
+        assert_eq!(Schema::number().as_rust_primitive_type(), Some("f64"));
+        assert_eq!(
+            with_format(Schema::string(), "date-time").as_rust_primitive_type(),
+            None
+        );
+        assert_eq!(Schema::string().as_rust_primitive_type(), None);
+        assert_eq!(
+            with_format(Schema::integer(), "uint8").as_rust_primitive_type(),
+            None
+        );
+        assert_eq!(Schema::default().as_rust_primitive_type(), None);
+
+        for type_name in ["bool", "i32", "i64", "f32", "f64"] {
+            let schema = Schema::from_type_name(type_name).unwrap();
+            assert_eq!(schema.as_rust_primitive_type(), Some(type_name));
+        }
+    }
+}
@@ -0,0 +1,1160 @@
+//! Backward compatibility checks between two versions of a spec.
+
+use std::{collections::BTreeMap, fmt};
+
+use derive_more::Display;
+use serde::Serialize;
+
+use super::{
+    diff::operations_by_name, ChangeKind, DiffTarget, MediaType, NumericBound, ObjectOrReference,
+    OperationRef, ParamLoc, Parameter, Schema, SchemaType, Server, Severity, Spec,
+};
+
+/// Kind of change found by [`Spec::check_backward_compatibility`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompatChangeKind {
+    #[display(fmt = "path removed")]
+    PathRemoved,
+
+    #[display(fmt = "operation removed")]
+    OperationRemoved,
+
+    /// A parameter was added as required, or an optional parameter became required.
+    #[display(fmt = "required parameter added")]
+    RequiredParameterAdded,
+
+    /// A parameter was removed, which breaks clients if the server rejects unknown parameters.
+    #[display(fmt = "parameter removed")]
+    ParameterRemoved,
+
+    /// A parameter or request body accepts fewer values, because of a narrower type, removed
+    /// enum values or tighter constraints.
+    #[display(fmt = "request type narrowed")]
+    RequestTypeNarrowed,
+
+    /// A request body field was added as required, or an optional field became required.
+    #[display(fmt = "required request field added")]
+    RequiredRequestFieldAdded,
+
+    #[display(fmt = "response field removed")]
+    ResponseFieldRemoved,
+
+    /// A response may contain values of a broader type, or additional enum values.
+    #[display(fmt = "response type broadened")]
+    ResponseTypeBroadened,
+
+    #[display(fmt = "security requirements changed")]
+    SecurityChanged,
+
+    /// A server URL was removed.
+    #[display(fmt = "servers changed")]
+    ServersChanged,
+
+    #[display(fmt = "path added")]
+    PathAdded,
+
+    #[display(fmt = "operation added")]
+    OperationAdded,
+
+    #[display(fmt = "optional parameter added")]
+    OptionalParameterAdded,
+
+    #[display(fmt = "response field added")]
+    ResponseFieldAdded,
+
+    /// A parameter or request body accepts more values, because of a broader type, added enum
+    /// values or looser constraints.
+    #[display(fmt = "constraint relaxed")]
+    ConstraintRelaxed,
+}
+
+impl CompatChangeKind {
+    /// Returns how changes of this kind affect existing clients, using the same [`Severity`] as
+    /// [`SpecDiff::breaking_changes`](super::SpecDiff::breaking_changes), or `None` if they are
+    /// not breaking.
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            Self::PathRemoved
+            | Self::OperationRemoved
+            | Self::RequiredParameterAdded
+            | Self::RequestTypeNarrowed
+            | Self::RequiredRequestFieldAdded
+            | Self::ResponseFieldRemoved => Some(Severity::Error),
+
+            Self::ParameterRemoved
+            | Self::ResponseTypeBroadened
+            | Self::SecurityChanged
+            | Self::ServersChanged => Some(Severity::Warning),
+
+            Self::PathAdded
+            | Self::OperationAdded
+            | Self::OptionalParameterAdded
+            | Self::ResponseFieldAdded
+            | Self::ConstraintRelaxed => None,
+        }
+    }
+
+    /// Returns true if changes of this kind can break existing clients.
+    pub fn is_breaking(&self) -> bool {
+        self.severity().is_some()
+    }
+}
+
+/// A change found by [`Spec::check_backward_compatibility`].
+#[derive(Debug, Clone, PartialEq, Eq, Display, Serialize)]
+#[display(fmt = "{}: {}", location, message)]
+pub struct CompatChange {
+    pub kind: CompatChangeKind,
+
+    /// Where the change was made, e.g. `/pets` for a path, `GET /pets` for an operation, or
+    /// `servers` for the root servers.
+    pub location: String,
+
+    /// Description of the change, e.g. ``required query parameter `owner` added``.
+    pub message: String,
+
+    /// Set if the change can break existing clients.
+    pub severity: Option<Severity>,
+}
+
+/// Changes between two versions of a spec, split by whether they can break existing clients.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BackwardCompatReport {
+    pub breaking: Vec<CompatChange>,
+
+    pub non_breaking: Vec<CompatChange>,
+}
+
+impl BackwardCompatReport {
+    /// Returns true if no breaking changes were found.
+    pub fn is_compatible(&self) -> bool {
+        self.breaking.is_empty()
+    }
+
+    fn push(&mut self, kind: CompatChangeKind, location: &str, message: String) {
+        let change = CompatChange {
+            kind,
+            location: location.to_owned(),
+            message,
+            severity: kind.severity(),
+        };
+
+        if change.severity.is_some() {
+            self.breaking.push(change);
+        } else {
+            self.non_breaking.push(change);
+        }
+    }
+}
+
+impl fmt::Display for BackwardCompatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} breaking, {} non-breaking changes",
+            self.breaking.len(),
+            self.non_breaking.len()
+        )?;
+
+        for change in &self.breaking {
+            write!(f, "\n! {}", change)?;
+        }
+
+        for change in &self.non_breaking {
+            write!(f, "\n  {}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Spec {
+    /// Checks whether clients of a previous version of the spec keep working with this one.
+    ///
+    /// Added and removed paths and operations are those of [`Spec::diff`], with the same
+    /// severities; operations of added or removed paths are not reported separately. Where
+    /// [`Spec::diff`] flags any modified operation as a possible break, this report details the
+    /// changes within it.
+    ///
+    /// Operations present in both versions are compared after resolving references and applying
+    /// parameters inherited from path items. Parameter and request body schemas are checked for
+    /// narrowed types, enums and constraints; response schemas for removed fields, broadened types
+    /// and added enum values. Schemas are compared for media types present in both versions, and
+    /// responses for status codes present in both. Unresolvable items are skipped.
+    pub fn check_backward_compatibility(&self, previous: &Spec) -> BackwardCompatReport {
+        let mut checker = Checker {
+            old: previous,
+            new: self,
+            report: BackwardCompatReport::default(),
+            visiting: vec![],
+        };
+
+        checker.check_servers("servers", &previous.servers, &self.servers);
+
+        let old_ops = operations_by_name(previous);
+        let new_ops = operations_by_name(self);
+
+        for change in previous.diff(self).changes {
+            let kind = match (change.target, change.kind) {
+                (DiffTarget::Path, ChangeKind::Removed) => CompatChangeKind::PathRemoved,
+                (DiffTarget::Path, ChangeKind::Added) => CompatChangeKind::PathAdded,
+                (DiffTarget::Operation, ChangeKind::Removed)
+                    if self.paths.contains_key(old_ops[&change.name].path) =>
+                {
+                    CompatChangeKind::OperationRemoved
+                }
+                (DiffTarget::Operation, ChangeKind::Added)
+                    if previous.paths.contains_key(new_ops[&change.name].path) =>
+                {
+                    CompatChangeKind::OperationAdded
+                }
+                _ => continue,
+            };
+
+            debug_assert_eq!(kind.severity(), change.severity);
+            checker.push(kind, &change.name, kind.to_string());
+        }
+
+        for (name, old_op) in &old_ops {
+            if let Some(new_op) = new_ops.get(name) {
+                checker.check_operation(name, old_op, new_op);
+            }
+        }
+
+        checker.report
+    }
+}
+
+/// Direction in which values described by a schema are sent.
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    Request,
+    Response,
+}
+
+struct Checker<'a> {
+    old: &'a Spec,
+    new: &'a Spec,
+    report: BackwardCompatReport,
+
+    /// Pairs of reference paths being compared, to stop at recursive schemas.
+    visiting: Vec<(Option<String>, Option<String>)>,
+}
+
+impl<'a> Checker<'a> {
+    fn push(&mut self, kind: CompatChangeKind, location: &str, message: String) {
+        self.report.push(kind, location, message);
+    }
+
+    fn check_operation(&mut self, location: &str, old: &OperationRef<'a>, new: &OperationRef<'a>) {
+        self.check_parameters(location, old, new);
+
+        let old_body = old
+            .operation
+            .request_body
+            .as_ref()
+            .and_then(|body| body.resolve(self.old).ok());
+        let new_body = new
+            .operation
+            .request_body
+            .as_ref()
+            .and_then(|body| body.resolve(self.new).ok());
+
+        match (old_body, new_body) {
+            (None, Some(new_body)) if new_body.required == Some(true) => self.push(
+                CompatChangeKind::RequiredRequestFieldAdded,
+                location,
+                "required request body added".to_owned(),
+            ),
+            (Some(old_body), Some(new_body)) => {
+                if new_body.required == Some(true) && old_body.required != Some(true) {
+                    self.push(
+                        CompatChangeKind::RequiredRequestFieldAdded,
+                        location,
+                        "request body is now required".to_owned(),
+                    );
+                }
+
+                self.check_content(
+                    Direction::Request,
+                    location,
+                    "request body",
+                    &old_body.content,
+                    &new_body.content,
+                );
+            }
+            _ => {}
+        }
+
+        for (status, old_response) in &old.operation.responses {
+            let new_response = match new.operation.responses.get(status) {
+                Some(new_response) => new_response,
+                None => continue,
+            };
+
+            if let (Ok(old_response), Ok(new_response)) = (
+                old_response.resolve(self.old),
+                new_response.resolve(self.new),
+            ) {
+                self.check_content(
+                    Direction::Response,
+                    location,
+                    &format!("response {}", status),
+                    &old_response.content,
+                    &new_response.content,
+                );
+            }
+        }
+
+        let old_security = old
+            .operation
+            .security
+            .as_ref()
+            .unwrap_or(&self.old.security);
+        let new_security = new
+            .operation
+            .security
+            .as_ref()
+            .unwrap_or(&self.new.security);
+
+        if old_security != new_security {
+            self.push(
+                CompatChangeKind::SecurityChanged,
+                location,
+                "security requirements changed".to_owned(),
+            );
+        }
+
+        let overrides_servers = |op: &OperationRef<'_>| {
+            !op.operation.servers.is_empty() || !op.path_item.servers.is_empty()
+        };
+
+        if overrides_servers(old) || overrides_servers(new) {
+            let old_servers = effective_servers(self.old, old);
+            let new_servers = effective_servers(self.new, new);
+            self.check_servers(location, old_servers, new_servers);
+        }
+    }
+
+    fn check_parameters(&mut self, location: &str, old: &OperationRef<'a>, new: &OperationRef<'a>) {
+        let old_params = parameters_by_key(self.old, old);
+        let new_params = parameters_by_key(self.new, new);
+
+        for key in old_params.keys() {
+            if !new_params.contains_key(key) {
+                self.push(
+                    CompatChangeKind::ParameterRemoved,
+                    location,
+                    format!("{} parameter `{}` removed", param_location(&key.0), key.1),
+                );
+            }
+        }
+
+        for (key, new_param) in &new_params {
+            let subject = format!("{} parameter `{}`", param_location(&key.0), key.1);
+            let required = new_param.required == Some(true);
+
+            let old_param = match old_params.get(key) {
+                Some(old_param) => old_param,
+                None if required => {
+                    self.push(
+                        CompatChangeKind::RequiredParameterAdded,
+                        location,
+                        format!("required {} added", subject),
+                    );
+                    continue;
+                }
+                None => {
+                    self.push(
+                        CompatChangeKind::OptionalParameterAdded,
+                        location,
+                        format!("optional {} added", subject),
+                    );
+                    continue;
+                }
+            };
+
+            if required && old_param.required != Some(true) {
+                self.push(
+                    CompatChangeKind::RequiredParameterAdded,
+                    location,
+                    format!("{} is now required", subject),
+                );
+            }
+
+            if let (Some(old_schema), Some(new_schema)) = (&old_param.schema, &new_param.schema) {
+                self.check_schema_refs(
+                    Direction::Request,
+                    location,
+                    &subject,
+                    "",
+                    old_schema,
+                    new_schema,
+                );
+            }
+        }
+    }
+
+    fn check_content(
+        &mut self,
+        direction: Direction,
+        location: &str,
+        label: &str,
+        old: &BTreeMap<String, MediaType>,
+        new: &BTreeMap<String, MediaType>,
+    ) {
+        for (media_type, old_media_type) in old {
+            let new_schema = new
+                .get(media_type)
+                .and_then(|new_media_type| new_media_type.schema.as_ref());
+
+            if let (Some(old_schema), Some(new_schema)) = (&old_media_type.schema, new_schema) {
+                let label = format!("{} ({})", label, media_type);
+                self.check_schema_refs(direction, location, &label, "", old_schema, new_schema);
+            }
+        }
+    }
+
+    fn check_schema_refs(
+        &mut self,
+        direction: Direction,
+        location: &str,
+        label: &str,
+        field: &str,
+        old: &ObjectOrReference<Schema>,
+        new: &ObjectOrReference<Schema>,
+    ) {
+        let refs = (
+            old.ref_path().map(str::to_owned),
+            new.ref_path().map(str::to_owned),
+        );
+
+        if refs != (None, None) && self.visiting.contains(&refs) {
+            return;
+        }
+
+        let old = old.resolve(self.old).ok();
+        let new = new.resolve(self.new).ok();
+        let old = old.and_then(|schema| schema.merged_all_of(self.old));
+        let new = new.and_then(|schema| schema.merged_all_of(self.new));
+
+        if let (Some(old), Some(new)) = (old, new) {
+            self.visiting.push(refs);
+            self.check_schemas(direction, location, label, field, &old, &new);
+            self.visiting.pop();
+        }
+    }
+
+    /// Compares schemas, including those of common properties and items. `field` is the path of
+    /// the schema's value within the value described by `label`, or empty for its root.
+    fn check_schemas(
+        &mut self,
+        direction: Direction,
+        location: &str,
+        label: &str,
+        field: &str,
+        old: &Schema,
+        new: &Schema,
+    ) {
+        let subject = if field.is_empty() {
+            label.to_owned()
+        } else {
+            format!("field `{}` of {}", field, label)
+        };
+
+        self.check_types(direction, location, &subject, old, new);
+        self.check_enums(direction, location, &subject, old, new);
+
+        if direction == Direction::Request {
+            self.check_constraints(location, &subject, old, new);
+        }
+
+        let field_path = |name: &str| {
+            if field.is_empty() {
+                name.to_owned()
+            } else {
+                format!("{}.{}", field, name)
+            }
+        };
+
+        match direction {
+            Direction::Request => {
+                for name in new
+                    .required
+                    .iter()
+                    .filter(|name| !old.required.contains(name))
+                {
+                    let message = if old.properties.contains_key(name) {
+                        format!("field `{}` of {} is now required", field_path(name), label)
+                    } else {
+                        format!("required field `{}` added to {}", field_path(name), label)
+                    };
+
+                    self.push(
+                        CompatChangeKind::RequiredRequestFieldAdded,
+                        location,
+                        message,
+                    );
+                }
+            }
+            Direction::Response => {
+                for name in old.properties.keys() {
+                    if !new.properties.contains_key(name) {
+                        self.push(
+                            CompatChangeKind::ResponseFieldRemoved,
+                            location,
+                            format!("field `{}` removed from {}", field_path(name), label),
+                        );
+                    }
+                }
+
+                for name in new.properties.keys() {
+                    if !old.properties.contains_key(name) {
+                        self.push(
+                            CompatChangeKind::ResponseFieldAdded,
+                            location,
+                            format!("field `{}` added to {}", field_path(name), label),
+                        );
+                    }
+                }
+            }
+        }
+
+        for (name, old_prop) in &old.properties {
+            if let Some(new_prop) = new.properties.get(name) {
+                let field = field_path(name);
+                self.check_schema_refs(direction, location, label, &field, old_prop, new_prop);
+            }
+        }
+
+        if let (Some(old_items), Some(new_items)) = (&old.items, &new.items) {
+            let field = format!("{}[]", field);
+            self.check_schema_refs(direction, location, label, &field, old_items, new_items);
+        }
+    }
+
+    fn check_types(
+        &mut self,
+        direction: Direction,
+        location: &str,
+        subject: &str,
+        old: &Schema,
+        new: &Schema,
+    ) {
+        if old.schema_type == new.schema_type {
+            return;
+        }
+
+        let message = format!(
+            "type of {} changed from {} to {}",
+            subject,
+            type_name(old.schema_type),
+            type_name(new.schema_type)
+        );
+
+        match direction {
+            Direction::Request if accepts_type(new.schema_type, old.schema_type) => {
+                self.push(CompatChangeKind::ConstraintRelaxed, location, message)
+            }
+            Direction::Request => {
+                self.push(CompatChangeKind::RequestTypeNarrowed, location, message)
+            }
+            Direction::Response if accepts_type(old.schema_type, new.schema_type) => {}
+            Direction::Response => {
+                self.push(CompatChangeKind::ResponseTypeBroadened, location, message)
+            }
+        }
+    }
+
+    fn check_enums(
+        &mut self,
+        direction: Direction,
+        location: &str,
+        subject: &str,
+        old: &Schema,
+        new: &Schema,
+    ) {
+        let (old_values, new_values) = (&old.enum_values, &new.enum_values);
+
+        if old_values.is_empty() && new_values.is_empty() {
+            return;
+        }
+
+        let list = |values: Vec<&serde_json::Value>| {
+            values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let removed = old_values
+            .iter()
+            .filter(|value| !new_values.contains(value))
+            .collect::<Vec<_>>();
+        let added = new_values
+            .iter()
+            .filter(|value| !old_values.contains(value))
+            .collect::<Vec<_>>();
+
+        match direction {
+            Direction::Request if old_values.is_empty() => self.push(
+                CompatChangeKind::RequestTypeNarrowed,
+                location,
+                format!("{} restricted to {}", subject, list(added)),
+            ),
+            Direction::Request if new_values.is_empty() => self.push(
+                CompatChangeKind::ConstraintRelaxed,
+                location,
+                format!("enum of {} removed", subject),
+            ),
+            Direction::Request => {
+                if !removed.is_empty() {
+                    self.push(
+                        CompatChangeKind::RequestTypeNarrowed,
+                        location,
+                        format!("{} no longer accepts {}", subject, list(removed)),
+                    );
+                }
+
+                if !added.is_empty() {
+                    self.push(
+                        CompatChangeKind::ConstraintRelaxed,
+                        location,
+                        format!("{} now accepts {}", subject, list(added)),
+                    );
+                }
+            }
+            Direction::Response if new_values.is_empty() => self.push(
+                CompatChangeKind::ResponseTypeBroadened,
+                location,
+                format!("enum of {} removed", subject),
+            ),
+            Direction::Response => {
+                if !old_values.is_empty() && !added.is_empty() {
+                    self.push(
+                        CompatChangeKind::ResponseTypeBroadened,
+                        location,
+                        format!("{} may now be {}", subject, list(added)),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_constraints(&mut self, location: &str, subject: &str, old: &Schema, new: &Schema) {
+        let count = |count: Option<u64>| count.map(|count| NumericBound::Integer(count as i64));
+
+        let (old_minimum, old_exclusive_minimum) = old.minimum_bounds();
+        let (new_minimum, new_exclusive_minimum) = new.minimum_bounds();
+        let (old_maximum, old_exclusive_maximum) = old.maximum_bounds();
+        let (new_maximum, new_exclusive_maximum) = new.maximum_bounds();
+
+        // (keyword, old value, new value, whether larger values are tighter)
+        let bounds = [
+            ("minimum", old_minimum, new_minimum, true),
+            (
+                "exclusiveMinimum",
+                old_exclusive_minimum,
+                new_exclusive_minimum,
+                true,
+            ),
+            ("maximum", old_maximum, new_maximum, false),
+            (
+                "exclusiveMaximum",
+                old_exclusive_maximum,
+                new_exclusive_maximum,
+                false,
+            ),
+            (
+                "minLength",
+                count(old.min_length),
+                count(new.min_length),
+                true,
+            ),
+            (
+                "maxLength",
+                count(old.max_length),
+                count(new.max_length),
+                false,
+            ),
+            ("minItems", count(old.min_items), count(new.min_items), true),
+            (
+                "maxItems",
+                count(old.max_items),
+                count(new.max_items),
+                false,
+            ),
+            (
+                "minProperties",
+                count(old.min_properties),
+                count(new.min_properties),
+                true,
+            ),
+            (
+                "maxProperties",
+                count(old.max_properties),
+                count(new.max_properties),
+                false,
+            ),
+        ];
+
+        for (keyword, old_bound, new_bound, larger_is_tighter) in bounds {
+            let (tightened, message) = match (old_bound, new_bound) {
+                (None, Some(new_bound)) => (
+                    true,
+                    format!("{} of {} set to {}", keyword, subject, new_bound),
+                ),
+                (Some(_), None) => (false, format!("{} of {} removed", keyword, subject)),
+                (Some(old_bound), Some(new_bound)) if old_bound != new_bound => (
+                    (new_bound > old_bound) == larger_is_tighter,
+                    format!(
+                        "{} of {} changed from {} to {}",
+                        keyword, subject, old_bound, new_bound
+                    ),
+                ),
+                _ => continue,
+            };
+
+            self.push_constraint_change(location, tightened, message);
+        }
+
+        let pattern_change = match (&old.pattern, &new.pattern) {
+            (None, Some(pattern)) => {
+                Some((true, format!("pattern of {} set to `{}`", subject, pattern)))
+            }
+            (Some(_), None) => Some((false, format!("pattern of {} removed", subject))),
+            (Some(old_pattern), Some(new_pattern)) if old_pattern != new_pattern => Some((
+                true,
+                format!(
+                    "pattern of {} changed from `{}` to `{}`",
+                    subject, old_pattern, new_pattern
+                ),
+            )),
+            _ => None,
+        };
+
+        if let Some((tightened, message)) = pattern_change {
+            self.push_constraint_change(location, tightened, message);
+        }
+    }
+
+    fn push_constraint_change(&mut self, location: &str, tightened: bool, message: String) {
+        let kind = if tightened {
+            CompatChangeKind::RequestTypeNarrowed
+        } else {
+            CompatChangeKind::ConstraintRelaxed
+        };
+
+        self.push(kind, location, message);
+    }
+
+    fn check_servers(&mut self, location: &str, old: &[Server], new: &[Server]) {
+        for server in old {
+            if !new.iter().any(|new_server| new_server.url == server.url) {
+                self.push(
+                    CompatChangeKind::ServersChanged,
+                    location,
+                    format!("server `{}` removed", server.url),
+                );
+            }
+        }
+    }
+}
+
+/// Returns true if every value of type `narrower` is also of type `wider`.
+fn accepts_type(wider: Option<SchemaType>, narrower: Option<SchemaType>) -> bool {
+    match (wider, narrower) {
+        (None, _) => true,
+        (Some(SchemaType::Number), Some(SchemaType::Integer)) => true,
+        (wider, narrower) => wider == narrower,
+    }
+}
+
+fn type_name(schema_type: Option<SchemaType>) -> String {
+    schema_type.map_or_else(|| "any".to_owned(), |schema_type| schema_type.to_string())
+}
+
+fn param_location(location: &ParamLoc) -> &'static str {
+    match location {
+        ParamLoc::Query => "query",
+        ParamLoc::Header => "header",
+        ParamLoc::Path => "path",
+        ParamLoc::Cookie => "cookie",
+    }
+}
+
+fn effective_servers<'a>(spec: &'a Spec, op: &OperationRef<'a>) -> &'a [Server] {
+    [&op.operation.servers, &op.path_item.servers, &spec.servers]
+        .into_iter()
+        .find(|servers| !servers.is_empty())
+        .unwrap_or(&spec.servers)
+}
+
+fn parameters_by_key(
+    spec: &Spec,
+    op: &OperationRef<'_>,
+) -> BTreeMap<(ParamLoc, String), Parameter> {
+    op.path_item
+        .effective_parameters(op.operation, spec)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|param| ((param.location.clone(), param.name.clone()), param))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the report of changes from `old` to `new`, given as the part of a spec following
+    /// its `info`.
+    fn compat(old: &str, new: &str) -> BackwardCompatReport {
+        let spec = |rest: &str| {
+            let yaml = format!(
+                "openapi: \"3.1.0\"\ninfo: {{ title: Pets, version: \"1\" }}\n{}",
+                rest
+            );
+            crate::from_reader(yaml.as_bytes()).unwrap()
+        };
+
+        spec(new).check_backward_compatibility(&spec(old))
+    }
+
+    #[test]
+    fn paths_and_operations() {
+        let report = compat(
+            r#"paths:
+  /pets:
+    get: { responses: {} }
+    delete: { responses: {} }
+  /stores:
+    get: { responses: {} }
+"#,
+            r#"paths:
+  /pets:
+    get: { responses: {} }
+    put: { responses: {} }
+  /owners:
+    get: { responses: {} }
+"#,
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "2 breaking, 2 non-breaking changes
+! /stores: path removed
+! DELETE /pets: operation removed
+  /owners: path added
+  PUT /pets: operation added"
+        );
+        assert_eq!(report.breaking[0].severity, Some(Severity::Error));
+        assert_eq!(report.non_breaking[0].severity, None);
+    }
+
+    #[test]
+    fn parameters() {
+        let report = compat(
+            r#"paths:
+  /pets:
+    get:
+      parameters:
+      - { name: sort, in: query }
+      - { name: limit, in: query }
+      - { name: X-Trace, in: header }
+      responses: {}
+"#,
+            r#"paths:
+  /pets:
+    get:
+      parameters:
+      - { name: limit, in: query, required: true }
+      - { name: owner, in: query, required: true }
+      - { name: page, in: query }
+      responses: {}
+"#,
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "4 breaking, 1 non-breaking changes
+! GET /pets: query parameter `sort` removed
+! GET /pets: header parameter `X-Trace` removed
+! GET /pets: query parameter `limit` is now required
+! GET /pets: required query parameter `owner` added
+  GET /pets: optional query parameter `page` added"
+        );
+        assert_eq!(report.breaking[0].kind, CompatChangeKind::ParameterRemoved);
+        assert_eq!(report.breaking[0].severity, Some(Severity::Warning));
+    }
+
+    #[test]
+    fn type_narrowing() {
+        let report = compat(
+            r#"paths:
+  /pets:
+    get:
+      parameters:
+      - { name: limit, in: query, schema: { type: number } }
+      responses:
+        "200":
+          content:
+            application/json: { schema: { $ref: '#/components/schemas/Pet' } }
+    post:
+      requestBody:
+        content:
+          application/json: { schema: { $ref: '#/components/schemas/Pet' } }
+      responses: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name: { type: string }
+        age: { type: integer }
+        weight: { type: number }
+        nickname: { type: string }
+        parent: { $ref: '#/components/schemas/Pet' }
+"#,
+            r#"paths:
+  /pets:
+    get:
+      parameters:
+      - { name: limit, in: query, schema: { type: integer } }
+      responses:
+        "200":
+          content:
+            application/json: { schema: { $ref: '#/components/schemas/Pet' } }
+    post:
+      requestBody:
+        content:
+          application/json: { schema: { $ref: '#/components/schemas/Pet' } }
+      responses: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name, age]
+      properties:
+        name: { type: string }
+        age: { type: number }
+        weight: { type: integer }
+        color: { type: string }
+        parent: { $ref: '#/components/schemas/Pet' }
+"#,
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "5 breaking, 2 non-breaking changes
+! GET /pets: type of query parameter `limit` changed from number to integer
+! GET /pets: field `nickname` removed from response 200 (application/json)
+! GET /pets: type of field `age` of response 200 (application/json) changed from integer to number
+! POST /pets: field `age` of request body (application/json) is now required
+! POST /pets: type of field `weight` of request body (application/json) changed from number to integer
+  GET /pets: field `color` added to response 200 (application/json)
+  POST /pets: type of field `age` of request body (application/json) changed from integer to number"
+        );
+        assert_eq!(
+            report.breaking[2].kind,
+            CompatChangeKind::ResponseTypeBroadened
+        );
+        assert_eq!(report.breaking[2].severity, Some(Severity::Warning));
+    }
+
+    #[test]
+    fn enums() {
+        let report = compat(
+            r#"paths:
+  /pets:
+    get:
+      parameters:
+      - { name: kind, in: query, schema: { type: string, enum: [cat, dog] } }
+      - { name: sort, in: query, schema: { type: string } }
+      - { name: order, in: query, schema: { type: string, enum: [asc, desc] } }
+      responses:
+        "200":
+          content:
+            application/json: { schema: { type: string, enum: [ok] } }
+"#,
+            r#"paths:
+  /pets:
+    get:
+      parameters:
+      - { name: kind, in: query, schema: { type: string, enum: [dog, bird] } }
+      - { name: sort, in: query, schema: { type: string, enum: [name] } }
+      - { name: order, in: query, schema: { type: string } }
+      responses:
+        "200":
+          content:
+            application/json: { schema: { type: string, enum: [ok, pending] } }
+"#,
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "3 breaking, 2 non-breaking changes
+! GET /pets: query parameter `kind` no longer accepts \"cat\"
+! GET /pets: query parameter `sort` restricted to \"name\"
+! GET /pets: response 200 (application/json) may now be \"pending\"
+  GET /pets: query parameter `kind` now accepts \"bird\"
+  GET /pets: enum of query parameter `order` removed"
+        );
+    }
+
+    #[test]
+    fn constraints() {
+        let report = compat(
+            r#"paths:
+  /pets:
+    get:
+      parameters:
+      - { name: limit, in: query, schema: { type: integer, maximum: 100, minimum: 1 } }
+      - { name: name, in: query, schema: { type: string, minLength: 3 } }
+      - { name: tag, in: query, schema: { type: string, pattern: "^[a-z]+$" } }
+      responses:
+        "200":
+          content:
+            application/json: { schema: { type: string, maxLength: 10 } }
+"#,
+            r#"paths:
+  /pets:
+    get:
+      parameters:
+      - { name: limit, in: query, schema: { type: integer, maximum: 50 } }
+      - { name: name, in: query, schema: { type: string, minLength: 1, maxLength: 20 } }
+      - { name: tag, in: query, schema: { type: string } }
+      responses:
+        "200":
+          content:
+            application/json: { schema: { type: string, maxLength: 20 } }
+"#,
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "2 breaking, 3 non-breaking changes
+! GET /pets: maximum of query parameter `limit` changed from 100 to 50
+! GET /pets: maxLength of query parameter `name` set to 20
+  GET /pets: minimum of query parameter `limit` removed
+  GET /pets: minLength of query parameter `name` changed from 3 to 1
+  GET /pets: pattern of query parameter `tag` removed"
+        );
+    }
+
+    #[test]
+    fn servers() {
+        let report = compat(
+            r#"servers: [{ url: "https://v1.example.com" }, { url: "https://example.com" }]
+paths:
+  /pets:
+    get: { responses: {} }
+  /stores:
+    servers: [{ url: "https://stores.example.com" }]
+    get: { responses: {} }
+"#,
+            r#"servers: [{ url: "https://example.com" }]
+paths:
+  /pets:
+    get: { responses: {} }
+  /stores:
+    get: { responses: {} }
+"#,
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "2 breaking, 0 non-breaking changes
+! servers: server `https://v1.example.com` removed
+! GET /stores: server `https://stores.example.com` removed"
+        );
+        assert_eq!(report.breaking[0].kind, CompatChangeKind::ServersChanged);
+    }
+
+    #[test]
+    fn security() {
+        let report = compat(
+            r#"paths:
+  /pets:
+    get: { responses: {} }
+    post:
+      security: [{ key: [] }]
+      responses: {}
+"#,
+            r#"security: [{ key: [] }]
+paths:
+  /pets:
+    get: { responses: {} }
+    post:
+      security: [{ key: [] }]
+      responses: {}
+"#,
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "1 breaking, 0 non-breaking changes
+! GET /pets: security requirements changed"
+        );
+        assert_eq!(report.breaking[0].severity, Some(Severity::Warning));
+    }
+
+    #[test]
+    fn unchanged_spec_is_compatible() {
+        let report = compat(
+            r#"paths:
+  /pets:
+    post:
+      requestBody:
+        content:
+          application/json: { schema: { $ref: '#/components/schemas/Pet' } }
+      responses: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        parent: { $ref: '#/components/schemas/Pet' }
+"#,
+            r#"paths:
+  /pets:
+    post:
+      requestBody:
+        content:
+          application/json: { schema: { $ref: '#/components/schemas/Pet' } }
+      responses: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        parent: { $ref: '#/components/schemas/Pet' }
+"#,
+        );
+
+        assert!(report.is_compatible());
+        assert_eq!(report, BackwardCompatReport::default());
+    }
+
+    #[test]
+    fn recursive_ref_with_siblings() {
+        let spec = r#"paths:
+  /nodes:
+    post:
+      requestBody:
+        content:
+          application/json: { schema: { $ref: '#/components/schemas/Node' } }
+      responses: {}
+components:
+  schemas:
+    Node:
+      type: object
+      properties:
+        next: { $ref: '#/components/schemas/Node', description: The next node. }
+"#;
+
+        assert!(compat(spec, spec).is_compatible());
+    }
+}
@@ -1,12 +1,25 @@
 use std::collections::BTreeMap;
 
+use derive_more::{Display, Error};
 use serde::{Deserialize, Serialize};
 
 use super::{
     schema::Schema, Callback, Example, Header, Link, ObjectOrReference, Parameter, PathItem,
-    RequestBody, Response, SecurityScheme,
+    RefType, RequestBody, Response, SecurityScheme, Spec,
 };
 
+/// Components merging errors.
+#[derive(Debug, Clone, PartialEq, Display, Error)]
+pub enum MergeError {
+    /// Both sets of components define a component with this name differently.
+    #[display(
+        fmt = "Conflicting definitions of component {}/{}",
+        "kind.component_path()",
+        name
+    )]
+    Conflict { kind: RefType, name: String },
+}
+
 /// Holds a set of reusable objects for different aspects of the OAS.
 ///
 /// All objects defined within the components object will have no effect on the API unless
@@ -66,3 +79,173 @@ pub struct Components {
     pub callbacks: BTreeMap<String, ObjectOrReference<Callback>>,
     // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#specificationExtensions}
 }
+
+impl Components {
+    /// Adds all components from `other`.
+    ///
+    /// Components defined identically in both are kept once. If any component is defined
+    /// differently in both, [`MergeError::Conflict`] is returned and `self` is left unchanged.
+    pub fn merge(&mut self, other: Components) -> Result<(), MergeError> {
+        fn check<T: PartialEq>(
+            ours: &BTreeMap<String, T>,
+            theirs: &BTreeMap<String, T>,
+            kind: RefType,
+        ) -> Result<(), MergeError> {
+            match theirs
+                .iter()
+                .find(|(name, val)| ours.get(*name).map_or(false, |ours| ours != *val))
+            {
+                Some((name, _)) => Err(MergeError::Conflict {
+                    kind,
+                    name: name.clone(),
+                }),
+                None => Ok(()),
+            }
+        }
+
+        macro_rules! merge {
+            ($($field:ident: $kind:ident),+ $(,)?) => {
+                $( check(&self.$field, &other.$field, RefType::$kind)?; )+
+                $( self.$field.extend(other.$field); )+
+            };
+        }
+
+        merge!(
+            schemas: Schema,
+            responses: Response,
+            parameters: Parameter,
+            examples: Example,
+            request_bodies: RequestBody,
+            headers: Header,
+            path_items: PathItem,
+            security_schemes: SecurityScheme,
+            links: Link,
+            callbacks: Callback,
+        );
+
+        Ok(())
+    }
+}
+
+/// The names of the components of each type, sorted alphabetically.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentNames {
+    pub schemas: Vec<String>,
+    pub parameters: Vec<String>,
+    pub responses: Vec<String>,
+    pub request_bodies: Vec<String>,
+    pub headers: Vec<String>,
+    pub examples: Vec<String>,
+    pub links: Vec<String>,
+    pub callbacks: Vec<String>,
+    pub security_schemes: Vec<String>,
+    pub path_items: Vec<String>,
+}
+
+impl Spec {
+    /// Returns the names of all components, grouped by type.
+    pub fn component_names(&self) -> ComponentNames {
+        let components = match &self.components {
+            Some(components) => components,
+            None => return ComponentNames::default(),
+        };
+
+        fn names<T>(components: &BTreeMap<String, T>) -> Vec<String> {
+            components.keys().cloned().collect()
+        }
+
+        ComponentNames {
+            schemas: names(&components.schemas),
+            parameters: names(&components.parameters),
+            responses: names(&components.responses),
+            request_bodies: names(&components.request_bodies),
+            headers: names(&components.headers),
+            examples: names(&components.examples),
+            links: names(&components.links),
+            callbacks: names(&components.callbacks),
+            security_schemes: names(&components.security_schemes),
+            path_items: names(&components.path_items),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_components() {
+        let mut pets = Components::default();
+        pets.schemas
+            .insert("Pet".to_owned(), Schema::object().into());
+        pets.schemas
+            .insert("Id".to_owned(), Schema::integer().into());
+
+        let mut users = Components::default();
+        users
+            .schemas
+            .insert("User".to_owned(), Schema::object().into());
+        users
+            .schemas
+            .insert("Id".to_owned(), Schema::integer().into());
+
+        let mut merged = pets.clone();
+        merged.merge(users).unwrap();
+        assert_eq!(
+            merged.schemas.keys().collect::<Vec<_>>(),
+            ["Id", "Pet", "User"]
+        );
+
+        let mut orders = Components::default();
+        orders
+            .schemas
+            .insert("Order".to_owned(), Schema::object().into());
+        orders
+            .schemas
+            .insert("Id".to_owned(), Schema::string().into());
+
+        let err = merged.merge(orders).unwrap_err();
+        assert_eq!(
+            err,
+            MergeError::Conflict {
+                kind: RefType::Schema,
+                name: "Id".to_owned(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Conflicting definitions of component schemas/Id"
+        );
+        assert!(!merged.schemas.contains_key("Order"));
+    }
+
+    #[test]
+    fn names_by_type() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  schemas:
+    Pet: { type: object }
+    Error: { type: object }
+  parameters:
+    limit: { name: limit, in: query }
+  securitySchemes:
+    apiKey: { type: apiKey, name: key, in: header }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let names = spec.component_names();
+        assert_eq!(names.schemas, ["Error", "Pet"]);
+        assert_eq!(names.parameters, ["limit"]);
+        assert_eq!(names.security_schemes, ["apiKey"]);
+        assert!(names.responses.is_empty());
+
+        let mut spec = spec;
+        spec.components = None;
+        assert_eq!(spec.component_names(), ComponentNames::default());
+    }
+}
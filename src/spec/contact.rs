@@ -12,7 +12,7 @@ pub struct Contact {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<Url>,
 
-    // TODO: Make sure the email is a valid email
+    /// Checked by [`Spec::validate_info`](super::Spec::validate_info).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
     // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#specificationExtensions
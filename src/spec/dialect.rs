@@ -0,0 +1,337 @@
+//! Detection of the schema dialects used within a spec.
+
+use std::collections::BTreeMap;
+
+use derive_more::{Display, Error};
+
+use super::{ExclusiveBound, ObjectOrReference, Schema, Spec};
+
+/// OpenAPI 3.0 schema dialect.
+pub const DIALECT_OAS30: &str = "oas30";
+
+/// OpenAPI 3.1 base dialect.
+pub const DIALECT_OAS31: &str = "oas31";
+
+/// JSON Schema draft 2020-12.
+pub const DIALECT_JSON_SCHEMA_2020_12: &str = "jsonschema202012";
+
+/// Dialect could not be determined.
+pub const DIALECT_UNKNOWN: &str = "unknown";
+
+/// A JSON Schema draft, ordered from oldest to newest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum JsonSchemaDraft {
+    #[display(fmt = "draft-04")]
+    Draft4,
+
+    #[display(fmt = "draft-06")]
+    Draft6,
+
+    #[display(fmt = "draft-07")]
+    Draft7,
+
+    #[display(fmt = "2019-09")]
+    Draft2019_09,
+
+    #[display(fmt = "2020-12")]
+    Draft2020_12,
+}
+
+impl JsonSchemaDraft {
+    /// Returns the draft identified by a `$schema` URI, if it is a known JSON Schema draft or
+    /// OpenAPI 3.1 dialect, which is based on draft 2020-12.
+    pub fn from_uri(uri: &str) -> Option<Self> {
+        let uri = uri.trim_end_matches('#');
+
+        if uri.contains("json-schema.org/draft-04/") {
+            Some(Self::Draft4)
+        } else if uri.contains("json-schema.org/draft-06/") {
+            Some(Self::Draft6)
+        } else if uri.contains("json-schema.org/draft-07/") {
+            Some(Self::Draft7)
+        } else if uri.contains("json-schema.org/draft/2019-09/") {
+            Some(Self::Draft2019_09)
+        } else if uri.contains("json-schema.org/draft/2020-12/")
+            || uri.contains("spec.openapis.org/oas/3.1/dialect")
+        {
+            Some(Self::Draft2020_12)
+        } else {
+            None
+        }
+    }
+}
+
+/// Inconsistencies between a schema and the JSON Schema draft it is written for.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum MetaSchemaError {
+    /// A keyword was introduced in a later draft.
+    #[display(fmt = "Keyword {} is not supported in JSON Schema {}", keyword, draft)]
+    KeywordNotSupportedInDraft {
+        keyword: &'static str,
+        draft: JsonSchemaDraft,
+    },
+}
+
+impl Schema {
+    /// Checks that the schema and its inline subschemas only use keywords supported by `draft`.
+    ///
+    /// `if`, `then` and `else` require draft-07, `$anchor` requires 2019-09, and `prefixItems`,
+    /// `$dynamicRef` and `$dynamicAnchor` require 2020-12. A keyword is reported once for each schema using it.
+    pub fn validate_against_metaschema(&self, draft: JsonSchemaDraft) -> Vec<MetaSchemaError> {
+        let mut errors = vec![];
+        collect_metaschema_errors(self, draft, &mut errors);
+        errors
+    }
+}
+
+fn collect_metaschema_errors(
+    schema: &Schema,
+    draft: JsonSchemaDraft,
+    errors: &mut Vec<MetaSchemaError>,
+) {
+    let keywords = [
+        ("if", schema.if_schema.is_some(), JsonSchemaDraft::Draft7),
+        (
+            "then",
+            schema.then_schema.is_some(),
+            JsonSchemaDraft::Draft7,
+        ),
+        (
+            "else",
+            schema.else_schema.is_some(),
+            JsonSchemaDraft::Draft7,
+        ),
+        (
+            "$anchor",
+            schema.anchor.is_some(),
+            JsonSchemaDraft::Draft2019_09,
+        ),
+        (
+            "prefixItems",
+            !schema.prefix_items.is_empty(),
+            JsonSchemaDraft::Draft2020_12,
+        ),
+        (
+            "$dynamicRef",
+            schema.dynamic_ref.is_some(),
+            JsonSchemaDraft::Draft2020_12,
+        ),
+        (
+            "$dynamicAnchor",
+            schema.dynamic_anchor.is_some(),
+            JsonSchemaDraft::Draft2020_12,
+        ),
+    ];
+
+    for (keyword, used, since) in keywords {
+        if used && draft < since {
+            errors.push(MetaSchemaError::KeywordNotSupportedInDraft { keyword, draft });
+        }
+    }
+
+    for sub in schema.subschemas() {
+        if let ObjectOrReference::Object(sub)
+        | ObjectOrReference::SchemaRef { overrides: sub, .. } = sub
+        {
+            collect_metaschema_errors(sub, draft, errors);
+        }
+    }
+}
+
+impl Spec {
+    /// Returns the schema dialect implied by the spec's `openapi` version.
+    pub fn default_schema_dialect(&self) -> &'static str {
+        if self.openapi.starts_with("3.0") {
+            DIALECT_OAS30
+        } else if self.openapi.starts_with("3.1") {
+            DIALECT_OAS31
+        } else {
+            DIALECT_UNKNOWN
+        }
+    }
+
+    /// Maps each named component schema to its detected dialect.
+    ///
+    /// A schema's `$schema` keyword is used if present. Otherwise, keywords specific to a dialect
+    /// found in the schema or its inline subschemas are used as hints: a boolean
+    /// `exclusiveMinimum` or `exclusiveMaximum` and `nullable` indicate OpenAPI 3.0 and
+    /// `prefixItems` indicates JSON Schema 2020-12. Schemas with no hints use
+    /// the [default dialect](Self::default_schema_dialect). Component schemas that are
+    /// unresolvable references are reported as `"unknown"`.
+    pub fn detect_schema_dialects(&self) -> BTreeMap<String, String> {
+        let default = self.default_schema_dialect();
+
+        let components = match &self.components {
+            Some(components) => components,
+            None => return BTreeMap::new(),
+        };
+
+        components
+            .schemas
+            .iter()
+            .map(|(name, oor)| {
+                let dialect = match oor.resolve(self) {
+                    Ok(schema) => detect_dialect(&schema).unwrap_or(default),
+                    Err(_) => DIALECT_UNKNOWN,
+                };
+
+                (name.clone(), dialect.to_owned())
+            })
+            .collect()
+    }
+}
+
+/// Detects a schema's dialect from its `$schema` keyword or dialect-specific keywords.
+fn detect_dialect(schema: &Schema) -> Option<&'static str> {
+    if let Some(uri) = &schema.schema_dialect {
+        return Some(dialect_from_uri(uri));
+    }
+
+    let has_flag_bound = [schema.exclusive_minimum, schema.exclusive_maximum]
+        .iter()
+        .any(|bound| matches!(bound, Some(ExclusiveBound::Flag(_))));
+
+    if has_flag_bound || schema.nullable.is_some() {
+        return Some(DIALECT_OAS30);
+    }
+
+    if !schema.prefix_items.is_empty() {
+        return Some(DIALECT_JSON_SCHEMA_2020_12);
+    }
+
+    // only inline subschemas and sibling keywords are inspected; referenced schemas are detected
+    // separately
+    schema
+        .subschemas()
+        .filter_map(|oor| match oor {
+            ObjectOrReference::Object(schema)
+            | ObjectOrReference::SchemaRef {
+                overrides: schema, ..
+            } => Some(schema),
+            ObjectOrReference::Ref { .. } => None,
+        })
+        .find_map(detect_dialect)
+}
+
+fn dialect_from_uri(uri: &str) -> &'static str {
+    if uri.contains("spec.openapis.org/oas/3.1/dialect") {
+        DIALECT_OAS31
+    } else if uri.contains("spec.openapis.org/oas/3.0") {
+        DIALECT_OAS30
+    } else if uri.contains("json-schema.org/draft/2020-12") {
+        DIALECT_JSON_SCHEMA_2020_12
+    } else {
+        DIALECT_UNKNOWN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn detects_dialects() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Mixed, version: "1" }
+paths: {}
+components:
+  schemas:
+    Plain: { type: string }
+    Legacy:
+      type: object
+      properties:
+        name: { type: string, nullable: true }
+    Percentage: { type: number, minimum: 0, exclusiveMinimum: true }
+    Bounded:
+      type: object
+      properties:
+        ratio: { type: number, maximum: 1, exclusiveMaximum: false }
+    Tuple:
+      type: array
+      prefixItems: [{ type: string }, { type: integer }]
+    Declared:
+      $schema: https://spec.openapis.org/oas/3.1/dialect/base
+      nullable: true
+    Custom:
+      $schema: https://example.com/my-dialect
+    Alias: { $ref: '#/components/schemas/Legacy' }
+    Missing: { $ref: '#/components/schemas/Nope' }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(spec.default_schema_dialect(), "oas31");
+        assert_eq!(
+            spec.detect_schema_dialects(),
+            btreemap! {
+                "Alias".to_owned() => "oas30".to_owned(),
+                "Bounded".to_owned() => "oas30".to_owned(),
+                "Custom".to_owned() => "unknown".to_owned(),
+                "Declared".to_owned() => "oas31".to_owned(),
+                "Legacy".to_owned() => "oas30".to_owned(),
+                "Missing".to_owned() => "unknown".to_owned(),
+                "Percentage".to_owned() => "oas30".to_owned(),
+                "Plain".to_owned() => "oas31".to_owned(),
+                "Tuple".to_owned() => "jsonschema202012".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn keywords_by_draft() {
+        let schema = serde_json::from_value::<Schema>(serde_json::json!({
+            "$anchor": "pair",
+            "$dynamicAnchor": "pair",
+            "type": "array",
+            "prefixItems": [{ "type": "string" }],
+            "items": {
+                "if": { "type": "string" },
+                "then": { "minLength": 1 },
+            },
+        }))
+        .unwrap();
+
+        assert!(schema
+            .validate_against_metaschema(JsonSchemaDraft::Draft2020_12)
+            .is_empty());
+
+        let keywords = |draft| {
+            schema
+                .validate_against_metaschema(draft)
+                .into_iter()
+                .map(|err| match err {
+                    MetaSchemaError::KeywordNotSupportedInDraft { keyword, .. } => keyword,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            keywords(JsonSchemaDraft::Draft2019_09),
+            ["prefixItems", "$dynamicAnchor"]
+        );
+        assert_eq!(
+            keywords(JsonSchemaDraft::Draft7),
+            ["$anchor", "prefixItems", "$dynamicAnchor"]
+        );
+        assert_eq!(
+            keywords(JsonSchemaDraft::Draft4),
+            ["$anchor", "prefixItems", "$dynamicAnchor", "if", "then"]
+        );
+
+        assert_eq!(
+            JsonSchemaDraft::from_uri("http://json-schema.org/draft-07/schema#"),
+            Some(JsonSchemaDraft::Draft7)
+        );
+        assert_eq!(
+            JsonSchemaDraft::from_uri("https://spec.openapis.org/oas/3.1/dialect/base"),
+            Some(JsonSchemaDraft::Draft2020_12)
+        );
+        assert_eq!(
+            JsonSchemaDraft::from_uri("https://example.com/schema"),
+            None
+        );
+    }
+}
@@ -0,0 +1,399 @@
+//! Differences between two versions of a spec.
+
+use std::{collections::BTreeMap, fmt};
+
+use derive_more::Display;
+use serde::Serialize;
+
+use super::{ObjectOrReference, OperationRef, ParamLoc, Parameter, Schema, Spec};
+
+/// How an item changed between two versions of a spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl ChangeKind {
+    /// Prefix of the change in [`SpecDiff`]'s `Display` output: `+`, `-` or `~`.
+    pub fn prefix(&self) -> char {
+        match self {
+            Self::Added => '+',
+            Self::Removed => '-',
+            Self::Modified => '~',
+        }
+    }
+
+    fn past_tense(&self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Modified => "changed",
+        }
+    }
+}
+
+/// The kind of item that changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffTarget {
+    #[display(fmt = "path")]
+    Path,
+
+    #[display(fmt = "operation")]
+    Operation,
+
+    #[display(fmt = "schema")]
+    Schema,
+
+    #[display(fmt = "parameter")]
+    Parameter,
+}
+
+/// How a breaking change affects existing clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Existing clients will break.
+    #[display(fmt = "error")]
+    Error,
+
+    /// Existing clients may break, depending on the details of the change.
+    #[display(fmt = "warning")]
+    Warning,
+}
+
+/// A single change between two versions of a spec.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change {
+    pub kind: ChangeKind,
+
+    pub target: DiffTarget,
+
+    /// Name of the item, e.g. `/pets` for a path, `GET /pets` for an operation, `Pet` for a
+    /// component schema or `GET /pets query limit` for a parameter.
+    pub name: String,
+
+    /// Set if the change can break existing clients.
+    pub severity: Option<Severity>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.kind.prefix(), self.target, self.name)
+    }
+}
+
+/// Changes to the paths, operations, component schemas and operation parameters of a spec.
+///
+/// Displays as a summary line (e.g., "1 path changed, 1 schema added") followed by one line per
+/// change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SpecDiff {
+    pub changes: Vec<Change>,
+}
+
+impl SpecDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the changes that can break existing clients, each with a severity.
+    pub fn breaking_changes(&self) -> Vec<&Change> {
+        self.changes
+            .iter()
+            .filter(|change| change.severity.is_some())
+            .collect()
+    }
+
+    /// Returns the changes as JSON, for machine-readable reports.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("diff serialization is infallible")
+    }
+
+    fn push(&mut self, kind: ChangeKind, target: DiffTarget, name: String) {
+        self.push_with_severity(kind, target, name, default_severity(kind, target));
+    }
+
+    fn push_with_severity(
+        &mut self,
+        kind: ChangeKind,
+        target: DiffTarget,
+        name: String,
+        severity: Option<Severity>,
+    ) {
+        self.changes.push(Change {
+            kind,
+            target,
+            name,
+            severity,
+        });
+    }
+}
+
+impl fmt::Display for SpecDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no changes");
+        }
+
+        let mut counts = BTreeMap::<DiffTarget, Vec<(ChangeKind, usize)>>::new();
+
+        for change in &self.changes {
+            let kinds = counts.entry(change.target).or_default();
+
+            match kinds.iter_mut().find(|(kind, _)| *kind == change.kind) {
+                Some((_, count)) => *count += 1,
+                None => kinds.push((change.kind, 1)),
+            }
+        }
+
+        let summary = counts
+            .into_iter()
+            .flat_map(|(target, kinds)| {
+                kinds.into_iter().map(move |(kind, count)| {
+                    let plural = if count == 1 { "" } else { "s" };
+                    format!("{} {}{} {}", count, target, plural, kind.past_tense())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", summary.join(", "))?;
+
+        for change in &self.changes {
+            write!(f, "\n{}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Spec {
+    /// Compares this spec to a newer version of it.
+    ///
+    /// Parameters are compared for operations present in both versions, after resolving them and
+    /// applying those inherited from path items. Unresolvable parameters are ignored.
+    pub fn diff(&self, new: &Spec) -> SpecDiff {
+        let mut diff = SpecDiff::default();
+
+        diff_maps(
+            &mut diff,
+            DiffTarget::Path,
+            &self.paths.iter().collect(),
+            &new.paths.iter().collect(),
+        );
+
+        let old_ops = operations_by_name(self);
+        let new_ops = operations_by_name(new);
+        diff_maps(
+            &mut diff,
+            DiffTarget::Operation,
+            &old_ops
+                .iter()
+                .map(|(name, op)| (name, op.operation))
+                .collect(),
+            &new_ops
+                .iter()
+                .map(|(name, op)| (name, op.operation))
+                .collect(),
+        );
+
+        diff_maps(
+            &mut diff,
+            DiffTarget::Schema,
+            &component_schemas(self),
+            &component_schemas(new),
+        );
+
+        for (op_name, old_op) in &old_ops {
+            let new_op = match new_ops.get(op_name) {
+                Some(new_op) => new_op,
+                None => continue,
+            };
+
+            let old_params = parameters_by_name(self, op_name, old_op);
+            let new_params = parameters_by_name(new, op_name, new_op);
+
+            for (name, param) in &new_params {
+                if !old_params.contains_key(name) {
+                    let severity = param.required.unwrap_or(false).then_some(Severity::Error);
+                    diff.push_with_severity(
+                        ChangeKind::Added,
+                        DiffTarget::Parameter,
+                        name.clone(),
+                        severity,
+                    );
+                }
+            }
+
+            diff_maps(
+                &mut diff,
+                DiffTarget::Parameter,
+                &old_params.iter().collect(),
+                &new_params
+                    .iter()
+                    .filter(|(name, _)| old_params.contains_key(*name))
+                    .collect(),
+            );
+        }
+
+        diff
+    }
+}
+
+/// Severity of changes whose impact only depends on their kind and target.
+fn default_severity(kind: ChangeKind, target: DiffTarget) -> Option<Severity> {
+    match (kind, target) {
+        (ChangeKind::Added, _) => None,
+        (ChangeKind::Removed, DiffTarget::Parameter) => Some(Severity::Warning),
+        (ChangeKind::Removed, _) => Some(Severity::Error),
+        (ChangeKind::Modified, DiffTarget::Path) => None,
+        (ChangeKind::Modified, _) => Some(Severity::Warning),
+    }
+}
+
+/// Records the items added to, removed from or modified between two maps.
+fn diff_maps<K, V>(
+    diff: &mut SpecDiff,
+    target: DiffTarget,
+    old: &BTreeMap<&K, V>,
+    new: &BTreeMap<&K, V>,
+) where
+    K: fmt::Display + Ord + ?Sized,
+    V: PartialEq,
+{
+    for (name, old_item) in old {
+        match new.get(name) {
+            None => diff.push(ChangeKind::Removed, target, name.to_string()),
+            Some(new_item) if new_item != old_item => {
+                diff.push(ChangeKind::Modified, target, name.to_string())
+            }
+            Some(_) => {}
+        }
+    }
+
+    for name in new.keys().filter(|name| !old.contains_key(*name)) {
+        diff.push(ChangeKind::Added, target, name.to_string());
+    }
+}
+
+fn component_schemas(spec: &Spec) -> BTreeMap<&String, &ObjectOrReference<Schema>> {
+    spec.components
+        .iter()
+        .flat_map(|components| &components.schemas)
+        .collect()
+}
+
+pub(super) fn operations_by_name(spec: &Spec) -> BTreeMap<String, OperationRef<'_>> {
+    spec.operation_refs()
+        .map(|op| (format!("{} {}", op.method, op.path), op))
+        .collect()
+}
+
+fn parameters_by_name(
+    spec: &Spec,
+    op_name: &str,
+    op: &OperationRef<'_>,
+) -> BTreeMap<String, Parameter> {
+    op.path_item
+        .effective_parameters(op.operation, spec)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|param| {
+            let location = match param.location {
+                ParamLoc::Query => "query",
+                ParamLoc::Header => "header",
+                ParamLoc::Path => "path",
+                ParamLoc::Cookie => "cookie",
+            };
+
+            (format!("{} {} {}", op_name, location, param.name), param)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const OLD: &str = r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets:
+    get:
+      parameters:
+      - { name: limit, in: query }
+      - { name: sort, in: query }
+      responses: {}
+  /stores:
+    get: { responses: {} }
+components:
+  schemas:
+    Pet: { type: object }
+"#;
+
+    const NEW: &str = r#"openapi: "3.1.0"
+info: { title: Pets, version: "2" }
+paths:
+  /pets:
+    get:
+      parameters:
+      - { name: limit, in: query, schema: { type: integer } }
+      - { name: owner, in: query, required: true }
+      responses: {}
+components:
+  schemas:
+    Pet: { type: object }
+    Owner: { type: object }
+"#;
+
+    #[test]
+    fn diff_specs() {
+        let old = crate::from_reader(OLD.as_bytes()).unwrap();
+        let new = crate::from_reader(NEW.as_bytes()).unwrap();
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.to_string(),
+            "1 path changed, 1 path removed, 1 operation changed, 1 operation removed, \
+             1 schema added, 1 parameter added, 1 parameter changed, 1 parameter removed
+~ path /pets
+- path /stores
+~ operation GET /pets
+- operation GET /stores
++ schema Owner
++ parameter GET /pets query owner
+~ parameter GET /pets query limit
+- parameter GET /pets query sort"
+        );
+
+        let breaking = diff
+            .breaking_changes()
+            .into_iter()
+            .map(|change| (change.name.as_str(), change.severity.unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            breaking,
+            [
+                ("/stores", Severity::Error),
+                ("GET /pets", Severity::Warning),
+                ("GET /stores", Severity::Error),
+                ("GET /pets query owner", Severity::Error),
+                ("GET /pets query limit", Severity::Warning),
+                ("GET /pets query sort", Severity::Warning),
+            ]
+        );
+
+        assert_eq!(
+            diff.to_json()["changes"][4],
+            json!({ "kind": "added", "target": "schema", "name": "Owner", "severity": null })
+        );
+
+        assert!(old.diff(&old).is_empty());
+        assert_eq!(old.diff(&old).to_string(), "no changes");
+    }
+}
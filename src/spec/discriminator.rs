@@ -0,0 +1,19 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// When request bodies or response payloads may be one of a number of different schemas, a
+/// `discriminator` object can be used to aid in serialization, deserialization, and validation.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#discriminatorObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Discriminator {
+    /// The name of the property in the payload that will hold the discriminator value.
+    #[serde(rename = "propertyName")]
+    pub property_name: String,
+
+    /// An object to hold mappings between payload values and schema names or references.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub mapping: BTreeMap<String, String>,
+}
@@ -18,3 +18,13 @@ pub enum Error {
     #[display(fmt = "Unsupported spec file version ({})", _0)]
     UnsupportedSpecFileVersion(#[error(not(source))] Version),
 }
+
+/// Spec parsing errors.
+#[derive(Debug, Display, Error, From)]
+pub enum ParseError {
+    #[display(fmt = "JSON error")]
+    Json(serde_json::Error),
+
+    #[display(fmt = "YAML error")]
+    Yaml(serde_yaml::Error),
+}
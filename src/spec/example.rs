@@ -59,3 +59,38 @@ impl FromRef for Example {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn example_from_ref() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  examples:
+    cat: { summary: A cat, value: { name: Tom } }
+    alias: { $ref: '#/components/examples/cat' }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let cat = Example::from_ref(&spec, "#/components/examples/alias").unwrap();
+        assert_eq!(cat.value, Some(json!({ "name": "Tom" })));
+
+        assert_eq!(
+            Example::from_ref(&spec, "#/components/examples/dog").unwrap_err(),
+            RefError::Unresolvable("#/components/examples/dog".to_owned())
+        );
+        assert_eq!(
+            Example::from_ref(&spec, "#/components/schemas/cat").unwrap_err(),
+            RefError::MismatchedType(RefType::Schema, RefType::Example)
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Fingerprints of the API surface described by specs.
+
+use serde_json::Value;
+
+use super::{schema::fnv1a_hash, Spec};
+
+/// Top-level spec fields describing the API surface.
+const SURFACE_FIELDS: &[&str] = &["paths", "webhooks", "components", "security"];
+
+/// Keywords of annotations that do not affect the API surface.
+const ANNOTATIONS: &[&str] = &[
+    "$comment",
+    "description",
+    "example",
+    "examples",
+    "externalDocs",
+    "summary",
+    "title",
+];
+
+/// Keywords whose values are instance data, which is kept as it is even where it looks like an
+/// annotation.
+const INSTANCE_DATA: &[&str] = &["const", "default", "enum"];
+
+/// Keywords whose values map arbitrary names, such as property names, to objects.
+const NAMED_MAPS: &[&str] = &[
+    "$defs",
+    "callbacks",
+    "content",
+    "dependentSchemas",
+    "encoding",
+    "headers",
+    "links",
+    "parameters",
+    "pathItems",
+    "paths",
+    "patternProperties",
+    "properties",
+    "requestBodies",
+    "responses",
+    "schemas",
+    "securitySchemes",
+    "webhooks",
+];
+
+impl Spec {
+    /// Hashes the API surface described by the spec: its paths, operations, parameters,
+    /// request bodies, responses and schemas, along with its components and security
+    /// requirements.
+    ///
+    /// Metadata such as `info`, servers and tags, and annotations such as titles, descriptions,
+    /// summaries and examples, are left out, so changing them does not change the hash. The
+    /// hash is deterministic across runs and platforms, so it can be persisted, e.g. to tell
+    /// whether code generated from a spec is out of date.
+    pub fn api_surface_hash(&self) -> u64 {
+        let mut spec = serde_json::to_value(self).expect("specs should always serialize to JSON");
+
+        if let Value::Object(fields) = &mut spec {
+            fields.retain(|field, _| SURFACE_FIELDS.contains(&field.as_str()));
+        }
+
+        strip_annotations(&mut spec, false);
+
+        fnv1a_hash(spec.to_string().as_bytes())
+    }
+}
+
+/// Removes annotations from a value, recursively. Keys of named maps are never removed, so that
+/// e.g. a property called `title` is kept, and instance data such as `enum` values is not
+/// recursed into.
+fn strip_annotations(value: &mut Value, is_named_map: bool) {
+    match value {
+        Value::Object(obj) if is_named_map => {
+            for child in obj.values_mut() {
+                strip_annotations(child, false);
+            }
+        }
+        Value::Object(obj) => {
+            obj.retain(|key, _| !ANNOTATIONS.contains(&key.as_str()));
+
+            for (key, child) in obj.iter_mut() {
+                if !INSTANCE_DATA.contains(&key.as_str()) {
+                    strip_annotations(child, NAMED_MAPS.contains(&key.as_str()));
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                strip_annotations(item, false);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets:
+    get:
+      summary: Lists pets.
+      parameters:
+      - { name: limit, in: query, description: Page size., schema: { type: integer } }
+      responses:
+        "200":
+          description: Pets.
+          content:
+            application/json:
+              schema: { $ref: '#/components/schemas/Pet' }
+components:
+  schemas:
+    Pet:
+      title: A pet
+      type: object
+      properties:
+        title: { type: string, description: Honorific. }
+        owner:
+          type: object
+          enum: [{ title: Dr }]
+          default: { description: vet }
+          const: { summary: none }
+"#;
+
+    fn hash_with(from: &str, to: &str) -> u64 {
+        assert!(SPEC.contains(from), "{}", from);
+        Spec::from_str(&SPEC.replace(from, to))
+            .unwrap()
+            .api_surface_hash()
+    }
+
+    #[test]
+    fn api_surface_hash() {
+        let hash = Spec::from_str(SPEC).unwrap().api_surface_hash();
+
+        for (from, to) in [
+            ("title: Pets", "title: Animals"),
+            ("version: \"1\"", "version: \"2\""),
+            ("Lists pets.", "Lists all pets."),
+            ("Page size.", "Number of pets."),
+            ("description: Pets.", "description: All pets."),
+            ("title: A pet", "title: An animal"),
+            ("Honorific.", "Title of the pet."),
+        ] {
+            assert_eq!(hash_with(from, to), hash, "{} -> {}", from, to);
+        }
+
+        for (from, to) in [
+            ("name: limit", "name: size"),
+            ("type: integer", "type: number"),
+            ("\"200\":", "\"201\":"),
+            ("application/json", "application/xml"),
+            ("title: { type: string", "name: { type: string"),
+            ("get:", "post:"),
+            ("title: Dr", "title: Mr"),
+            ("description: vet", "description: owner"),
+            ("summary: none", "summary: some"),
+        ] {
+            assert_ne!(hash_with(from, to), hash, "{} -> {}", from, to);
+        }
+    }
+}
@@ -3,6 +3,8 @@ use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use super::{FromRef, Ref, RefError, RefType, Spec};
+
 /// Allows configuration of the supported OAuth Flows.
 ///
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#oauth-flows-object>.
@@ -22,6 +24,21 @@ pub struct Flows {
     pub authorization_code: Option<AuthorizationCodeFlow>,
 }
 
+impl Flows {
+    /// Returns true if any of the flows defines `scope`.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        let implicit = self.implicit.as_ref().map(|flow| &flow.scopes);
+        let password = self.password.as_ref().map(|flow| &flow.scopes);
+        let client_credentials = self.client_credentials.as_ref().map(|flow| &flow.scopes);
+        let authorization_code = self.authorization_code.as_ref().map(|flow| &flow.scopes);
+
+        [implicit, password, client_credentials, authorization_code]
+            .into_iter()
+            .flatten()
+            .any(|scopes| scopes.contains_key(scope))
+    }
+}
+
 /// Configuration details for a implicit OAuth Flow.
 ///
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#oauth-flow-object>.
@@ -93,6 +110,23 @@ pub struct Callback(
     serde_json::Value, // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#specificationExtensions}
 );
 
+impl FromRef for Callback {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        let refpath = path.parse::<Ref>()?;
+
+        match refpath.kind {
+            RefType::Callback => spec
+                .components
+                .as_ref()
+                .and_then(|cs| cs.callbacks.get(&refpath.name))
+                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
+                .and_then(|oor| oor.resolve(spec)),
+
+            typ => Err(RefError::MismatchedType(typ, RefType::Callback)),
+        }
+    }
+}
+
 // FIXME: Implement
 // /// Allows configuration of the supported OAuth Flows.
 // /// https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#oauthFlowsObject
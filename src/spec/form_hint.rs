@@ -0,0 +1,198 @@
+//! Form control hints for generating user interfaces from schemas.
+
+use super::{Schema, SchemaType, Spec};
+
+/// Strings allowed to be longer than this are edited in a [`FormatHint::TextArea`].
+const TEXT_AREA_MIN_LENGTH: u64 = 256;
+
+/// The form control best suited to editing values of a schema.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormatHint {
+    /// For `date` strings.
+    DatePicker,
+
+    /// For `date-time` strings.
+    DateTimePicker,
+
+    /// For `time` strings.
+    TimePicker,
+
+    /// For `password` strings.
+    PasswordInput,
+
+    /// For `email` and `idn-email` strings.
+    EmailInput,
+
+    /// For `uri`, `uri-reference`, `iri` and `url` strings.
+    UrlInput,
+
+    /// For `color` strings, and strings whose `pattern` starts with `^#`.
+    ColorPicker,
+
+    /// For `phone` and `tel` strings, and strings whose `pattern` starts with `^\+`.
+    PhoneInput,
+
+    /// For `binary` strings.
+    FileUpload,
+
+    /// For values restricted by an `enum`.
+    Select,
+
+    /// For booleans.
+    Checkbox,
+
+    /// For short or unbounded strings.
+    TextInput { max_length: Option<u64> },
+
+    /// For strings allowed to be longer than 255 characters.
+    TextArea { max_length: Option<u64> },
+
+    /// For numbers; the step is the schema's `multipleOf`, or 1 for integers.
+    NumberInput { step: Option<f64> },
+}
+
+impl Schema {
+    /// Returns the form control best suited to editing values of this schema, based on its
+    /// `enum`, `format`, `pattern`, `type` and `maxLength`, in that order of precedence.
+    ///
+    /// `allOf` branches are merged in first. Returns `None` for objects, arrays and schemas
+    /// without a type.
+    pub fn format_validation_hint(&self, spec: &Spec) -> Option<FormatHint> {
+        let merged = self.merged_all_of(spec);
+        let schema = merged.as_ref().unwrap_or(self);
+
+        if !schema.enum_values.is_empty() {
+            return Some(FormatHint::Select);
+        }
+
+        let format_hint = schema.format.as_ref().and_then(|format| {
+            Some(match format.as_str() {
+                "date" => FormatHint::DatePicker,
+                "date-time" => FormatHint::DateTimePicker,
+                "time" => FormatHint::TimePicker,
+                "password" => FormatHint::PasswordInput,
+                "email" | "idn-email" => FormatHint::EmailInput,
+                "uri" | "uri-reference" | "iri" | "url" => FormatHint::UrlInput,
+                "color" => FormatHint::ColorPicker,
+                "phone" | "tel" => FormatHint::PhoneInput,
+                "binary" => FormatHint::FileUpload,
+                _ => return None,
+            })
+        });
+
+        let pattern_hint = schema.pattern.as_deref().and_then(|pattern| {
+            if pattern.starts_with("^#") {
+                Some(FormatHint::ColorPicker)
+            } else if pattern.starts_with(r"^\+") {
+                Some(FormatHint::PhoneInput)
+            } else {
+                None
+            }
+        });
+
+        if let Some(hint) = format_hint.or(pattern_hint) {
+            return Some(hint);
+        }
+
+        match schema.schema_type? {
+            SchemaType::Boolean => Some(FormatHint::Checkbox),
+            SchemaType::Integer | SchemaType::Number => {
+                let step = schema
+                    .multiple_of
+                    .as_ref()
+                    .and_then(serde_json::Number::as_f64);
+                let default_step = (schema.schema_type == Some(SchemaType::Integer)).then_some(1.0);
+
+                Some(FormatHint::NumberInput {
+                    step: step.or(default_step),
+                })
+            }
+            SchemaType::String => {
+                let max_length = schema.max_length;
+
+                if max_length.map_or(false, |len| len >= TEXT_AREA_MIN_LENGTH) {
+                    Some(FormatHint::TextArea { max_length })
+                } else {
+                    Some(FormatHint::TextInput { max_length })
+                }
+            }
+            SchemaType::Array | SchemaType::Object | SchemaType::Null => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_hints() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Forms, version: "1" }
+components:
+  schemas:
+    birthday: { type: string, format: date }
+    alarm: { type: string, format: time }
+    secret: { type: string, format: password }
+    favourite: { type: string, pattern: '^#[0-9a-f]{6}$' }
+    phone: { type: string, pattern: '^\+[0-9]+$' }
+    status: { type: string, format: email, enum: [a@example.com] }
+    name: { type: string, maxLength: 50 }
+    bio: { type: string, maxLength: 1000 }
+    age: { type: integer }
+    price: { type: number, multipleOf: 0.01 }
+    weight: { type: number }
+    active: { type: boolean }
+    short_bio:
+      allOf: [{ $ref: '#/components/schemas/bio' }, { maxLength: 100 }]
+    pet: { type: object }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let hint = |name: &str| {
+            spec.components.as_ref().unwrap().schemas[name]
+                .resolve(&spec)
+                .unwrap()
+                .format_validation_hint(&spec)
+        };
+
+        assert_eq!(hint("birthday"), Some(FormatHint::DatePicker));
+        assert_eq!(hint("alarm"), Some(FormatHint::TimePicker));
+        assert_eq!(hint("secret"), Some(FormatHint::PasswordInput));
+        assert_eq!(hint("favourite"), Some(FormatHint::ColorPicker));
+        assert_eq!(hint("phone"), Some(FormatHint::PhoneInput));
+        assert_eq!(hint("status"), Some(FormatHint::Select));
+        assert_eq!(
+            hint("name"),
+            Some(FormatHint::TextInput {
+                max_length: Some(50)
+            })
+        );
+        assert_eq!(
+            hint("bio"),
+            Some(FormatHint::TextArea {
+                max_length: Some(1000)
+            })
+        );
+        assert_eq!(
+            hint("age"),
+            Some(FormatHint::NumberInput { step: Some(1.0) })
+        );
+        assert_eq!(
+            hint("price"),
+            Some(FormatHint::NumberInput { step: Some(0.01) })
+        );
+        assert_eq!(hint("weight"), Some(FormatHint::NumberInput { step: None }));
+        assert_eq!(hint("active"), Some(FormatHint::Checkbox));
+        assert_eq!(
+            hint("short_bio"),
+            Some(FormatHint::TextInput {
+                max_length: Some(100)
+            })
+        );
+        assert_eq!(hint("pet"), None);
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use super::{FromRef, ObjectOrReference, Ref, RefError, RefType, Spec};
 use crate::Schema;
 
 // TODO: update to 3.1 spec including JSON Schema conformance.
@@ -24,7 +25,7 @@ pub struct Header {
     pub required: Option<bool>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub schema: Option<Schema>,
+    pub schema: Option<ObjectOrReference<Schema>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "uniqueItems")]
@@ -57,3 +58,20 @@ pub struct Header {
     // multipleOf ??
     // allowEmptyValue ( for query / body params )
 }
+
+impl FromRef for Header {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        let refpath = path.parse::<Ref>()?;
+
+        match refpath.kind {
+            RefType::Header => spec
+                .components
+                .as_ref()
+                .and_then(|cs| cs.headers.get(&refpath.name))
+                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
+                .and_then(|oor| oor.resolve(spec)),
+
+            typ => Err(RefError::MismatchedType(typ, RefType::Header)),
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Best-effort schema inference from example values.
+
+use serde_json::Value as JsonValue;
+
+use super::{schema::Type, ObjectOrReference, Schema};
+
+impl Schema {
+    /// Infers a schema describing the given value.
+    ///
+    /// Objects require each of their keys, with property schemas inferred from the values. Array
+    /// items are inferred from all elements, as by [`infer_from_values`](Self::infer_from_values).
+    pub fn infer_from_value(value: &JsonValue) -> Schema {
+        match value {
+            JsonValue::Null => Schema::of_type(Type::Null),
+            JsonValue::Bool(_) => Schema::boolean(),
+            JsonValue::Number(num) if num.is_i64() || num.is_u64() => Schema::integer(),
+            JsonValue::Number(_) => Schema::number(),
+            JsonValue::String(_) => Schema::string(),
+
+            JsonValue::Array(items) if items.is_empty() => Schema::of_type(Type::Array),
+            JsonValue::Array(items) => Schema::array(Schema::infer_from_values(items)),
+
+            JsonValue::Object(obj) => {
+                let mut schema = Schema::object();
+
+                for (name, value) in obj {
+                    schema.add_property(name.clone(), Schema::infer_from_value(value));
+                }
+
+                schema.required = obj.keys().cloned().collect();
+                schema
+            }
+        }
+    }
+
+    /// Infers a schema that all the given values are valid against.
+    ///
+    /// Integers and numbers widen to numbers, `null`s make the schema an `anyOf` of the inferred
+    /// schema and `{type: null}`, and other mismatched types leave the type unconstrained.
+    /// Properties are only required if every object has them.
+    pub fn infer_from_values(values: &[JsonValue]) -> Schema {
+        values
+            .iter()
+            .map(Schema::infer_from_value)
+            .reduce(widen)
+            .unwrap_or_default()
+    }
+}
+
+/// Returns a schema that both inferred schemas are compatible with.
+fn widen(a: Schema, b: Schema) -> Schema {
+    let (a, a_nullable) = split_null(a);
+    let (b, b_nullable) = split_null(b);
+
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (Some(schema), None) | (None, Some(schema)) => return nullable(schema),
+        (None, None) => return Schema::of_type(Type::Null),
+    };
+
+    let widened = match (a.schema_type, b.schema_type) {
+        (Some(Type::Object), Some(Type::Object)) => widen_objects(a, b),
+        (Some(Type::Array), Some(Type::Array)) => widen_arrays(a, b),
+        (Some(x), Some(y)) if x == y => a,
+        (Some(Type::Integer), Some(Type::Number)) | (Some(Type::Number), Some(Type::Integer)) => {
+            Schema::number()
+        }
+        _ => Schema::default(),
+    };
+
+    if a_nullable || b_nullable {
+        nullable(widened)
+    } else {
+        widened
+    }
+}
+
+/// Returns a schema that also accepts `null`.
+pub(crate) fn nullable(schema: Schema) -> Schema {
+    // unconstrained schemas already accept `null`
+    if schema == Schema::default() {
+        return schema;
+    }
+
+    Schema {
+        any_of: vec![schema.into(), Schema::of_type(Type::Null).into()],
+        ..Schema::default()
+    }
+}
+
+/// Splits an inferred schema into the schema of its non-`null` values, if any, and whether it
+/// accepts `null`.
+fn split_null(schema: Schema) -> (Option<Schema>, bool) {
+    if schema.schema_type == Some(Type::Null) {
+        return (None, true);
+    }
+
+    let is_nullable = schema.any_of.len() == 2
+        && schema.any_of[1] == Schema::of_type(Type::Null).into()
+        && Schema {
+            any_of: vec![],
+            ..schema.clone()
+        } == Schema::default();
+
+    if is_nullable {
+        let inner = schema.any_of.into_iter().next().map(inferred);
+        return (inner, true);
+    }
+
+    (Some(schema), false)
+}
+
+fn widen_objects(mut a: Schema, b: Schema) -> Schema {
+    for (name, prop) in b.properties {
+        let prop = match a.properties.remove(&name) {
+            Some(existing) => widen(inferred(existing), inferred(prop)).into(),
+            None => prop,
+        };
+
+        a.properties.insert(name, prop);
+    }
+
+    a.required.retain(|field| b.required.contains(field));
+    a
+}
+
+fn widen_arrays(mut a: Schema, b: Schema) -> Schema {
+    a.items = match (a.items, b.items) {
+        (Some(x), Some(y)) => Some(Box::new(widen(inferred(*x), inferred(*y)).into())),
+        (x, y) => x.or(y),
+    };
+
+    a
+}
+
+/// Unwraps an inferred schema, which is never a reference.
+fn inferred(oor: ObjectOrReference<Schema>) -> Schema {
+    match oor {
+        ObjectOrReference::Object(schema) => schema,
+        ObjectOrReference::Ref { .. } | ObjectOrReference::SchemaRef { .. } => Schema::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn infer_single_value() {
+        let schema = Schema::infer_from_value(&json!({
+            "name": "Rex",
+            "age": 3,
+            "weight": 4.5,
+            "good": true,
+            "owner": null,
+            "tags": ["dog"],
+        }));
+
+        let expected = Schema {
+            required: ["age", "good", "name", "owner", "tags", "weight"]
+                .map(String::from)
+                .to_vec(),
+            ..Schema::object()
+                .with_property("name", Schema::string())
+                .with_property("age", Schema::integer())
+                .with_property("weight", Schema::number())
+                .with_property("good", Schema::boolean())
+                .with_property("owner", Schema::of_type(Type::Null))
+                .with_property("tags", Schema::array(Schema::string()))
+        };
+        assert_eq!(schema, expected);
+
+        assert_eq!(
+            Schema::infer_from_value(&json!([])),
+            Schema::of_type(Type::Array)
+        );
+    }
+
+    #[test]
+    fn infer_multiple_values() {
+        let schema = Schema::infer_from_values(&[
+            json!({ "id": 1, "size": 1, "name": "Rex", "misc": "a" }),
+            json!({ "id": 2, "size": 1.5, "name": null, "misc": 1, "tags": [1] }),
+        ]);
+
+        let expected = Schema {
+            required: ["id", "misc", "name", "size"].map(String::from).to_vec(),
+            ..Schema::object()
+                .with_property("id", Schema::integer())
+                .with_property("size", Schema::number())
+                .with_property(
+                    "name",
+                    Schema {
+                        any_of: vec![Schema::string().into(), Schema::of_type(Type::Null).into()],
+                        ..Schema::default()
+                    },
+                )
+                .with_property("misc", Schema::default())
+                .with_property("tags", Schema::array(Schema::integer()))
+        };
+        assert_eq!(schema, expected);
+
+        assert_eq!(Schema::infer_from_values(&[]), Schema::default());
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn inferred_schemas_accept_all_values() {
+        let spec = crate::from_reader(
+            r#"{ "openapi": "3.1.0", "info": { "title": "t", "version": "1" }, "paths": {} }"#
+                .as_bytes(),
+        )
+        .unwrap();
+
+        let samples = [
+            vec![json!({ "name": "Rex" }), json!({ "name": null })],
+            vec![json!(null), json!(1), json!(null), json!(2.5)],
+            vec![json!([1, null]), json!([{ "a": 1 }]), json!(null)],
+            vec![
+                json!({ "owner": { "name": "Ann", "age": null } }),
+                json!({ "owner": null }),
+                json!({ "owner": { "name": null, "age": 3 } }),
+            ],
+            vec![json!(null), json!(null)],
+            vec![json!([1, null, 2.5])],
+            vec![json!("a"), json!(1), json!(null)],
+        ];
+
+        for values in samples {
+            let schema = Schema::infer_from_values(&values);
+
+            for value in &values {
+                assert!(
+                    schema.strict_validates(value, &spec),
+                    "{} against {:?}",
+                    value,
+                    schema
+                );
+            }
+        }
+    }
+}
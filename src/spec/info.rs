@@ -1,7 +1,8 @@
+use derive_more::{Display, Error};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use super::{Contact, License};
+use super::{Contact, License, Spec};
 
 /// General information about the API.
 ///
@@ -38,3 +39,125 @@ pub struct Info {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<License>,
 }
+
+/// Problems with the Info Object of a spec.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum InfoValidationError {
+    #[display(fmt = "Info title is empty")]
+    EmptyTitle,
+
+    #[display(fmt = "Info version is empty")]
+    EmptyVersion,
+
+    #[display(fmt = "Invalid contact email: {}", _0)]
+    InvalidContactEmail(#[error(not(source))] String),
+
+    #[display(fmt = "Invalid license URL: {}", _0)]
+    InvalidLicenseUrl(#[error(not(source))] Url),
+
+    #[display(fmt = "Terms of service is not an absolute URL: {}", _0)]
+    InvalidTermsOfService(#[error(not(source))] Url),
+}
+
+impl Spec {
+    /// Checks that the title and version of the spec are not empty, that the contact email is a
+    /// valid email address and that the license and terms of service URLs are web addresses.
+    ///
+    /// Relative URLs are already rejected when the spec is parsed.
+    pub fn validate_info(&self) -> Result<(), InfoValidationError> {
+        let info = &self.info;
+
+        if info.title.trim().is_empty() {
+            return Err(InfoValidationError::EmptyTitle);
+        }
+
+        if info.version.trim().is_empty() {
+            return Err(InfoValidationError::EmptyVersion);
+        }
+
+        if let Some(email) = info.contact.as_ref().and_then(|c| c.email.as_ref()) {
+            if !is_email(email) {
+                return Err(InfoValidationError::InvalidContactEmail(email.clone()));
+            }
+        }
+
+        if let Some(url) = info.license.as_ref().and_then(|l| l.url.as_ref()) {
+            if !url.has_host() {
+                return Err(InfoValidationError::InvalidLicenseUrl(url.clone()));
+            }
+        }
+
+        if let Some(url) = &info.terms_of_service {
+            if !url.has_host() {
+                return Err(InfoValidationError::InvalidTermsOfService(url.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks the basic shape of an email address, `local@domain.tld`.
+pub(crate) fn is_email(email: &str) -> bool {
+    let (local, domain) = match email.split_once('@') {
+        Some(parts) => parts,
+        None => return false,
+    };
+
+    !local.is_empty()
+        && !email.chars().any(char::is_whitespace)
+        && !domain.contains('@')
+        && domain.contains('.')
+        && domain.split('.').all(|label| !label.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec_with_info(info: &str) -> Spec {
+        let spec = format!("openapi: \"3.1.0\"\ninfo: {}\npaths: {{}}\n", info);
+        crate::from_reader(spec.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn info_validation() {
+        let spec = spec_with_info(
+            r#"{ title: Pets, version: "1", termsOfService: "https://example.com/tos",
+                contact: { email: api@example.com }, license: { name: MIT, url: "https://mit.edu" } }"#,
+        );
+        spec.validate_info().unwrap();
+
+        let spec = spec_with_info(r#"{ title: " ", version: "1" }"#);
+        assert_eq!(spec.validate_info(), Err(InfoValidationError::EmptyTitle));
+
+        let spec = spec_with_info(r#"{ title: Pets, version: "" }"#);
+        assert_eq!(spec.validate_info(), Err(InfoValidationError::EmptyVersion));
+
+        let spec =
+            spec_with_info(r#"{ title: Pets, version: "1", contact: { email: api.example.com } }"#);
+        assert_eq!(
+            spec.validate_info(),
+            Err(InfoValidationError::InvalidContactEmail(
+                "api.example.com".to_owned()
+            ))
+        );
+
+        let spec =
+            spec_with_info(r#"{ title: Pets, version: "1", termsOfService: "urn:isbn:123" }"#);
+        assert!(matches!(
+            spec.validate_info(),
+            Err(InfoValidationError::InvalidTermsOfService(_))
+        ));
+    }
+
+    #[test]
+    fn email_addresses() {
+        assert!(is_email("a@b.co"));
+        assert!(!is_email("a@b"));
+        assert!(!is_email("@b.co"));
+        assert!(!is_email("a@@b.co"));
+        assert!(!is_email("a b@c.co"));
+        assert!(!is_email("a@b..co"));
+    }
+}
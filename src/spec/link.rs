@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use super::Server;
+use super::{FromRef, Ref, RefError, RefType, Server, Spec};
 
 /// The Link object represents a possible design-time link for a response.
 ///
@@ -97,3 +97,20 @@ pub enum Link {
         // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#specificationExtension
     },
 }
+
+impl FromRef for Link {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        let refpath = path.parse::<Ref>()?;
+
+        match refpath.kind {
+            RefType::Link => spec
+                .components
+                .as_ref()
+                .and_then(|cs| cs.links.get(&refpath.name))
+                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
+                .and_then(|oor| oor.resolve(spec)),
+
+            typ => Err(RefError::MismatchedType(typ, RefType::Link)),
+        }
+    }
+}
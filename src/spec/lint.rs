@@ -0,0 +1,192 @@
+//! Checks for questionable, but valid, spec constructs.
+
+use std::collections::BTreeSet;
+
+use derive_more::Display;
+use http::Method;
+
+use super::{FromRef, ObjectOrReference, OperationRef, Ref, RefType, Schema, Spec};
+
+/// A potential problem found by [`Spec::lint`].
+#[derive(Debug, Clone, PartialEq, Display)]
+pub enum LintWarning {
+    /// An operation that is not deprecated uses a deprecated component schema in a parameter,
+    /// request body or response.
+    #[display(fmt = "{} {} uses deprecated schema `{}`", method, path, schema)]
+    DeprecatedSchemaUsed {
+        path: String,
+        method: Method,
+        schema: String,
+    },
+}
+
+impl Spec {
+    /// Checks the spec for potential problems.
+    pub fn lint(&self) -> Vec<LintWarning> {
+        let deprecated = self
+            .deprecated_schemas()
+            .map(|(name, _)| name)
+            .collect::<BTreeSet<_>>();
+
+        if deprecated.is_empty() {
+            return vec![];
+        }
+
+        let mut warnings = vec![];
+
+        for op in self.operation_refs() {
+            if op.operation.deprecated == Some(true) {
+                continue;
+            }
+
+            for schema in self.referenced_schema_names(&op) {
+                if deprecated.contains(schema.as_str()) {
+                    warnings.push(LintWarning::DeprecatedSchemaUsed {
+                        path: op.path.to_owned(),
+                        method: op.method.clone(),
+                        schema,
+                    });
+                }
+            }
+        }
+
+        warnings
+    }
+
+    /// Collects the names of all component schemas that an operation's parameters, request body
+    /// and responses refer to, directly or through other schemas.
+    fn referenced_schema_names(&self, op: &OperationRef<'_>) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+
+        let params = op
+            .path_item
+            .effective_parameters(op.operation, self)
+            .unwrap_or_default();
+
+        for schema in params.iter().filter_map(|param| param.schema.as_ref()) {
+            self.collect_schema_refs(schema, &mut names);
+        }
+
+        let request_content = op
+            .operation
+            .request_body
+            .iter()
+            .filter_map(|body| body.resolve(self).ok())
+            .flat_map(|body| body.content.into_values());
+
+        let response_content = op
+            .operation
+            .responses(self)
+            .into_values()
+            .flat_map(|response| response.content.into_values());
+
+        for content in request_content.chain(response_content) {
+            if let Some(schema) = &content.schema {
+                self.collect_schema_refs(schema, &mut names);
+            }
+        }
+
+        names
+    }
+
+    fn collect_schema_refs(&self, oor: &ObjectOrReference<Schema>, names: &mut BTreeSet<String>) {
+        // sibling keywords may reference schemas not used by the referenced schema
+        if let ObjectOrReference::SchemaRef { overrides, .. } = oor {
+            self.collect_schema_refs(&ObjectOrReference::Object(overrides.clone()), names);
+        }
+
+        let schema = match oor {
+            ObjectOrReference::Object(schema) => schema.clone(),
+            ObjectOrReference::Ref { ref_path } | ObjectOrReference::SchemaRef { ref_path, .. } => {
+                let name = match ref_path.parse::<Ref>() {
+                    Ok(r) if r.kind() == RefType::Schema => r.name().to_owned(),
+                    _ => return,
+                };
+
+                // skip schemas already visited, which also stops recursive references
+                if !names.insert(name) {
+                    return;
+                }
+
+                match Schema::from_ref(self, ref_path) {
+                    Ok(schema) => schema,
+                    Err(_) => return,
+                }
+            }
+        };
+
+        for sub in schema.subschemas() {
+            self.collect_schema_refs(sub, names);
+        }
+
+        if let Some(additional) = schema.additional_properties_schema() {
+            self.collect_schema_refs(&additional, names);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deprecated_schema_usage() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Lint, version: "1" }
+paths:
+  /pets:
+    get:
+      parameters:
+      - name: filter
+        in: query
+        schema: { type: array, items: { $ref: '#/components/schemas/LegacyFilter' } }
+      responses:
+        "200":
+          description: Pets.
+          content:
+            application/json:
+              schema: { type: array, items: { $ref: '#/components/schemas/Pet' } }
+    post:
+      deprecated: true
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: '#/components/schemas/LegacyFilter' }
+      responses: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        owner: { $ref: '#/components/schemas/LegacyOwner' }
+        friend: { $ref: '#/components/schemas/Pet' }
+    LegacyOwner: { type: string, deprecated: true }
+    LegacyFilter: { type: string, deprecated: true }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let warnings = spec.lint();
+        assert_eq!(
+            warnings,
+            [
+                LintWarning::DeprecatedSchemaUsed {
+                    path: "/pets".to_owned(),
+                    method: Method::GET,
+                    schema: "LegacyFilter".to_owned(),
+                },
+                LintWarning::DeprecatedSchemaUsed {
+                    path: "/pets".to_owned(),
+                    method: Method::GET,
+                    schema: "LegacyOwner".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "GET /pets uses deprecated schema `LegacyFilter`"
+        );
+    }
+}
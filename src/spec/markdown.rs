@@ -0,0 +1,533 @@
+//! Markdown documentation generation.
+
+use std::fmt::Write as _;
+
+use super::{
+    MediaType, ObjectOrReference, OperationRef, Ref, Schema, SchemaType, Spec, SpecRefResolver,
+};
+
+impl Schema {
+    /// Renders the schema's properties as a Markdown table.
+    ///
+    /// The table has `Property`, `Type`, `Required`, `Description`, `Example` and `Constraints`
+    /// columns. Properties of nested objects are listed after their parent using dotted names.
+    /// `allOf` branches are merged into the table; `oneOf` and `anyOf` variants are listed in a
+    /// note above it.
+    pub fn to_markdown_table(&self, spec: &Spec) -> String {
+        let mut out = String::new();
+
+        let schema = if self.all_of.is_empty() {
+            self.clone()
+        } else {
+            let names = variant_names(&self.all_of);
+            writeln!(out, "_All of (`allOf`): {}._\n", names.join(", ")).unwrap();
+
+            self.merge_all_of(&SpecRefResolver::new(spec))
+                .unwrap_or_else(|_| self.clone())
+        };
+
+        for (keyword, variants) in [("oneOf", &schema.one_of), ("anyOf", &schema.any_of)] {
+            if !variants.is_empty() {
+                let names = variant_names(variants);
+                let kind = if keyword == "oneOf" { "One" } else { "Any" };
+                writeln!(
+                    out,
+                    "_{} of (`{}`): {}._\n",
+                    kind,
+                    keyword,
+                    names.join(", ")
+                )
+                .unwrap();
+            }
+        }
+
+        out.push_str("| Property | Type | Required | Description | Example | Constraints |\n");
+        out.push_str("| --- | --- | --- | --- | --- | --- |\n");
+
+        if schema.properties.is_empty() {
+            push_row(&mut out, "(value)", &schema, &type_label(&schema), true);
+        } else {
+            push_property_rows(&mut out, &schema, spec, "", &mut vec![]);
+        }
+
+        out
+    }
+}
+
+impl Spec {
+    /// Renders a Markdown API reference.
+    ///
+    /// Operations are grouped into a section per tag, in the order the tags are declared, with
+    /// untagged operations last. Each operation lists its parameters, request body and
+    /// responses, with schema tables and generated example bodies.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+
+        writeln!(out, "# API Reference\n").unwrap();
+        writeln!(
+            out,
+            "**{}** (version {})\n",
+            self.info.title, self.info.version
+        )
+        .unwrap();
+
+        if let Some(description) = &self.info.description {
+            writeln!(out, "{}\n", description.trim_end()).unwrap();
+        }
+
+        let mut groups = self.operations_by_tag();
+        let untagged = groups.remove("");
+
+        let mut sections = vec![];
+
+        for tag in &self.tags {
+            if let Some(ops) = groups.remove(&tag.name) {
+                sections.push((tag.name.clone(), tag.description.clone(), ops));
+            }
+        }
+
+        sections.extend(groups.into_iter().map(|(tag, ops)| (tag, None, ops)));
+
+        if let Some(ops) = untagged {
+            sections.push(("Other".to_owned(), None, ops));
+        }
+
+        for (tag, description, ops) in sections {
+            writeln!(out, "## {}\n", tag).unwrap();
+
+            if let Some(description) = description {
+                writeln!(out, "{}\n", description.trim_end()).unwrap();
+            }
+
+            for op in ops {
+                self.push_operation_markdown(&mut out, &op);
+            }
+        }
+
+        out
+    }
+
+    fn push_operation_markdown(&self, out: &mut String, op: &OperationRef<'_>) {
+        let operation = op.operation;
+
+        match &operation.summary {
+            Some(summary) => writeln!(out, "### `{} {}` - {}\n", op.method, op.path, summary),
+            None => writeln!(out, "### `{} {}`\n", op.method, op.path),
+        }
+        .unwrap();
+
+        if operation.deprecated == Some(true) {
+            writeln!(out, "**Deprecated.**\n").unwrap();
+        }
+
+        if let Some(id) = &operation.operation_id {
+            writeln!(out, "Operation ID: `{}`\n", id).unwrap();
+        }
+
+        if let Some(description) = &operation.description {
+            writeln!(out, "{}\n", description.trim_end()).unwrap();
+        }
+
+        let params = op
+            .path_item
+            .effective_parameters(operation, self)
+            .unwrap_or_default();
+
+        if !params.is_empty() {
+            writeln!(out, "#### Parameters\n").unwrap();
+            out.push_str("| Name | In | Type | Required | Description |\n");
+            out.push_str("| --- | --- | --- | --- | --- |\n");
+
+            for param in params {
+                let location = serde_json::to_value(&param.location).unwrap();
+
+                writeln!(
+                    out,
+                    "| `{}` | {} | {} | {} | {} |",
+                    param.name,
+                    location.as_str().unwrap_or_default(),
+                    escape_cell(&param.schema.as_ref().map(oor_label).unwrap_or_default()),
+                    if param.required == Some(true) {
+                        "yes"
+                    } else {
+                        "no"
+                    },
+                    escape_cell(param.description.as_deref().unwrap_or_default()),
+                )
+                .unwrap();
+            }
+
+            out.push('\n');
+        }
+
+        if let Some(body) = operation
+            .request_body
+            .as_ref()
+            .and_then(|body| body.resolve(self).ok())
+        {
+            writeln!(out, "#### Request Body\n").unwrap();
+
+            if let Some(description) = &body.description {
+                writeln!(out, "{}\n", description.trim_end()).unwrap();
+            }
+
+            for (media_type, content) in &body.content {
+                self.push_media_type_markdown(out, media_type, content);
+            }
+        }
+
+        if !operation.responses.is_empty() {
+            writeln!(out, "#### Responses\n").unwrap();
+        }
+
+        for (status, response) in operation.responses(self) {
+            match &response.description {
+                Some(description) => writeln!(out, "##### `{}` - {}\n", status, description),
+                None => writeln!(out, "##### `{}`\n", status),
+            }
+            .unwrap();
+
+            for (media_type, content) in &response.content {
+                self.push_media_type_markdown(out, media_type, content);
+            }
+        }
+    }
+
+    fn push_media_type_markdown(&self, out: &mut String, media_type: &str, content: &MediaType) {
+        writeln!(out, "Content type: `{}`\n", media_type).unwrap();
+
+        let schema = match content.schema.as_ref().map(|oor| oor.resolve(self)) {
+            Some(Ok(schema)) => schema,
+            _ => return,
+        };
+
+        let is_complex = !schema.properties.is_empty()
+            || !schema.all_of.is_empty()
+            || !schema.one_of.is_empty()
+            || !schema.any_of.is_empty();
+
+        if is_complex {
+            writeln!(out, "{}", schema.to_markdown_table(self)).unwrap();
+        } else {
+            writeln!(out, "Type: `{}`\n", type_label(&schema)).unwrap();
+        }
+
+        let example = content
+            .examples(self)
+            .into_values()
+            .find_map(|example| example.value)
+            .unwrap_or_else(|| schema.example_value(self));
+
+        if let Ok(example) = serde_json::to_string_pretty(&example) {
+            writeln!(out, "```json\n{}\n```\n", example).unwrap();
+        }
+    }
+}
+
+/// Depth past which nested object properties are not expanded.
+const NESTING_LIMIT: usize = 4;
+
+fn push_property_rows(
+    out: &mut String,
+    schema: &Schema,
+    spec: &Spec,
+    prefix: &str,
+    visiting: &mut Vec<String>,
+) {
+    for (name, oor) in &schema.properties {
+        let prop = match oor.resolve(spec) {
+            Ok(prop) => prop,
+            Err(_) => continue,
+        };
+
+        let path = format!("{}{}", prefix, name);
+        let label = oor_label(oor);
+
+        push_row(out, &path, &prop, &label, schema.required.contains(name));
+
+        let is_object = prop.schema_type == Some(SchemaType::Object) || !prop.properties.is_empty();
+
+        if !is_object || visiting.len() >= NESTING_LIMIT {
+            continue;
+        }
+
+        let ref_path = match oor.ref_path() {
+            Some(ref_path) if visiting.iter().any(|visited| visited == ref_path) => continue,
+            Some(ref_path) => ref_path.to_owned(),
+            None => String::new(),
+        };
+
+        visiting.push(ref_path);
+        push_property_rows(out, &prop, spec, &format!("{}.", path), visiting);
+        visiting.pop();
+    }
+}
+
+fn push_row(out: &mut String, name: &str, schema: &Schema, label: &str, required: bool) {
+    let example = schema
+        .examples
+        .first()
+        .or(schema.default.as_ref())
+        .map(|example| format!("`{}`", example))
+        .unwrap_or_default();
+
+    writeln!(
+        out,
+        "| `{}` | {} | {} | {} | {} | {} |",
+        name,
+        escape_cell(label),
+        if required { "yes" } else { "no" },
+        escape_cell(schema.description.as_deref().unwrap_or_default()),
+        escape_cell(&example),
+        escape_cell(&constraints(schema).join(", ")),
+    )
+    .unwrap();
+}
+
+fn type_label(schema: &Schema) -> String {
+    match schema.schema_type {
+        Some(SchemaType::Array) => match schema.items.as_deref() {
+            Some(items) => format!("array<{}>", oor_label(items)),
+            None => "array".to_owned(),
+        },
+        Some(typ) => typ.to_string(),
+        None if !schema.one_of.is_empty() => "oneOf".to_owned(),
+        None if !schema.any_of.is_empty() => "anyOf".to_owned(),
+        None if !schema.all_of.is_empty() => "allOf".to_owned(),
+        None if !schema.properties.is_empty() => "object".to_owned(),
+        None => "any".to_owned(),
+    }
+}
+
+fn oor_label(oor: &ObjectOrReference<Schema>) -> String {
+    match oor {
+        ObjectOrReference::Ref { ref_path } | ObjectOrReference::SchemaRef { ref_path, .. } => {
+            ref_label(ref_path)
+        }
+        ObjectOrReference::Object(schema) => type_label(schema),
+    }
+}
+
+fn ref_label(ref_path: &str) -> String {
+    ref_path
+        .parse::<Ref>()
+        .map(|r| r.name)
+        .unwrap_or_else(|_| ref_path.to_owned())
+}
+
+fn variant_names(variants: &[ObjectOrReference<Schema>]) -> Vec<String> {
+    variants
+        .iter()
+        .map(|oor| match oor {
+            ObjectOrReference::Ref { ref_path } | ObjectOrReference::SchemaRef { ref_path, .. } => {
+                format!("`{}`", ref_label(ref_path))
+            }
+            ObjectOrReference::Object(schema) => match &schema.title {
+                Some(title) => format!("`{}`", title),
+                None => format!("inline {}", type_label(schema)),
+            },
+        })
+        .collect()
+}
+
+fn constraints(schema: &Schema) -> Vec<String> {
+    let mut constraints = vec![];
+
+    macro_rules! push {
+        ($label:literal, $field:expr) => {
+            if let Some(val) = &$field {
+                constraints.push(format!(concat!($label, ": {}"), val));
+            }
+        };
+    }
+
+    push!("format", schema.format);
+    push!(
+        "pattern",
+        schema.pattern.as_ref().map(|p| format!("`{}`", p))
+    );
+    let (minimum, exclusive_minimum) = schema.minimum_bounds();
+    let (maximum, exclusive_maximum) = schema.maximum_bounds();
+    push!("minimum", minimum);
+    push!("exclusive minimum", exclusive_minimum);
+    push!("maximum", maximum);
+    push!("exclusive maximum", exclusive_maximum);
+    push!("multiple of", schema.multiple_of);
+    push!("min length", schema.min_length);
+    push!("max length", schema.max_length);
+    push!("min items", schema.min_items);
+    push!("max items", schema.max_items);
+
+    if schema.unique_items == Some(true) {
+        constraints.push("unique items".to_owned());
+    }
+
+    if !schema.enum_values.is_empty() {
+        let values = schema
+            .enum_values
+            .iter()
+            .map(|val| format!("`{}`", val))
+            .collect::<Vec<_>>();
+        constraints.push(format!("one of: {}", values.join(", ")));
+    }
+
+    if schema.read_only == Some(true) {
+        constraints.push("read only".to_owned());
+    }
+
+    if schema.write_only == Some(true) {
+        constraints.push("write only".to_owned());
+    }
+
+    if schema.deprecated == Some(true) {
+        constraints.push("deprecated".to_owned());
+    }
+
+    constraints
+}
+
+/// Escapes text for use in a Markdown table cell.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn schema_markdown_table() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  schemas:
+    Category:
+      type: object
+      properties:
+        name: { type: string, description: "Category | name" }
+    Pet:
+      allOf:
+      - $ref: '#/components/schemas/Named'
+      - type: object
+        required: [id]
+        properties:
+          id: { type: integer, format: int64, minimum: 1, examples: [10] }
+          category: { $ref: '#/components/schemas/Category' }
+          tags: { type: array, items: { type: string } }
+    Named:
+      type: object
+      required: [name]
+      properties:
+        name: { type: string, maxLength: 20 }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let pet = spec.components.as_ref().unwrap().schemas["Pet"]
+            .resolve(&spec)
+            .unwrap();
+
+        assert_eq!(
+            pet.to_markdown_table(&spec),
+            "\
+_All of (`allOf`): `Named`, inline object._
+
+| Property | Type | Required | Description | Example | Constraints |
+| --- | --- | --- | --- | --- | --- |
+| `category` | Category | no |  |  |  |
+| `category.name` | string | no | Category \\| name |  |  |
+| `id` | integer | yes |  | `10` | format: int64, minimum: 1 |
+| `name` | string | yes |  |  | max length: 20 |
+| `tags` | array<string> | no |  |  |  |
+"
+        );
+
+        let tags = &pet.all_of[1].resolve(&spec).unwrap().properties["tags"];
+        let tags = tags.resolve(&spec).unwrap();
+        assert!(tags
+            .to_markdown_table(&spec)
+            .contains("| `(value)` | array<string> | yes |"));
+    }
+
+    #[test]
+    fn spec_markdown() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1.0" }
+tags:
+- { name: pets, description: Everything about pets. }
+paths:
+  /pets/{petId}:
+    parameters:
+    - { name: petId, in: path, required: true, schema: { type: integer } }
+    get:
+      tags: [pets]
+      summary: Get a pet
+      operationId: getPet
+      responses:
+        "200":
+          description: The pet.
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  name: { type: string, examples: [Rex] }
+  /health:
+    get:
+      responses:
+        "204": { description: Healthy. }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.to_markdown(),
+            r#"# API Reference
+
+**Pets** (version 1.0)
+
+## pets
+
+Everything about pets.
+
+### `GET /pets/{petId}` - Get a pet
+
+Operation ID: `getPet`
+
+#### Parameters
+
+| Name | In | Type | Required | Description |
+| --- | --- | --- | --- | --- |
+| `petId` | path | integer | yes |  |
+
+#### Responses
+
+##### `200` - The pet.
+
+Content type: `application/json`
+
+| Property | Type | Required | Description | Example | Constraints |
+| --- | --- | --- | --- | --- | --- |
+| `name` | string | no |  | `"Rex"` |  |
+
+```json
+{
+  "name": "Rex"
+}
+```
+
+## Other
+
+### `GET /health`
+
+#### Responses
+
+##### `204` - Healthy.
+
+"#
+        );
+    }
+}
@@ -1,5 +1,7 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
+use derive_more::Display;
+use http::Method;
 use serde::{Deserialize, Serialize};
 
 use super::{Encoding, Error, Example, MediaTypeExamples, ObjectOrReference, Spec};
@@ -45,3 +47,294 @@ impl MediaType {
             .unwrap_or_default()
     }
 }
+
+/// A request body or response content map that may trip up tools expecting a single media type.
+#[derive(Debug, Clone, PartialEq, Eq, Display, derive_more::Error)]
+pub enum ContentTypeError {
+    /// The content map declares more than one media type.
+    #[display(fmt = "{} {} {} has multiple content types", method, path, location)]
+    MultipleContentTypes {
+        path: String,
+        method: Method,
+
+        /// `requestBody` or `responses/{status}`.
+        location: String,
+
+        content_types: Vec<String>,
+    },
+
+    /// A wildcard media type overlaps with a more specific one in the same content map.
+    #[display(
+        fmt = "{} {} {} has {} overlapping with {}",
+        method,
+        path,
+        location,
+        wildcard,
+        specific
+    )]
+    AmbiguousWildcard {
+        path: String,
+        method: Method,
+
+        /// `requestBody` or `responses/{status}`.
+        location: String,
+
+        /// The wildcard media type, e.g. `application/*`.
+        wildcard: String,
+
+        /// The more specific media type, e.g. `application/json`.
+        specific: String,
+    },
+}
+
+impl Spec {
+    /// Checks that the request body and responses of each operation declare a single media type.
+    ///
+    /// Content maps with several media types are reported, as are wildcards (`*/*` or e.g.
+    /// `application/*`) declared alongside more specific media types they cover. Request bodies
+    /// and responses that cannot be resolved are skipped.
+    pub fn validate_content_type_schemas(&self) -> Vec<ContentTypeError> {
+        let mut errors = vec![];
+
+        for op in self.operation_refs() {
+            let mut contents = vec![];
+
+            if let Some(Ok(body)) = op
+                .operation
+                .request_body
+                .as_ref()
+                .map(|body| body.resolve(self))
+            {
+                contents.push(("requestBody".to_owned(), body.content));
+            }
+
+            for (status, response) in &op.operation.responses {
+                if let Ok(response) = response.resolve(self) {
+                    contents.push((format!("responses/{}", status), response.content));
+                }
+            }
+
+            for (location, content) in contents {
+                if content.len() < 2 {
+                    continue;
+                }
+
+                let content_types = content.into_keys().collect::<Vec<_>>();
+
+                for wildcard in &content_types {
+                    for specific in &content_types {
+                        if wildcard != specific && media_type_covers(wildcard, specific) {
+                            errors.push(ContentTypeError::AmbiguousWildcard {
+                                path: op.path.to_owned(),
+                                method: op.method.clone(),
+                                location: location.clone(),
+                                wildcard: wildcard.clone(),
+                                specific: specific.clone(),
+                            });
+                        }
+                    }
+                }
+
+                errors.push(ContentTypeError::MultipleContentTypes {
+                    path: op.path.to_owned(),
+                    method: op.method.clone(),
+                    location,
+                    content_types,
+                });
+            }
+        }
+
+        errors
+    }
+
+    /// Returns the media types accepted by the request body of the operation with the given ID,
+    /// sorted and deduplicated.
+    ///
+    /// Returns an empty list if the operation does not exist, has no request body, or its
+    /// request body cannot be resolved.
+    pub fn operation_request_content_types(&self, operation_id: &str) -> Vec<String> {
+        self.operation_by_id(operation_id)
+            .and_then(|op| op.operation.request_body.as_ref())
+            .and_then(|body| body.resolve(self).ok())
+            .map(|body| body.content.into_keys().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the media types produced by the operation with the given ID for a status code,
+    /// sorted and deduplicated.
+    ///
+    /// The response is looked up as in [`generate_mock_response`](Self::generate_mock_response).
+    pub fn operation_response_content_types(&self, operation_id: &str, status: u16) -> Vec<String> {
+        self.operation_by_id(operation_id)
+            .and_then(|op| op.operation.response_for_status(status))
+            .and_then(|response| response.resolve(self).ok())
+            .map(|response| response.content.into_keys().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns every media type used by a request body or response in the spec, including
+    /// those declared in components.
+    pub fn all_content_types(&self) -> BTreeSet<String> {
+        let mut bodies = self
+            .operation_refs()
+            .filter_map(|op| op.operation.request_body.as_ref())
+            .collect::<Vec<_>>();
+        let mut responses = self
+            .operation_refs()
+            .flat_map(|op| op.operation.responses.values())
+            .collect::<Vec<_>>();
+
+        if let Some(components) = &self.components {
+            bodies.extend(components.request_bodies.values());
+            responses.extend(components.responses.values());
+        }
+
+        let body_types = bodies
+            .into_iter()
+            .filter_map(|body| body.resolve(self).ok())
+            .flat_map(|body| body.content.into_keys());
+        let response_types = responses
+            .into_iter()
+            .filter_map(|response| response.resolve(self).ok())
+            .flat_map(|response| response.content.into_keys());
+
+        body_types.chain(response_types).collect()
+    }
+}
+
+/// Returns true if `range` is a wildcard media type (`*/*` or e.g. `application/*`) that covers
+/// `media_type`. Parameters are ignored.
+fn media_type_covers(range: &str, media_type: &str) -> bool {
+    let essence = |media_type: &str| {
+        let media_type = media_type.split(';').next().unwrap_or_default().trim();
+        media_type
+            .split_once('/')
+            .map(|(kind, subtype)| (kind.to_ascii_lowercase(), subtype.to_ascii_lowercase()))
+    };
+
+    match (essence(range), essence(media_type)) {
+        (Some((kind, subtype)), Some((other_kind, _))) if subtype == "*" => {
+            kind == "*" || kind == other_kind
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_type_ambiguity() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Files, version: "1" }
+paths:
+  /files:
+    post:
+      requestBody:
+        content:
+          application/*: {}
+          application/json: {}
+          text/plain: {}
+      responses:
+        "201":
+          description: Created.
+          content:
+            application/json: {}
+    get:
+      responses:
+        "200":
+          description: OK.
+          content:
+            "*/*": {}
+            image/png: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let errors = spec
+            .validate_content_type_schemas()
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            [
+                "GET /files responses/200 has */* overlapping with image/png",
+                "GET /files responses/200 has multiple content types",
+                "POST /files requestBody has application/* overlapping with application/json",
+                "POST /files requestBody has multiple content types",
+            ]
+        );
+
+        assert!(media_type_covers("*/*", "text/plain"));
+        assert!(media_type_covers("Text/*; charset=utf-8", "text/html"));
+        assert!(!media_type_covers("text/plain", "text/plain"));
+        assert!(!media_type_covers("image/*", "text/plain"));
+    }
+
+    #[test]
+    fn content_types() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets:
+    post:
+      operationId: createPet
+      requestBody:
+        content:
+          application/xml: {}
+          application/json: {}
+      responses:
+        "201":
+          description: Created.
+          content:
+            application/json: {}
+        4XX: { $ref: '#/components/responses/Error' }
+components:
+  requestBodies:
+    Upload:
+      content:
+        image/png: {}
+  responses:
+    Error:
+      description: Error.
+      content:
+        application/problem+json: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.operation_request_content_types("createPet"),
+            ["application/json", "application/xml"]
+        );
+        assert_eq!(
+            spec.operation_response_content_types("createPet", 201),
+            ["application/json"]
+        );
+        assert_eq!(
+            spec.operation_response_content_types("createPet", 404),
+            ["application/problem+json"]
+        );
+        assert!(spec
+            .operation_response_content_types("createPet", 500)
+            .is_empty());
+        assert!(spec.operation_request_content_types("nope").is_empty());
+
+        assert_eq!(
+            spec.all_content_types().into_iter().collect::<Vec<_>>(),
+            [
+                "application/json",
+                "application/problem+json",
+                "application/xml",
+                "image/png"
+            ]
+        );
+    }
+}
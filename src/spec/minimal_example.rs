@@ -0,0 +1,409 @@
+//! Generation of minimal values that satisfy schemas.
+
+use std::ops::Bound;
+
+use derive_more::{Display, Error, From};
+use serde_json::{json, Map, Value};
+
+use super::{
+    schema::example_string, ObjectOrReference, PatternError, RefError, Schema, SchemaType, Spec,
+    SpecRefResolver,
+};
+
+/// Depth of nested schemas after which generation gives up, e.g. on recursive required
+/// properties.
+const DEPTH_LIMIT: usize = 32;
+
+/// Longest string tried beyond `minLength` when looking for one that matches a `pattern`.
+const PATTERN_SEARCH_LENGTH: u64 = 32;
+
+/// Errors generating a [minimal example](Schema::minimum_valid_example).
+#[derive(Debug, Clone, PartialEq, Display, Error, From)]
+pub enum ExampleGenError {
+    #[display(fmt = "Reference error")]
+    Ref(RefError),
+
+    #[display(fmt = "Pattern error")]
+    Pattern(PatternError),
+
+    /// No value satisfies the schema's constraints, e.g., its `minimum` exceeds its `maximum`.
+    #[display(fmt = "Unsatisfiable schema: {}", _0)]
+    #[from(ignore)]
+    Unsatisfiable(#[error(not(source))] &'static str),
+
+    /// No string matching the pattern could be found.
+    #[display(fmt = "No string matching pattern {:?} could be generated", _0)]
+    #[from(ignore)]
+    UnsupportedPattern(#[error(not(source))] String),
+
+    #[display(fmt = "Schemas are nested too deeply")]
+    #[from(ignore)]
+    DepthLimit,
+}
+
+impl Schema {
+    /// Constructs the simplest value that satisfies this schema, for use as a minimal test
+    /// fixture.
+    ///
+    /// Unlike [`example_value`](Self::example_value), declared `examples` and `default`s are
+    /// ignored. Integers and numbers are the value closest to zero within their bounds, strings
+    /// are as short as `minLength` and `format` allow, arrays have `minItems` items and objects
+    /// only have their `required` properties, plus optional ones up to `minProperties`. A `const`
+    /// yields its value, an `enum` its first value and `oneOf` and `anyOf` their first branch;
+    /// `allOf` branches are merged.
+    ///
+    /// Strings matching a `pattern` are searched for among repetitions of a few characters, so
+    /// only simple patterns are supported.
+    pub fn minimum_valid_example(&self, spec: &Spec) -> Result<Value, ExampleGenError> {
+        self.minimum_valid_example_at_depth(spec, 0)
+    }
+
+    fn minimum_valid_example_at_depth(
+        &self,
+        spec: &Spec,
+        depth: usize,
+    ) -> Result<Value, ExampleGenError> {
+        if depth > DEPTH_LIMIT {
+            return Err(ExampleGenError::DepthLimit);
+        }
+
+        let resolver = SpecRefResolver::new(spec);
+        let schema = self.merge_all_of(&resolver)?;
+
+        let sub_example = |oor: &ObjectOrReference<Schema>| {
+            oor.resolve(spec)?
+                .minimum_valid_example_at_depth(spec, depth + 1)
+        };
+
+        if let Some(value) = schema
+            .const_value
+            .as_ref()
+            .or_else(|| schema.enum_values.first())
+        {
+            return Ok(value.clone());
+        }
+
+        if let Some(branch) = schema.one_of.first().or_else(|| schema.any_of.first()) {
+            return sub_example(branch);
+        }
+
+        match schema.schema_type {
+            Some(SchemaType::Boolean) => Ok(Value::Bool(false)),
+            Some(SchemaType::Integer) => minimal_integer(&schema, spec),
+            Some(SchemaType::Number) => minimal_number(&schema),
+            Some(SchemaType::String) => minimal_string(&schema),
+
+            Some(SchemaType::Array) => {
+                let count = schema.min_items.unwrap_or(0);
+
+                if schema.max_items.map_or(false, |max| max < count) {
+                    return Err(ExampleGenError::Unsatisfiable("minItems exceeds maxItems"));
+                }
+
+                let items = (0..count as usize)
+                    .map(
+                        |index| match schema.prefix_items.get(index).or(schema.items.as_deref()) {
+                            Some(item) => sub_example(item),
+                            None => Ok(Value::Null),
+                        },
+                    )
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let has_duplicates = items
+                    .iter()
+                    .enumerate()
+                    .any(|(index, item)| items[..index].contains(item));
+                if schema.unique_items == Some(true) && has_duplicates {
+                    return Err(ExampleGenError::Unsatisfiable(
+                        "uniqueItems requires distinct items",
+                    ));
+                }
+
+                Ok(Value::Array(items))
+            }
+
+            Some(SchemaType::Object) => minimal_object(&schema, spec, depth),
+            None if !schema.properties.is_empty() || !schema.required.is_empty() => {
+                minimal_object(&schema, spec, depth)
+            }
+
+            Some(SchemaType::Null) | None => Ok(Value::Null),
+        }
+    }
+}
+
+pub(crate) fn minimal_integer(schema: &Schema, spec: &Spec) -> Result<Value, ExampleGenError> {
+    let (lower, upper) = schema
+        .integer_range(spec)
+        .unwrap_or((Bound::Unbounded, Bound::Unbounded));
+
+    let value = match (lower, upper) {
+        (Bound::Included(lower), Bound::Included(upper)) if lower > upper => {
+            return Err(ExampleGenError::Unsatisfiable("minimum exceeds maximum"));
+        }
+        // the range is empty, see `Schema::integer_range`
+        (Bound::Excluded(_), _) | (_, Bound::Excluded(_)) => {
+            return Err(ExampleGenError::Unsatisfiable("no multiple in range"));
+        }
+        (Bound::Included(lower), _) if lower > 0 => lower,
+        (_, Bound::Included(upper)) if upper < 0 => upper,
+        _ => 0,
+    };
+
+    Ok(json!(value))
+}
+
+pub(crate) fn minimal_number(schema: &Schema) -> Result<Value, ExampleGenError> {
+    let (minimum, exclusive_minimum) = schema.minimum_bounds();
+    let (maximum, exclusive_maximum) = schema.maximum_bounds();
+
+    let minimum = minimum.map(|min| min.as_f64());
+    let maximum = maximum.map(|max| max.as_f64());
+    let exclusive_minimum = exclusive_minimum.map(|min| min.as_f64());
+    let exclusive_maximum = exclusive_maximum.map(|max| max.as_f64());
+
+    let within_bounds = |value: f64| {
+        minimum.map_or(true, |min| value >= min)
+            && maximum.map_or(true, |max| value <= max)
+            && exclusive_minimum.map_or(true, |min| value > min)
+            && exclusive_maximum.map_or(true, |max| value < max)
+    };
+
+    // the tightest bounds, along with whether they are exclusive
+    let lower = match (minimum, exclusive_minimum) {
+        (Some(min), Some(exclusive)) if exclusive >= min => Some((exclusive, true)),
+        (Some(min), _) => Some((min, false)),
+        (None, exclusive) => exclusive.map(|min| (min, true)),
+    };
+    let upper = match (maximum, exclusive_maximum) {
+        (Some(max), Some(exclusive)) if exclusive <= max => Some((exclusive, true)),
+        (Some(max), _) => Some((max, false)),
+        (None, exclusive) => exclusive.map(|max| (max, true)),
+    };
+
+    let mut value = match (lower, upper) {
+        _ if within_bounds(0.0) => 0.0,
+        (Some((min, false)), _) if min > 0.0 => min,
+        (Some((min, true)), Some((max, _))) if min >= 0.0 => (min + max) / 2.0,
+        (Some((min, true)), None) if min >= 0.0 => min.floor() + 1.0,
+        (_, Some((max, false))) => max,
+        (Some((min, _)), Some((max, true))) => (min + max) / 2.0,
+        (None, Some((max, true))) => max.ceil() - 1.0,
+        (_, None) => 0.0,
+    };
+
+    if let Some(step) = schema
+        .multiple_of
+        .as_ref()
+        .and_then(serde_json::Number::as_f64)
+        .filter(|step| *step > 0.0)
+    {
+        value = if value >= 0.0 {
+            (value / step).ceil() * step
+        } else {
+            (value / step).floor() * step
+        };
+    }
+
+    if !within_bounds(value) {
+        return Err(ExampleGenError::Unsatisfiable(
+            "no number satisfies the bounds",
+        ));
+    }
+
+    Ok(json!(value))
+}
+
+fn minimal_string(schema: &Schema) -> Result<Value, ExampleGenError> {
+    let min_length = schema.min_length.unwrap_or(0);
+    let max_length = schema.max_length.unwrap_or(u64::MAX);
+
+    if max_length < min_length {
+        return Err(ExampleGenError::Unsatisfiable(
+            "minLength exceeds maxLength",
+        ));
+    }
+
+    let base = example_string(schema.format.as_ref()).unwrap_or_default();
+    let base_length = base.chars().count() as u64;
+
+    if base_length > max_length {
+        return Err(ExampleGenError::Unsatisfiable(
+            "format requires strings longer than maxLength",
+        ));
+    }
+
+    let padded = |length: u64, pad: char| {
+        let mut string = base.to_owned();
+        string.extend(std::iter::repeat(pad).take(length.saturating_sub(base_length) as usize));
+        string
+    };
+
+    let regex = match &schema.pattern {
+        Some(_) => schema.compile_pattern()?,
+        None => return Ok(Value::String(padded(min_length, 'a'))),
+    };
+
+    let shortest = min_length.max(base_length);
+    let longest = max_length.min(shortest + PATTERN_SEARCH_LENGTH);
+
+    (shortest..=longest)
+        .flat_map(|length| ['a', '0', 'A'].map(|pad| padded(length, pad)))
+        .find(|string| regex.is_match(string))
+        .map(Value::String)
+        .ok_or_else(|| {
+            ExampleGenError::UnsupportedPattern(schema.pattern.clone().unwrap_or_default())
+        })
+}
+
+fn minimal_object(schema: &Schema, spec: &Spec, depth: usize) -> Result<Value, ExampleGenError> {
+    let property_schema = |name: &str| -> Option<ObjectOrReference<Schema>> {
+        if let Some(oor) = schema.properties.get(name) {
+            return Some(oor.clone());
+        }
+
+        schema.additional_properties_schema()
+    };
+
+    let mut obj = Map::new();
+
+    for name in &schema.required {
+        let value = match property_schema(name) {
+            Some(oor) => oor
+                .resolve(spec)?
+                .minimum_valid_example_at_depth(spec, depth + 1)?,
+            None => Value::Null,
+        };
+
+        obj.insert(name.clone(), value);
+    }
+
+    let min_properties = schema.min_properties.unwrap_or(0) as usize;
+
+    for (name, oor) in &schema.properties {
+        if obj.len() >= min_properties {
+            break;
+        }
+
+        if !obj.contains_key(name) {
+            let value = oor
+                .resolve(spec)?
+                .minimum_valid_example_at_depth(spec, depth + 1)?;
+            obj.insert(name.clone(), value);
+        }
+    }
+
+    if obj.len() < min_properties {
+        return Err(ExampleGenError::Unsatisfiable(
+            "minProperties exceeds the declared properties",
+        ));
+    }
+
+    if schema
+        .max_properties
+        .map_or(false, |max| (obj.len() as u64) > max)
+    {
+        return Err(ExampleGenError::Unsatisfiable(
+            "required properties exceed maxProperties",
+        ));
+    }
+
+    Ok(Value::Object(obj))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::FromRef;
+
+    const SPEC: &str = r#"openapi: "3.1.0"
+info: { title: Fixtures, version: "1" }
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name, age, tags, owner]
+      properties:
+        name: { type: string, minLength: 3, examples: [Rex] }
+        age: { type: integer, minimum: 1, default: 5 }
+        weight: { type: number, exclusiveMinimum: 0, maximum: 10 }
+        tags: { type: array, minItems: 2, items: { type: string } }
+        owner: { $ref: '#/components/schemas/Owner' }
+        nickname: { type: string }
+    Owner:
+      allOf:
+      - { type: object, required: [email], properties: { email: { type: string, format: email } } }
+      - { required: [id], properties: { id: { type: integer, multipleOf: 5, minimum: 3 } } }
+    Code: { type: string, pattern: '^[0-9]{3}$' }
+    Temperature: { type: number, maximum: -2.5 }
+    Color: { type: string, enum: [red, green] }
+    Node:
+      type: object
+      required: [next]
+      properties: { next: { $ref: '#/components/schemas/Node' } }
+    Impossible: { type: integer, minimum: 10, maximum: 5 }
+    Unique: { type: array, minItems: 2, uniqueItems: true, items: { type: boolean } }
+"#;
+
+    fn example(spec: &Spec, name: &str) -> Result<Value, ExampleGenError> {
+        spec.components.as_ref().unwrap().schemas[name]
+            .resolve(spec)
+            .unwrap()
+            .minimum_valid_example(spec)
+    }
+
+    #[test]
+    fn minimal_examples() {
+        let spec = crate::from_reader(SPEC.as_bytes()).unwrap();
+
+        assert_eq!(
+            example(&spec, "Pet").unwrap(),
+            json!({
+                "name": "aaa",
+                "age": 1,
+                "tags": ["", ""],
+                "owner": { "email": "user@example.com", "id": 5 },
+            })
+        );
+        assert_eq!(example(&spec, "Code").unwrap(), json!("000"));
+        assert_eq!(example(&spec, "Temperature").unwrap(), json!(-2.5));
+        assert_eq!(example(&spec, "Color").unwrap(), json!("red"));
+
+        let weight = Schema::from_ref(&spec, "#/components/schemas/Pet")
+            .unwrap()
+            .properties["weight"]
+            .resolve(&spec)
+            .unwrap();
+        assert_eq!(weight.minimum_valid_example(&spec).unwrap(), json!(5.0));
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn minimal_examples_are_valid() {
+        let spec = crate::from_reader(SPEC.as_bytes()).unwrap();
+
+        for name in ["Pet", "Code", "Temperature", "Color"] {
+            let schema = Schema::from_ref(&spec, &format!("#/components/schemas/{}", name));
+            let value = example(&spec, name).unwrap();
+            assert!(schema.unwrap().strict_validates(&value, &spec), "{}", name);
+        }
+    }
+
+    #[test]
+    fn unsatisfiable_examples() {
+        let spec = crate::from_reader(SPEC.as_bytes()).unwrap();
+
+        assert_eq!(
+            example(&spec, "Node").unwrap_err(),
+            ExampleGenError::DepthLimit
+        );
+        assert_eq!(
+            example(&spec, "Impossible").unwrap_err(),
+            ExampleGenError::Unsatisfiable("minimum exceeds maximum")
+        );
+        assert_eq!(
+            example(&spec, "Unique").unwrap_err(),
+            ExampleGenError::Unsatisfiable("uniqueItems requires distinct items")
+        );
+    }
+}
@@ -0,0 +1,375 @@
+//! Mock request and response generation for testing API clients and servers.
+
+use std::collections::BTreeMap;
+
+use derive_more::{Display, Error, From};
+use http::Method;
+use serde_json::Value as JsonValue;
+
+use super::{ParamLoc, RefError, Spec};
+
+/// Mock generation errors.
+#[derive(Debug, Clone, PartialEq, Display, Error, From)]
+pub enum MockGenError {
+    #[display(fmt = "Operation ID not found: {}", _0)]
+    #[from(ignore)]
+    OperationNotFound(#[error(not(source))] String),
+
+    #[display(fmt = "Operation has no request body: {}", _0)]
+    #[from(ignore)]
+    NoRequestBody(#[error(not(source))] String),
+
+    #[display(fmt = "Response not found: {} {}", operation_id, status)]
+    #[from(ignore)]
+    ResponseNotFound { operation_id: String, status: u16 },
+
+    #[display(fmt = "Reference error")]
+    Ref(RefError),
+}
+
+/// A request that is valid against the spec for a single operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockRequest {
+    pub method: Method,
+
+    /// Path with all path parameters substituted.
+    pub path: String,
+
+    pub headers: BTreeMap<String, String>,
+
+    pub query_params: BTreeMap<String, String>,
+
+    /// Example body for the first media type of the request body, if there is one.
+    pub body: Option<JsonValue>,
+}
+
+/// A response that is valid against the spec for a single operation and status code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockResponse {
+    pub status: u16,
+
+    pub headers: BTreeMap<String, String>,
+
+    /// Example body for the first media type of the response, if there is one.
+    pub body: Option<JsonValue>,
+}
+
+impl Spec {
+    /// Generates a request for the operation with the given ID.
+    ///
+    /// Path parameters and required query, header and cookie parameters are filled with
+    /// [example values](crate::Schema::example_value). If the operation accepts a request body,
+    /// one is generated for its first media type along with a matching `Content-Type` header.
+    pub fn generate_mock_request(&self, operation_id: &str) -> Result<MockRequest, MockGenError> {
+        let op = self
+            .operation_by_id(operation_id)
+            .ok_or_else(|| MockGenError::OperationNotFound(operation_id.to_owned()))?;
+
+        let mut req = MockRequest {
+            method: op.method,
+            path: op.path.to_owned(),
+            headers: BTreeMap::new(),
+            query_params: BTreeMap::new(),
+            body: None,
+        };
+
+        let mut cookies = vec![];
+
+        for param in op.path_item.effective_parameters(op.operation, self)? {
+            if param.location != ParamLoc::Path && !param.required.unwrap_or(false) {
+                continue;
+            }
+
+            let value = param
+                .schema
+                .as_ref()
+                .and_then(|schema| schema.resolve(self).ok())
+                .map(|schema| schema.example_value(self))
+                .unwrap_or(JsonValue::Null);
+            let value = param_value_string(&value);
+
+            match param.location {
+                ParamLoc::Path => {
+                    req.path = req.path.replace(&format!("{{{}}}", param.name), &value);
+                }
+                ParamLoc::Query => {
+                    req.query_params.insert(param.name, value);
+                }
+                ParamLoc::Header => {
+                    req.headers.insert(param.name, value);
+                }
+                ParamLoc::Cookie => cookies.push(format!("{}={}", param.name, value)),
+            }
+        }
+
+        if !cookies.is_empty() {
+            req.headers.insert("Cookie".to_owned(), cookies.join("; "));
+        }
+
+        if let Some(body) = &op.operation.request_body {
+            let body = body.resolve(self)?;
+
+            if let Some((media_type, content)) = body.content.iter().next() {
+                req.headers
+                    .insert("Content-Type".to_owned(), media_type.clone());
+
+                if let Some(schema) = &content.schema {
+                    req.body = Some(schema.resolve(self)?.example_value(self));
+                }
+            }
+        }
+
+        Ok(req)
+    }
+
+    /// Generates a request for the operation with the given ID, failing with
+    /// [`MockGenError::NoRequestBody`] if the operation does not accept a request body.
+    pub fn generate_mock_request_with_body(
+        &self,
+        operation_id: &str,
+    ) -> Result<MockRequest, MockGenError> {
+        let op = self
+            .operation_by_id(operation_id)
+            .ok_or_else(|| MockGenError::OperationNotFound(operation_id.to_owned()))?;
+
+        if op.operation.request_body.is_none() {
+            return Err(MockGenError::NoRequestBody(operation_id.to_owned()));
+        }
+
+        self.generate_mock_request(operation_id)
+    }
+
+    /// Generates a response for the operation with the given ID and status code.
+    ///
+    /// The response is looked up by exact status code, then by status code range (e.g., `2XX`),
+    /// then falls back to the `default` response. Each documented header is filled with an
+    /// [example value](crate::Schema::example_value). The body uses the first example of the
+    /// response's first media type, or else an example value generated from its schema.
+    pub fn generate_mock_response(
+        &self,
+        operation_id: &str,
+        status: u16,
+    ) -> Result<MockResponse, MockGenError> {
+        let operation = self
+            .operation_by_id(operation_id)
+            .ok_or_else(|| MockGenError::OperationNotFound(operation_id.to_owned()))?
+            .operation;
+
+        let response = operation
+            .response_for_status(status)
+            .ok_or_else(|| MockGenError::ResponseNotFound {
+                operation_id: operation_id.to_owned(),
+                status,
+            })?
+            .resolve(self)?;
+
+        let mut res = MockResponse {
+            status,
+            headers: BTreeMap::new(),
+            body: None,
+        };
+
+        for (name, header) in &response.headers {
+            let value = header
+                .resolve(self)?
+                .schema
+                .as_ref()
+                .and_then(|schema| schema.resolve(self).ok())
+                .map(|schema| schema.example_value(self))
+                .unwrap_or(JsonValue::Null);
+
+            res.headers.insert(name.clone(), param_value_string(&value));
+        }
+
+        if let Some((media_type, content)) = response.content.iter().next() {
+            res.headers
+                .insert("Content-Type".to_owned(), media_type.clone());
+
+            let example = content
+                .examples(self)
+                .into_values()
+                .find_map(|example| example.value);
+
+            res.body = match (example, &content.schema) {
+                (Some(example), _) => Some(example),
+                (None, Some(schema)) => Some(schema.resolve(self)?.example_value(self)),
+                (None, None) => None,
+            };
+        }
+
+        Ok(res)
+    }
+}
+
+/// Formats a parameter value without quoting strings.
+pub(crate) fn param_value_string(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(string) => string.clone(),
+        JsonValue::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const SPEC: &str = r#"openapi: "3.1.0"
+info:
+  title: Pets
+  version: "1"
+paths:
+  /pets/{petId}:
+    parameters:
+    - { name: petId, in: path, required: true, schema: { type: string, format: uuid } }
+    get:
+      operationId: getPet
+      parameters:
+      - { name: verbose, in: query, required: true, schema: { type: boolean } }
+      - { name: page, in: query, schema: { type: integer } }
+      responses:
+        "200":
+          description: The pet.
+          headers:
+            X-Rate-Limit: { schema: { type: integer, minimum: 10 } }
+          content:
+            application/json:
+              schema: { $ref: '#/components/schemas/pet' }
+        4XX:
+          description: Client error.
+          content:
+            application/json:
+              schema: { type: object }
+              example: { error: not found }
+    put:
+      operationId: updatePet
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: '#/components/schemas/pet' }
+      responses: {}
+components:
+  schemas:
+    pet:
+      type: object
+      properties:
+        name: { type: string, minLength: 8 }
+        age: { type: integer, minimum: 1 }
+"#;
+
+    #[test]
+    fn mock_request_fills_parameters() {
+        let spec = crate::from_reader(SPEC.as_bytes()).unwrap();
+
+        let req = spec.generate_mock_request("getPet").unwrap();
+        assert_eq!(req.method, Method::GET);
+        assert_eq!(req.path, "/pets/00000000-0000-0000-0000-000000000000");
+        assert_eq!(req.query_params.get("verbose").unwrap(), "true");
+        assert!(!req.query_params.contains_key("page"));
+        assert!(req.body.is_none());
+
+        assert_eq!(
+            spec.generate_mock_request_with_body("getPet").unwrap_err(),
+            MockGenError::NoRequestBody("getPet".to_owned())
+        );
+
+        assert_eq!(
+            spec.generate_mock_request("nope").unwrap_err(),
+            MockGenError::OperationNotFound("nope".to_owned())
+        );
+    }
+
+    #[test]
+    fn mock_request_generates_body() {
+        let spec = crate::from_reader(SPEC.as_bytes()).unwrap();
+
+        let req = spec.generate_mock_request_with_body("updatePet").unwrap();
+        assert_eq!(req.method, Method::PUT);
+        assert_eq!(req.headers.get("Content-Type").unwrap(), "application/json");
+        assert_eq!(req.body, Some(json!({ "name": "stringaa", "age": 1 })));
+    }
+
+    #[test]
+    fn mock_response_uses_examples_and_schemas() {
+        let spec = crate::from_reader(SPEC.as_bytes()).unwrap();
+
+        let res = spec.generate_mock_response("getPet", 200).unwrap();
+        assert_eq!(res.status, 200);
+        assert_eq!(res.headers.get("X-Rate-Limit").unwrap(), "10");
+        assert_eq!(res.headers.get("Content-Type").unwrap(), "application/json");
+        assert_eq!(res.body, Some(json!({ "name": "stringaa", "age": 1 })));
+
+        let res = spec.generate_mock_response("getPet", 404).unwrap();
+        assert_eq!(res.body, Some(json!({ "error": "not found" })));
+
+        assert_eq!(
+            spec.generate_mock_response("getPet", 500).unwrap_err(),
+            MockGenError::ResponseNotFound {
+                operation_id: "getPet".to_owned(),
+                status: 500,
+            }
+        );
+    }
+
+    #[test]
+    fn mock_request_satisfies_bounds() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Bounds, version: "1" }
+paths:
+  /measurements:
+    post:
+      operationId: addMeasurement
+      parameters:
+      - { name: ratio, in: query, required: true, schema: { type: number, exclusiveMinimum: 0, maximum: 0.5 } }
+      - { name: count, in: query, required: true, schema: { type: integer, minimum: 0, exclusiveMinimum: 0 } }
+      - { name: step, in: query, required: true, schema: { type: integer, minimum: 1, multipleOf: 5 } }
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [ratio, count, step, offset]
+              properties:
+                ratio: { type: number, minimum: 0.25, exclusiveMaximum: 0.75 }
+                count: { type: integer, exclusiveMinimum: 0.5, exclusiveMaximum: 3 }
+                step: { type: integer, maximum: -1, multipleOf: 4 }
+                offset: { type: number, exclusiveMaximum: -2.5, multipleOf: 0.5 }
+      responses: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let req = spec.generate_mock_request("addMeasurement").unwrap();
+        let operation = spec.operation_by_id("addMeasurement").unwrap().operation;
+
+        for param in &operation.parameters {
+            let param = param.resolve(&spec).unwrap();
+            let schema = param.resolved_schema(&spec).unwrap().unwrap();
+            let value = &req.query_params[&param.name];
+            let value = serde_json::from_str(value).unwrap();
+
+            assert!(
+                schema.strict_validates(&value, &spec),
+                "{} = {}",
+                param.name,
+                value
+            );
+        }
+
+        let body_schema = operation
+            .request_body
+            .as_ref()
+            .unwrap()
+            .resolve(&spec)
+            .unwrap()
+            .content["application/json"]
+            .schema(&spec)
+            .unwrap();
+        let body = req.body.unwrap();
+        assert!(body_schema.strict_validates(&body, &spec), "{}", body);
+    }
+}
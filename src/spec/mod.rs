@@ -2,62 +2,105 @@
 //!
 //! High-level structures include [`Spec`], [`Components`] & [`Schema`].
 
-use std::{collections::BTreeMap, iter::Iterator};
+use std::{borrow::Cow, collections::BTreeMap, iter::Iterator, str::FromStr};
 
 use derive_more::Error;
 use http::Method;
-use log::debug;
+use log::{debug, error};
 use serde::{Deserialize, Serialize};
 
+mod casing;
+mod client;
+mod codegen;
+mod compat;
 mod components;
 mod contact;
+mod dialect;
+mod diff;
+mod discriminator;
 mod encoding;
 
 mod error;
 mod example;
 mod external_doc;
+mod fingerprint;
 mod flows;
+mod form_hint;
 mod header;
+mod infer;
 mod info;
 mod license;
 mod link;
+mod lint;
+mod markdown;
 mod media_type;
 mod media_type_examples;
+mod minimal_example;
+mod mock;
+mod numeric_bound;
 mod operation;
 mod parameter;
 mod path_item;
+pub(crate) mod pattern;
+mod postman;
+mod prune;
+mod redundant;
 mod r#ref;
 mod request_body;
+mod resolver;
 mod response;
 mod schema;
+mod security_requirement;
 mod security_scheme;
 mod server;
+mod spec_extensions;
+mod stats;
 mod tag;
 
+pub use casing::*;
+pub use client::*;
+pub use codegen::*;
+pub use compat::*;
 pub use components::*;
 pub use contact::*;
+pub use dialect::*;
+pub use diff::*;
+pub use discriminator::*;
 pub use encoding::*;
 pub use example::*;
 pub use external_doc::*;
 pub use flows::*;
+pub use form_hint::*;
 pub use header::*;
 pub use info::*;
 pub use license::*;
 pub use link::*;
+pub use lint::*;
 pub use media_type::*;
 pub use media_type_examples::*;
+pub use minimal_example::*;
+pub use mock::*;
+pub use numeric_bound::*;
 pub use operation::*;
 pub use parameter::*;
 pub use path_item::*;
+pub use pattern::*;
 pub use r#ref::*;
+pub use redundant::*;
 pub use request_body::*;
+pub use resolver::*;
 pub use response::*;
+pub use security_requirement::*;
 pub use security_scheme::*;
 pub use server::*;
+pub use stats::*;
 pub use tag::*;
 
-pub use error::Error;
-pub use schema::{Error as SchemaError, Schema, Type as SchemaType};
+pub use error::{Error, ParseError};
+pub use schema::{
+    CompatibilityError, Encoding as SchemaEncoding, Error as SchemaError, KnownFormat, Schema,
+    SchemaKey, SchemaOrBool, SchemaUsage, Type as SchemaType,
+};
 
 const OPENAPI_SUPPORTED_VERSION_RANGE: &str = "~3";
 
@@ -72,6 +115,9 @@ pub struct Spec {
     /// the API
     /// [`info.version`](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#infoVersion)
     /// string.
+    ///
+    /// Deserialization fails unless this is a `2.x.y` or `3.x.y` version.
+    #[serde(deserialize_with = "deserialize_openapi_version")]
     pub openapi: String,
 
     /// Provides metadata about the API. The metadata MAY be used by tooling as required.
@@ -94,19 +140,24 @@ pub struct Spec {
     /// [`Server Object`](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#serverObject)
     /// in order to construct the full URL. The Paths MAY be empty, due to
     /// [ACL constraints](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#securityFiltering).
+    ///
+    /// Optional since OpenAPI 3.1, where a spec may only describe webhooks; a missing `paths`
+    /// field is treated as empty.
+    #[serde(default)]
     pub paths: BTreeMap<String, PathItem>,
 
     /// An element to hold various schemas for the specification.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Components>,
 
-    // FIXME: Implement
-    // /// A declaration of which security mechanisms can be used across the API.
-    // /// The list of  values includes alternative security requirement objects that can be used.
-    // /// Only one of the security requirement objects need to be satisfied to authorize a request.
-    // /// Individual operations can override this definition.
-    // #[serde(skip_serializing_if = "Option::is_none")]
-    // pub security: Option<SecurityRequirement>,
+    /// A declaration of which security mechanisms can be used across the API.
+    /// The list of  values includes alternative security requirement objects that can be used.
+    /// Only one of the security requirement objects need to be satisfied to authorize a request.
+    /// Individual operations can override this definition.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<SecurityRequirement>,
+
     /// A list of tags used by the specification with additional metadata.
     ///The order of the tags can be used to reflect on their order by the parsing tools.
     /// Not all tags that are used by the
@@ -137,6 +188,19 @@ pub struct Spec {
 // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#specificationExtensions}
 
 impl Spec {
+    /// Parses a spec from a JSON or YAML string.
+    ///
+    /// Input starting with `{`, ignoring leading whitespace, is parsed as JSON; anything else
+    /// is parsed as YAML.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Spec, ParseError> {
+        if s.trim_start().starts_with('{') {
+            Ok(serde_json::from_str(s)?)
+        } else {
+            Ok(serde_yaml::from_str(s)?)
+        }
+    }
+
     pub fn validate_version(&self) -> Result<semver::Version, Error> {
         let spec_version = &self.openapi;
         let sem_ver = semver::Version::parse(spec_version)?;
@@ -190,7 +254,559 @@ impl Spec {
         ops.into_iter()
     }
 
+    /// Iterates over all path items with their path templates.
+    ///
+    /// Path items with a `$ref` to a component path item are replaced by the referenced item.
+    /// Unresolvable references are left as-is.
+    pub fn path_items(&self) -> impl Iterator<Item = (&str, Cow<'_, PathItem>)> {
+        self.paths.iter().map(move |(path, path_item)| {
+            let resolved = path_item
+                .reference
+                .as_deref()
+                .and_then(|ref_path| PathItem::from_ref(self, ref_path).ok());
+
+            let path_item = match resolved {
+                Some(resolved) => Cow::Owned(resolved),
+                None => Cow::Borrowed(path_item),
+            };
+
+            (path.as_str(), path_item)
+        })
+    }
+
+    /// Iterates over the path templates (e.g., `/pets/{petId}`) of all paths.
+    pub fn path_templates(&self) -> impl Iterator<Item = &str> {
+        self.paths.keys().map(String::as_str)
+    }
+
+    /// Iterates over the path templates starting with `prefix`, e.g. `/v2/users`.
+    ///
+    /// The prefix is matched as a plain string, so `/v2/user` also matches `/v2/users`.
+    pub fn paths_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a str> {
+        self.path_templates()
+            .filter(move |template| template.starts_with(prefix))
+    }
+
+    /// Returns the prefixes made of the first `depth` segments of path templates that are shared
+    /// by more than one path, in order.
+    ///
+    /// For example, `/v2/users` and `/v2/users/{id}` share the prefix `/v2` at depth 1 and
+    /// `/v2/users` at depth 2. Paths with fewer than `depth` segments are ignored.
+    pub fn common_path_prefixes(&self, depth: usize) -> Vec<String> {
+        let mut counts = BTreeMap::<String, usize>::new();
+
+        for template in self.path_templates() {
+            let segments = template
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .take(depth)
+                .collect::<Vec<_>>();
+
+            if depth == 0 || segments.len() < depth {
+                continue;
+            }
+
+            *counts
+                .entry(format!("/{}", segments.join("/")))
+                .or_default() += 1;
+        }
+
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(prefix, _)| prefix)
+            .collect()
+    }
+
     pub fn primary_server(&self) -> Option<&Server> {
         self.servers.first()
     }
+
+    /// Returns every distinct server declared in the spec, at the root, path item or operation
+    /// level.
+    ///
+    /// Servers are deduplicated by URL, keeping the first declaration found.
+    pub fn collect_all_servers(&self) -> Vec<&Server> {
+        let path_servers = self.paths.values().flat_map(|path_item| {
+            path_item.servers.iter().chain(
+                path_item
+                    .methods()
+                    .into_iter()
+                    .flat_map(|(_, op)| &op.servers),
+            )
+        });
+
+        let mut servers = Vec::<&Server>::new();
+
+        for server in self.servers.iter().chain(path_servers) {
+            if !servers.iter().any(|s| s.url == server.url) {
+                servers.push(server);
+            }
+        }
+
+        servers
+    }
+
+    /// Returns the servers that apply to the operation with the given ID.
+    ///
+    /// Operation-level servers override path item servers, which override the root servers.
+    /// Returns an empty list if the operation is not found.
+    pub fn effective_servers_for_operation(&self, operation_id: &str) -> Vec<&Server> {
+        let op = match self.operation_by_id(operation_id) {
+            Some(op) => op,
+            None => return vec![],
+        };
+
+        let servers = [&op.operation.servers, &op.path_item.servers, &self.servers]
+            .into_iter()
+            .find(|servers| !servers.is_empty())
+            .unwrap_or(&self.servers);
+
+        servers.iter().collect()
+    }
+
+    /// Returns the servers that apply to the operation with the given ID, including the implied
+    /// default server.
+    ///
+    /// Like [`Spec::effective_servers_for_operation`], operation-level servers override path item
+    /// servers, which override the root servers. An empty `servers` array is treated the same as
+    /// an absent one, so it inherits the servers of the level above. If no level declares any
+    /// servers, a single server with a URL of `/` is returned, as the spec defines for an absent
+    /// or empty root `servers` array. Returns an empty list if the operation is not found.
+    pub fn resolve_operation_servers(&self, operation_id: &str) -> Vec<Server> {
+        if self.operation_by_id(operation_id).is_none() {
+            return vec![];
+        }
+
+        let servers = self.effective_servers_for_operation(operation_id);
+
+        if servers.is_empty() {
+            return vec![Server {
+                url: "/".to_owned(),
+                ..Server::default()
+            }];
+        }
+
+        servers.into_iter().cloned().collect()
+    }
+
+    /// Returns all operations tagged with `tag`.
+    pub fn find_operations_by_tag(&self, tag: &str) -> Vec<OperationRef<'_>> {
+        self.operation_refs()
+            .filter(|op| op.operation.tags.iter().any(|t| t == tag))
+            .collect()
+    }
+
+    /// Groups all operations by tag.
+    ///
+    /// Operations with multiple tags appear in each of their groups. Untagged operations are
+    /// grouped under an empty string.
+    pub fn operations_by_tag(&self) -> BTreeMap<String, Vec<OperationRef<'_>>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+
+        for op in self.operation_refs() {
+            if op.operation.tags.is_empty() {
+                groups.entry(String::new()).or_default().push(op);
+                continue;
+            }
+
+            for tag in &op.operation.tags {
+                groups.entry(tag.clone()).or_default().push(op.clone());
+            }
+        }
+
+        groups
+    }
+
+    /// Iterates over all operations marked as deprecated.
+    pub fn deprecated_operations(&self) -> impl Iterator<Item = OperationRef<'_>> {
+        self.operation_refs()
+            .filter(|op| op.operation.deprecated == Some(true))
+    }
+
+    /// Iterates over all component schemas marked as deprecated.
+    pub fn deprecated_schemas(&self) -> impl Iterator<Item = (&str, &Schema)> {
+        self.components
+            .iter()
+            .flat_map(|components| &components.schemas)
+            .filter_map(|(name, oor)| match oor {
+                ObjectOrReference::Object(schema) if schema.deprecated == Some(true) => {
+                    Some((name.as_str(), schema))
+                }
+                _ => None,
+            })
+    }
+
+    /// Iterates over all deprecated parameters along with the operations they apply to.
+    ///
+    /// Parameters declared on a path item are reported for each of its operations.
+    pub fn deprecated_parameters(&self) -> impl Iterator<Item = (OperationRef<'_>, Parameter)> {
+        self.operation_refs().flat_map(move |op| {
+            op.path_item
+                .parameters
+                .iter()
+                .chain(&op.operation.parameters)
+                .filter_map(|oor| oor.resolve(self).map_err(|err| error!("{}", err)).ok())
+                .filter(|param| param.deprecated == Some(true))
+                .map(|param| (op.clone(), param))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Iterates over all operations along with their paths and methods.
+    fn operation_refs(&self) -> impl Iterator<Item = OperationRef<'_>> {
+        self.paths.iter().flat_map(|(path, path_item)| {
+            path_item
+                .methods()
+                .into_iter()
+                .map(move |(method, operation)| OperationRef {
+                    path,
+                    method,
+                    path_item,
+                    operation,
+                })
+        })
+    }
+}
+
+impl FromStr for Spec {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Spec::from_str(s)
+    }
+}
+
+/// Deserializes the `openapi` version, rejecting those that are not `2.x.y` or `3.x.y`.
+fn deserialize_openapi_version<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::{Error as _, Unexpected};
+
+    let version = String::deserialize(deserializer)?;
+
+    match semver::Version::parse(&version) {
+        Ok(sem_ver) if sem_ver.major == 2 || sem_ver.major == 3 => Ok(version),
+        _ => Err(D::Error::invalid_value(
+            Unexpected::Str(&version),
+            &"an OpenAPI version of the form 3.x.y or 2.x.y",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operations_grouped_by_tag() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets:
+    get: { operationId: listPets, tags: [pets], responses: {} }
+    post: { operationId: createPet, tags: [pets, admin], responses: {} }
+  /health:
+    get: { operationId: health, responses: {} }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        fn ids<'a>(ops: &[OperationRef<'a>]) -> Vec<&'a str> {
+            ops.iter()
+                .map(|op| op.operation.operation_id.as_deref().unwrap())
+                .collect()
+        }
+
+        assert_eq!(
+            ids(&spec.find_operations_by_tag("pets")),
+            ["listPets", "createPet"]
+        );
+        assert!(spec.find_operations_by_tag("unknown").is_empty());
+
+        let groups = spec.operations_by_tag();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(ids(&groups["admin"]), ["createPet"]);
+        assert_eq!(ids(&groups["pets"]), ["listPets", "createPet"]);
+        assert_eq!(ids(&groups[""]), ["health"]);
+    }
+
+    #[test]
+    fn deprecated_items() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets:
+    parameters:
+    - { $ref: '#/components/parameters/legacy' }
+    get: { operationId: listPets, responses: {} }
+    post: { operationId: createPet, deprecated: true, responses: {} }
+components:
+  parameters:
+    legacy: { name: legacy, in: query, deprecated: true }
+  schemas:
+    Pet: { type: object }
+    OldPet: { type: object, deprecated: true }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let ops = spec.deprecated_operations().collect::<Vec<_>>();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].operation.operation_id.as_deref(), Some("createPet"));
+
+        let schemas = spec.deprecated_schemas().collect::<Vec<_>>();
+        assert_eq!(schemas.len(), 1);
+        assert_eq!(schemas[0].0, "OldPet");
+
+        let params = spec.deprecated_parameters().collect::<Vec<_>>();
+        assert_eq!(params.len(), 2);
+        assert!(params.iter().all(|(_, param)| param.name == "legacy"));
+    }
+
+    #[test]
+    fn servers_by_level() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Servers, version: "1" }
+servers:
+- url: https://api.example.com
+paths:
+  /files:
+    servers:
+    - url: https://files.example.com
+    get:
+      operationId: listFiles
+      responses: {}
+    post:
+      operationId: uploadFile
+      servers:
+      - url: https://upload.example.com
+      - url: https://api.example.com
+      responses: {}
+  /health:
+    get:
+      operationId: health
+      responses: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        fn urls<'a>(servers: &[&'a Server]) -> Vec<&'a str> {
+            servers.iter().map(|s| s.url.as_str()).collect()
+        }
+
+        assert_eq!(
+            urls(&spec.collect_all_servers()),
+            [
+                "https://api.example.com",
+                "https://files.example.com",
+                "https://upload.example.com",
+            ]
+        );
+
+        assert_eq!(
+            urls(&spec.effective_servers_for_operation("uploadFile")),
+            ["https://upload.example.com", "https://api.example.com"]
+        );
+        assert_eq!(
+            urls(&spec.effective_servers_for_operation("listFiles")),
+            ["https://files.example.com"]
+        );
+        assert_eq!(
+            urls(&spec.effective_servers_for_operation("health")),
+            ["https://api.example.com"]
+        );
+        assert!(spec.effective_servers_for_operation("nope").is_empty());
+    }
+
+    #[test]
+    fn webhooks_without_paths() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Events, version: "1" }
+webhooks:
+  newPet:
+    post:
+      operationId: newPet
+      responses:
+        "200": { description: Received. }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert!(spec.paths.is_empty());
+        assert_eq!(spec.path_templates().count(), 0);
+        assert!(spec.webhooks.contains_key("newPet"));
+        assert!(spec.validate_parameter_uniqueness().is_empty());
+        assert!(spec.check_response_completeness().is_empty());
+    }
+
+    #[test]
+    fn paths_by_prefix() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Users, version: "2" }
+paths:
+  /health: {}
+  /v2/users: {}
+  /v2/users/{id}: {}
+  /v2/users/{id}/pets: {}
+  /v2/orders: {}
+  /v2/orders/{id}: {}
+  /v3/users: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.paths_with_prefix("/v2/users").collect::<Vec<_>>(),
+            ["/v2/users", "/v2/users/{id}", "/v2/users/{id}/pets"]
+        );
+        assert_eq!(spec.paths_with_prefix("/v4").count(), 0);
+
+        assert_eq!(spec.common_path_prefixes(1), ["/v2"]);
+        assert_eq!(spec.common_path_prefixes(2), ["/v2/orders", "/v2/users"]);
+        assert_eq!(spec.common_path_prefixes(3), ["/v2/users/{id}"]);
+        assert!(spec.common_path_prefixes(4).is_empty());
+    }
+
+    #[test]
+    fn resolve_operation_servers_through_overrides() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Servers, version: "1" }
+servers:
+- url: https://api.example.com
+paths:
+  /files:
+    servers:
+    - url: https://files.example.com
+    get:
+      operationId: listFiles
+      servers: []
+      responses: {}
+    post:
+      operationId: uploadFile
+      servers:
+      - url: https://upload.example.com
+      responses: {}
+  /health:
+    get:
+      operationId: health
+      responses: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        fn urls(servers: Vec<Server>) -> Vec<String> {
+            servers.into_iter().map(|s| s.url).collect()
+        }
+
+        // operation servers override path item servers
+        assert_eq!(
+            urls(spec.resolve_operation_servers("uploadFile")),
+            ["https://upload.example.com"]
+        );
+
+        // an empty operation list inherits path item servers
+        assert_eq!(
+            urls(spec.resolve_operation_servers("listFiles")),
+            ["https://files.example.com"]
+        );
+
+        // root servers apply when no other level declares any
+        assert_eq!(
+            urls(spec.resolve_operation_servers("health")),
+            ["https://api.example.com"]
+        );
+
+        assert!(spec.resolve_operation_servers("nope").is_empty());
+
+        let mut spec = spec;
+        spec.servers.clear();
+        assert_eq!(urls(spec.resolve_operation_servers("health")), ["/"]);
+    }
+
+    #[test]
+    fn path_items_resolve_references() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Paths, version: "1" }
+paths:
+  /pets:
+    $ref: '#/components/pathItems/Pets'
+  /toys:
+    summary: Toys
+  /unknown:
+    $ref: 'other.yml#/paths/~1unknown'
+components:
+  pathItems:
+    Pets:
+      summary: Pets
+      get:
+        responses: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.path_templates().collect::<Vec<_>>(),
+            ["/pets", "/toys", "/unknown"]
+        );
+
+        let items = spec.path_items().collect::<Vec<_>>();
+        assert_eq!(items[0].1.summary.as_deref(), Some("Pets"));
+        assert!(items[0].1.get.is_some());
+        assert_eq!(items[1].1.summary.as_deref(), Some("Toys"));
+        assert!(items[2].1.reference.is_some());
+    }
+
+    #[test]
+    fn parse_json_or_yaml() {
+        let yaml = "openapi: 3.1.0\ninfo: { title: Parse, version: '1' }\npaths: {}\n";
+        let json = r#"
+            { "openapi": "3.1.0", "info": { "title": "Parse", "version": "1" }, "paths": {} }
+        "#;
+
+        let from_yaml = Spec::from_str(yaml).unwrap();
+        let from_json = json.parse::<Spec>().unwrap();
+        assert_eq!(from_yaml, from_json);
+        assert_eq!(from_json.info.title, "Parse");
+
+        assert!(matches!(
+            Spec::from_str(r#"{ "openapi": "#).unwrap_err(),
+            ParseError::Json(_)
+        ));
+        assert!(matches!(
+            Spec::from_str("openapi: [").unwrap_err(),
+            ParseError::Yaml(_)
+        ));
+    }
+
+    #[test]
+    fn openapi_version_checked_on_parse() {
+        for version in ["3.1.0", "3.0.3", "2.0.0"] {
+            let yaml = format!("openapi: {version}\ninfo: {{ title: V, version: '1' }}\n");
+            assert_eq!(Spec::from_str(&yaml).unwrap().openapi, version);
+        }
+
+        for version in ["3", "3.1", "4.0.0", "v3.1.0", "latest"] {
+            let yaml = format!("openapi: '{version}'\ninfo: {{ title: V, version: '1' }}\n");
+            match Spec::from_str(&yaml).unwrap_err() {
+                ParseError::Yaml(err) => assert!(err.to_string().contains("3.x.y or 2.x.y")),
+                err => panic!("unexpected error: {:?}", err),
+            }
+        }
+    }
 }
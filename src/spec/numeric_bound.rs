@@ -0,0 +1,131 @@
+use std::{cmp::Ordering, fmt};
+
+use serde::{Deserialize, Serialize};
+
+/// A numeric schema bound (e.g., `minimum`) that keeps whether it was written as an integer.
+///
+/// Bounds compare by value, regardless of representation, so `Integer(1) == Float(1.0)`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NumericBound {
+    Integer(i64),
+    Float(f64),
+}
+
+impl NumericBound {
+    /// Converts a JSON number, preferring the integer representation.
+    pub fn from_number(num: &serde_json::Number) -> Option<Self> {
+        match num.as_i64() {
+            Some(num) => Some(Self::Integer(num)),
+            None => num.as_f64().map(Self::Float),
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Self::Integer(num) => num as f64,
+            Self::Float(num) => num,
+        }
+    }
+
+    /// Returns the bound as an integer, if it is one.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Self::Integer(num) => Some(num),
+            Self::Float(_) => None,
+        }
+    }
+}
+
+impl PartialEq for NumericBound {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for NumericBound {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => a.partial_cmp(b),
+            _ => self.as_f64().partial_cmp(&other.as_f64()),
+        }
+    }
+}
+
+/// An `exclusiveMinimum` or `exclusiveMaximum`: a bound of its own since OpenAPI 3.1, or in
+/// OpenAPI 3.0 a flag making `minimum` or `maximum` exclusive.
+///
+/// [`Schema::minimum_bounds`](crate::spec::Schema::minimum_bounds) and
+/// [`Schema::maximum_bounds`](crate::spec::Schema::maximum_bounds) give the bounds in either form.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExclusiveBound {
+    Bound(NumericBound),
+    Flag(bool),
+}
+
+impl From<NumericBound> for ExclusiveBound {
+    fn from(bound: NumericBound) -> Self {
+        Self::Bound(bound)
+    }
+}
+
+impl From<i64> for NumericBound {
+    fn from(num: i64) -> Self {
+        Self::Integer(num)
+    }
+}
+
+impl From<f64> for NumericBound {
+    fn from(num: f64) -> Self {
+        Self::Float(num)
+    }
+}
+
+impl fmt::Display for NumericBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(num) => num.fmt(f),
+            Self::Float(num) => num.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn bounds_keep_representation() {
+        let int = serde_json::from_value::<NumericBound>(json!(0)).unwrap();
+        assert_eq!(int.as_i64(), Some(0));
+        assert_eq!(serde_json::to_string(&int).unwrap(), "0");
+
+        let float = serde_json::from_value::<NumericBound>(json!(0.5)).unwrap();
+        assert_eq!(float.as_i64(), None);
+        assert_eq!(serde_json::to_string(&float).unwrap(), "0.5");
+
+        let float = serde_json::from_str::<NumericBound>("1.0").unwrap();
+        assert_eq!(serde_json::to_string(&float).unwrap(), "1.0");
+    }
+
+    #[test]
+    fn exclusive_bounds_accept_flags() {
+        let bound = serde_json::from_value::<ExclusiveBound>(json!(0)).unwrap();
+        assert_eq!(bound, ExclusiveBound::Bound(NumericBound::Integer(0)));
+
+        let flag = serde_json::from_value::<ExclusiveBound>(json!(true)).unwrap();
+        assert_eq!(flag, ExclusiveBound::Flag(true));
+        assert_eq!(serde_json::to_string(&flag).unwrap(), "true");
+    }
+
+    #[test]
+    fn bounds_compare_by_value() {
+        assert_eq!(NumericBound::Integer(1), NumericBound::Float(1.0));
+        assert!(NumericBound::Integer(1) < NumericBound::Float(1.5));
+        assert!(NumericBound::Float(-0.5) < NumericBound::Integer(0));
+        assert!(NumericBound::Integer(i64::MAX - 1) < NumericBound::Integer(i64::MAX));
+    }
+}
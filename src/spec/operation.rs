@@ -1,11 +1,16 @@
 use std::collections::BTreeMap;
 
+use derive_more::Display;
+use http::Method;
 use log::error;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    Callback, Error, ExternalDoc, ObjectOrReference, Parameter, RequestBody, Response, Server, Spec,
+    Callback, Error, ExternalDoc, ObjectOrReference, ParamLoc, Parameter, PathItem, RefError,
+    RequestBody, Response, SecurityRequirement, Server, Spec,
 };
+use crate::Schema;
 
 /// Describes a single API operation on a path.
 ///
@@ -92,20 +97,29 @@ pub struct Operation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<bool>,
 
-    // FIXME: Implement
-    // /// A declaration of which security mechanisms can be used for this operation. The list of
-    // /// values includes alternative security requirement objects that can be used. Only one
-    // /// of the security requirement objects need to be satisfied to authorize a request.
-    // /// This definition overrides any declared top-level
-    // /// [`security`](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#oasSecurity).
-    // /// To remove a top-level security declaration, an empty array can be used.
-    // pub security: Option<SecurityRequirement>,
+    /// A declaration of which security mechanisms can be used for this operation. The list of
+    /// values includes alternative security requirement objects that can be used. Only one
+    /// of the security requirement objects need to be satisfied to authorize a request.
+    /// This definition overrides any declared top-level
+    /// [`security`](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#oasSecurity).
+    /// To remove a top-level security declaration, an empty array can be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
     /// An alternative `server` array to service this operation. If an alternative `server`
     /// object is specified at the Path Item Object or Root level, it will be overridden by
     /// this value.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<Server>,
+
+    /// [Specification Extensions](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#specificationExtensions),
+    /// e.g. `x-internal`. Keys include the `x-` prefix.
+    #[serde(
+        flatten,
+        deserialize_with = "crate::spec::spec_extensions::deserialize"
+    )]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 impl Operation {
@@ -141,6 +155,55 @@ impl Operation {
         Ok(params)
     }
 
+    /// Returns the security requirements that apply to this operation.
+    ///
+    /// Operation-level `security` overrides the spec-level declaration; an empty list means the
+    /// operation requires no security. When absent, the spec-level `security` applies.
+    pub fn effective_security(&self, spec: &Spec) -> Vec<SecurityRequirement> {
+        match &self.security {
+            Some(security) => security.clone(),
+            None => spec.security.clone(),
+        }
+    }
+
+    /// Returns the resolved schemas of the operation's parameters, keyed by name and location.
+    ///
+    /// Parameters described by `content` use the schema of its media type. Parameters without a
+    /// schema are omitted.
+    pub fn all_parameter_schemas(
+        &self,
+        spec: &Spec,
+    ) -> Result<BTreeMap<(String, ParamLoc), Schema>, RefError> {
+        let mut schemas = BTreeMap::new();
+
+        for oor in &self.parameters {
+            let param = oor.resolve(spec)?;
+
+            if let Some(schema) = param.resolved_schema(spec)? {
+                schemas.insert((param.name, param.location), schema);
+            }
+        }
+
+        Ok(schemas)
+    }
+
+    /// Looks up the response for a status code by exact code, then by status code range (e.g.,
+    /// `2XX`), then falls back to the `default` response.
+    pub(crate) fn response_for_status(&self, status: u16) -> Option<&ObjectOrReference<Response>> {
+        let code = status.to_string();
+        let range = format!("{}XX", status / 100);
+
+        self.responses
+            .get(&code)
+            .or_else(|| {
+                self.responses
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(&range))
+                    .map(|(_, response)| response)
+            })
+            .or_else(|| self.responses.get("default"))
+    }
+
     pub fn parameter(&self, search: &str, spec: &Spec) -> Result<Option<Parameter>, Error> {
         let param = self
             .parameters(spec)?
@@ -151,3 +214,298 @@ impl Operation {
         Ok(param)
     }
 }
+
+/// An operation along with the path and method it is declared under.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationRef<'a> {
+    /// Path template of the operation, e.g. `/pets/{petId}`.
+    pub path: &'a str,
+
+    pub method: Method,
+
+    /// The path item declaring the operation.
+    pub path_item: &'a PathItem,
+
+    pub operation: &'a Operation,
+}
+
+/// Index of a spec's operations by operation ID, for repeated lookups.
+///
+/// The index borrows the spec, so it cannot go stale. If several operations share an ID, the
+/// first one in path order is indexed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationIndex<'a> {
+    operations: BTreeMap<&'a str, OperationRef<'a>>,
+}
+
+impl<'a> OperationIndex<'a> {
+    /// Indexes the operations of the spec.
+    pub fn build(spec: &'a Spec) -> Self {
+        let mut operations = BTreeMap::new();
+
+        for op in spec.operation_refs() {
+            if let Some(id) = &op.operation.operation_id {
+                operations.entry(id.as_str()).or_insert(op);
+            }
+        }
+
+        Self { operations }
+    }
+
+    /// Returns the operation with the given ID.
+    pub fn get(&self, operation_id: &str) -> Option<&OperationRef<'a>> {
+        self.operations.get(operation_id)
+    }
+}
+
+/// An operation ID that does not follow a naming convention.
+#[derive(Debug, Clone, PartialEq, Display, derive_more::Error)]
+#[display(
+    fmt = "Operation ID {} of {} {} does not match the expected format",
+    operation_id,
+    method,
+    path
+)]
+pub struct OperationIdFormatError {
+    pub operation_id: String,
+    pub path: String,
+    pub method: Method,
+}
+
+/// Common naming conventions for operation IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperationIdPattern(&'static str);
+
+impl OperationIdPattern {
+    /// E.g. `listPets`.
+    pub const CAMEL_CASE: Self = Self("^[a-z][a-zA-Z0-9]*$");
+
+    /// E.g. `list_pets`.
+    pub const SNAKE_CASE: Self = Self("^[a-z][a-z0-9]*(_[a-z0-9]+)*$");
+
+    /// E.g. `ListPets`.
+    pub const PASCAL_CASE: Self = Self("^[A-Z][a-zA-Z0-9]*$");
+
+    /// A camel case verb followed by a noun, e.g. `getPetById`.
+    pub const VERB_NOUN_CAMEL: Self = Self(
+        "^(get|list|create|update|delete|patch|put|post|search|find|add|remove|set|replace|check|upload|download)[A-Z][a-zA-Z0-9]*$",
+    );
+
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    pub fn to_regex(&self) -> Regex {
+        Regex::new(self.0).unwrap()
+    }
+}
+
+impl Spec {
+    /// Returns the operation with the given ID.
+    ///
+    /// If several operations share an ID, the first one in path order is returned. Use an
+    /// [`OperationIndex`] for repeated lookups.
+    pub fn operation_by_id(&self, operation_id: &str) -> Option<OperationRef<'_>> {
+        self.operation_refs()
+            .find(|op| op.operation.operation_id.as_deref() == Some(operation_id))
+    }
+
+    /// Returns the operations whose IDs contain the query, ignoring case.
+    pub fn operation_by_id_fuzzy(&self, query: &str) -> Vec<OperationRef<'_>> {
+        let query = query.to_lowercase();
+
+        self.operation_refs()
+            .filter(|op| {
+                op.operation
+                    .operation_id
+                    .as_ref()
+                    .map_or(false, |id| id.to_lowercase().contains(&query))
+            })
+            .collect()
+    }
+
+    /// Returns the operations whose IDs do not match the given pattern, such as one of the
+    /// [`OperationIdPattern`]s. Operations without an ID are ignored.
+    pub fn verify_operation_id_format(&self, pattern: &Regex) -> Vec<OperationIdFormatError> {
+        self.operation_refs()
+            .filter_map(|op| {
+                let operation_id = op.operation.operation_id.as_deref()?;
+
+                (!pattern.is_match(operation_id)).then(|| OperationIdFormatError {
+                    operation_id: operation_id.to_owned(),
+                    path: op.path.to_owned(),
+                    method: op.method,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn effective_security_falls_back_to_spec() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+security: [{ apiKey: [] }]
+paths:
+  /pets:
+    get: { responses: {} }
+    post: { security: [{ oauth: [write] }], responses: {} }
+    options: { security: [], responses: {} }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let item = &spec.paths["/pets"];
+
+        assert_eq!(
+            item.get.as_ref().unwrap().effective_security(&spec),
+            [btreemap! { "apiKey".to_owned() => vec![] }]
+        );
+        assert_eq!(
+            item.post.as_ref().unwrap().effective_security(&spec),
+            [btreemap! { "oauth".to_owned() => vec!["write".to_owned()] }]
+        );
+        assert!(item
+            .options
+            .as_ref()
+            .unwrap()
+            .effective_security(&spec)
+            .is_empty());
+    }
+
+    #[test]
+    fn operation_id_format() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets:
+    get: { operationId: listPets, responses: {} }
+    post: { operationId: create_pet, responses: {} }
+    put: { responses: {} }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let errors = spec.verify_operation_id_format(&OperationIdPattern::CAMEL_CASE.to_regex());
+        assert_eq!(
+            errors,
+            [OperationIdFormatError {
+                operation_id: "create_pet".to_owned(),
+                path: "/pets".to_owned(),
+                method: Method::POST,
+            }]
+        );
+
+        let snake = OperationIdPattern::SNAKE_CASE.to_regex();
+        assert_eq!(spec.verify_operation_id_format(&snake).len(), 1);
+
+        let verb_noun = OperationIdPattern::VERB_NOUN_CAMEL.to_regex();
+        assert!(verb_noun.is_match("getPetById"));
+        assert!(!verb_noun.is_match("petsGet"));
+        assert!(OperationIdPattern::PASCAL_CASE
+            .to_regex()
+            .is_match("ListPets"));
+    }
+
+    #[test]
+    fn all_parameter_schemas() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets/{id}:
+    get:
+      parameters:
+      - { name: id, in: path, required: true, schema: { type: integer } }
+      - { name: id, in: query, schema: { $ref: '#/components/schemas/Id' } }
+      - $ref: '#/components/parameters/Filter'
+      - { name: X-Trace, in: header }
+      responses: {}
+components:
+  schemas:
+    Id: { type: string }
+  parameters:
+    Filter:
+      name: filter
+      in: query
+      content:
+        application/json:
+          schema: { type: object }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let op = spec.paths["/pets/{id}"].get.as_ref().unwrap();
+        let schemas = op.all_parameter_schemas(&spec).unwrap();
+
+        assert_eq!(
+            schemas,
+            btreemap! {
+                ("id".to_owned(), ParamLoc::Path) => Schema::integer(),
+                ("id".to_owned(), ParamLoc::Query) => Schema::string(),
+                ("filter".to_owned(), ParamLoc::Query) => Schema::object(),
+            }
+        );
+    }
+
+    #[test]
+    fn operation_lookup_by_id() {
+        let mut spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets:
+    get: { operationId: listPets, responses: {} }
+    post: { operationId: createPet, responses: {} }
+  /pets/{id}:
+    get: { operationId: getPet, responses: {} }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let op = spec.operation_by_id("getPet").unwrap();
+        assert_eq!((op.path, op.method), ("/pets/{id}", Method::GET));
+        assert!(spec.operation_by_id("getpet").is_none());
+
+        let index = OperationIndex::build(&spec);
+        assert_eq!(index.get("getPet"), spec.operation_by_id("getPet").as_ref());
+        assert_eq!(index.get("createPet").unwrap().method, Method::POST);
+        assert!(index.get("getpet").is_none());
+
+        let ids = |ops: Vec<OperationRef<'_>>| {
+            ops.into_iter()
+                .map(|op| op.operation.operation_id.clone().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(spec.operation_by_id_fuzzy("PET")),
+            ["listPets", "createPet", "getPet"]
+        );
+        assert_eq!(ids(spec.operation_by_id_fuzzy("ets")), ["listPets"]);
+        assert!(spec.operation_by_id_fuzzy("store").is_empty());
+
+        // lookups reflect changes to the paths
+        let get = spec
+            .paths
+            .get_mut("/pets/{id}")
+            .unwrap()
+            .get
+            .take()
+            .unwrap();
+        spec.paths.get_mut("/pets").unwrap().put = Some(get);
+        let op = spec.operation_by_id("getPet").unwrap();
+        assert_eq!((op.path, op.method), ("/pets", Method::PUT));
+    }
+}
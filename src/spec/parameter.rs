@@ -1,10 +1,15 @@
+use std::collections::BTreeMap;
+
+use derive_more::{Display, Error, From};
 use serde::{Deserialize, Serialize};
 
-use super::{FromRef, Ref, RefError, RefType, Spec};
+use super::{
+    server::template_variables, FromRef, MediaType, ObjectOrReference, Ref, RefError, RefType,
+    Spec, SpecRefResolver,
+};
 use crate::Schema;
-use serde::ser::SerializeStruct;
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[serde(rename_all = "camelCase")]
 pub enum ParamLoc {
     #[default]
@@ -21,7 +26,7 @@ pub enum ParamLoc {
 /// and [location](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#parameterIn).
 ///
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#parameterObject>.
-#[derive(Clone, Debug, Deserialize, PartialEq, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Parameter {
     pub name: String,
 
@@ -46,7 +51,12 @@ pub struct Parameter {
     style: Option<ParameterStyle>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub schema: Option<Schema>,
+    pub schema: Option<ObjectOrReference<Schema>>,
+
+    /// Alternative to `schema` for complex serializations; holds a single media type.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub content: BTreeMap<String, MediaType>,
     //    #[serde(skip_serializing_if = "Option::is_none")]
     //    #[serde(rename = "uniqueItems")]
     //    pub unique_items: Option<bool>,
@@ -60,7 +70,7 @@ pub struct Parameter {
     //    pub format: Option<String>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 enum ParameterStyle {
     Form,
@@ -72,6 +82,23 @@ enum ParameterStyle {
     DeepObject,
 }
 
+impl Parameter {
+    /// Resolves the schema of the parameter, taken from the single entry of `content` if there is
+    /// no `schema`. Returns `None` if neither is present.
+    pub fn resolved_schema(&self, spec: &Spec) -> Result<Option<Schema>, RefError> {
+        self.schema
+            .as_ref()
+            .or_else(|| {
+                self.content
+                    .values()
+                    .next()
+                    .and_then(|media_type| media_type.schema.as_ref())
+            })
+            .map(|schema| schema.resolve(spec))
+            .transpose()
+    }
+}
+
 impl FromRef for Parameter {
     fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError>
     where
@@ -92,33 +119,384 @@ impl FromRef for Parameter {
     }
 }
 
-impl Serialize for Parameter {
-    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut s = serializer.serialize_struct("Parameter", 10)?;
-
-        s.serialize_field("name", &self.name)?;
-        s.serialize_field("in", &self.location)?;
-        s.serialize_field("description", &self.description)?;
-        s.serialize_field("required", &self.required)?;
-        s.serialize_field("deprecated", &self.deprecated)?;
-        s.serialize_field("schema", &self.schema)?;
-        //s.serialize_field("uniqueItems", &self.unique_items)?;
-        //s.serialize_field("type", &self.param_type)?;
-        //s.serialize_field("format", &self.format)?;
-
-        if let Some(style) = &self.style {
-            s.serialize_field("style", style)?;
-        } else {
-            match self.location {
-                ParamLoc::Query | ParamLoc::Cookie => {
-                    s.serialize_field("style", &ParameterStyle::Form)?;
+/// Problems with the parameters declared for an operation.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum ParameterUniquenessError {
+    #[display(
+        fmt = "Parameter {} in {:?} is declared more than once",
+        name,
+        location
+    )]
+    Duplicate {
+        name: String,
+        location: ParamLoc,
+        path_template: String,
+    },
+
+    #[display(fmt = "Path parameter {} does not appear in {}", name, path_template)]
+    OrphanedPathParam { name: String, path_template: String },
+}
+
+/// Errors collecting the path parameter schemas of a path.
+#[derive(Debug, Clone, PartialEq, Display, Error, From)]
+pub enum PathParameterError {
+    #[display(fmt = "Path not found: {}", _0)]
+    #[from(ignore)]
+    PathNotFound(#[error(not(source))] String),
+
+    #[display(
+        fmt = "Path parameter {} has different schemas in different operations of {}",
+        name,
+        path_template
+    )]
+    #[from(ignore)]
+    ConflictingSchemas { name: String, path_template: String },
+
+    #[display(fmt = "Reference error")]
+    Ref(RefError),
+}
+
+impl Spec {
+    /// Checks that parameters are unique by name and location within each parameter list, and
+    /// that every path parameter of an operation appears in its path template.
+    ///
+    /// Unresolvable parameter references are ignored.
+    pub fn validate_parameter_uniqueness(&self) -> Vec<ParameterUniquenessError> {
+        let mut errors = vec![];
+
+        for (path, path_item) in &self.paths {
+            let lists = std::iter::once(&path_item.parameters).chain(
+                path_item
+                    .methods()
+                    .into_iter()
+                    .map(|(_, operation)| &operation.parameters),
+            );
+
+            for params in lists {
+                self.push_duplicate_params(path, params, &mut errors);
+            }
+        }
+
+        for op in self.operation_refs() {
+            let params = match op.path_item.effective_parameters(op.operation, self) {
+                Ok(params) => params,
+                Err(_) => continue,
+            };
+            let placeholders = template_variables(op.path);
+
+            for param in params {
+                if param.location == ParamLoc::Path && !placeholders.contains(&param.name.as_str())
+                {
+                    let error = ParameterUniquenessError::OrphanedPathParam {
+                        name: param.name,
+                        path_template: op.path.to_owned(),
+                    };
+
+                    // path item parameters are checked once per path
+                    if !errors.contains(&error) {
+                        errors.push(error);
+                    }
                 }
-                ParamLoc::Path | ParamLoc::Header => {
-                    s.serialize_field("style", &ParameterStyle::Simple)?;
+            }
+        }
+
+        errors
+    }
+
+    /// Returns the resolved schemas of the path parameters of a path, by parameter name.
+    ///
+    /// Parameters are collected from every operation of the path, along with those declared on
+    /// the path item. Parameters without a schema are omitted.
+    pub fn path_parameter_schemas(
+        &self,
+        path_template: &str,
+    ) -> Result<BTreeMap<String, Schema>, PathParameterError> {
+        let path_item = self
+            .path_items()
+            .find(|(path, _)| *path == path_template)
+            .map(|(_, path_item)| path_item)
+            .ok_or_else(|| PathParameterError::PathNotFound(path_template.to_owned()))?;
+
+        let mut param_lists = path_item
+            .methods()
+            .into_iter()
+            .map(|(_, operation)| path_item.effective_parameters(operation, self))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if param_lists.is_empty() {
+            param_lists.push(
+                path_item
+                    .parameters
+                    .iter()
+                    .map(|oor| oor.resolve(self))
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+
+        let resolver = SpecRefResolver::new(self);
+        let mut schemas = BTreeMap::new();
+
+        for param in param_lists.into_iter().flatten() {
+            let schema = match (&param.location, &param.schema) {
+                (ParamLoc::Path, Some(schema)) => schema.resolve_with(&resolver)?,
+                _ => continue,
+            };
+
+            match schemas.get(&param.name) {
+                Some(existing) if *existing != schema => {
+                    return Err(PathParameterError::ConflictingSchemas {
+                        name: param.name,
+                        path_template: path_template.to_owned(),
+                    });
                 }
+                Some(_) => {}
+                None => {
+                    schemas.insert(param.name, schema);
+                }
+            }
+        }
+
+        Ok(schemas)
+    }
+
+    /// Returns the resolved schema of a parameter of the operation with the given ID.
+    ///
+    /// Parameters declared on the operation's path item are included, unless overridden by the
+    /// operation. Returns `None` if the operation or parameter is not found, if the parameter has
+    /// no schema, or if it cannot be resolved.
+    pub fn get_schema_for_parameter(
+        &self,
+        operation_id: &str,
+        param_name: &str,
+        param_in: ParamLoc,
+    ) -> Option<Schema> {
+        self.effective_parameter(operation_id, param_name, param_in)?
+            .resolved_schema(self)
+            .ok()
+            .flatten()
+    }
+
+    /// Returns whether a parameter of the operation with the given ID is required.
+    ///
+    /// Path parameters are always required; other parameters are required if their `required`
+    /// field is true. Returns `None` if the operation or parameter is not found.
+    pub fn parameter_is_required(
+        &self,
+        operation_id: &str,
+        param_name: &str,
+        param_in: ParamLoc,
+    ) -> Option<bool> {
+        let param = self.effective_parameter(operation_id, param_name, param_in)?;
+        Some(param.location == ParamLoc::Path || param.required == Some(true))
+    }
+
+    fn effective_parameter(
+        &self,
+        operation_id: &str,
+        param_name: &str,
+        param_in: ParamLoc,
+    ) -> Option<Parameter> {
+        let op = self.operation_by_id(operation_id)?;
+
+        op.path_item
+            .effective_parameters(op.operation, self)
+            .ok()?
+            .into_iter()
+            .find(|param| param.name == param_name && param.location == param_in)
+    }
+
+    fn push_duplicate_params(
+        &self,
+        path: &str,
+        params: &[ObjectOrReference<Parameter>],
+        errors: &mut Vec<ParameterUniquenessError>,
+    ) {
+        let mut seen = Vec::<(String, ParamLoc)>::new();
+
+        for param in params.iter().filter_map(|oor| oor.resolve(self).ok()) {
+            let key = (param.name, param.location);
+
+            if seen.contains(&key) {
+                errors.push(ParameterUniquenessError::Duplicate {
+                    name: key.0,
+                    location: key.1,
+                    path_template: path.to_owned(),
+                });
+            } else {
+                seen.push(key);
             }
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{KnownFormat, SchemaType};
+
+    #[test]
+    fn parameter_uniqueness() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets/{petId}:
+    parameters:
+    - { name: petId, in: path, required: true }
+    - { name: ownerId, in: path, required: true }
+    get:
+      parameters:
+      - { name: petId, in: path, required: true }
+      - { name: limit, in: query }
+      - $ref: '#/components/parameters/Limit'
+      - { name: limit, in: header }
+      responses: {}
+    delete:
+      responses: {}
+components:
+  parameters:
+    Limit: { name: limit, in: query }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.validate_parameter_uniqueness(),
+            [
+                ParameterUniquenessError::Duplicate {
+                    name: "limit".to_owned(),
+                    location: ParamLoc::Query,
+                    path_template: "/pets/{petId}".to_owned(),
+                },
+                ParameterUniquenessError::OrphanedPathParam {
+                    name: "ownerId".to_owned(),
+                    path_template: "/pets/{petId}".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn path_parameter_schemas() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets/{petId}/toys/{toyId}:
+    parameters:
+    - { name: petId, in: path, required: true, schema: { $ref: '#/components/schemas/Id' } }
+    get:
+      parameters:
+      - { name: toyId, in: path, required: true, schema: { type: integer } }
+      - { name: verbose, in: query, schema: { type: boolean } }
+      responses: {}
+    delete:
+      parameters:
+      - { name: toyId, in: path, required: true, schema: { type: integer } }
+      responses: {}
+  /owners/{ownerId}:
+    get:
+      parameters:
+      - { name: ownerId, in: path, required: true, schema: { type: integer } }
+      responses: {}
+    put:
+      parameters:
+      - { name: ownerId, in: path, required: true, schema: { type: string } }
+      responses: {}
+components:
+  schemas:
+    Id: { type: string, format: uuid }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let schemas = spec
+            .path_parameter_schemas("/pets/{petId}/toys/{toyId}")
+            .unwrap();
+        assert_eq!(schemas.len(), 2);
+        assert_eq!(schemas["petId"].format, Some(KnownFormat::Uuid));
+        assert_eq!(schemas["toyId"], Schema::integer());
+
+        assert_eq!(
+            spec.path_parameter_schemas("/owners/{ownerId}")
+                .unwrap_err(),
+            PathParameterError::ConflictingSchemas {
+                name: "ownerId".to_owned(),
+                path_template: "/owners/{ownerId}".to_owned(),
+            }
+        );
+        assert_eq!(
+            spec.path_parameter_schemas("/nope").unwrap_err(),
+            PathParameterError::PathNotFound("/nope".to_owned())
+        );
+    }
+
+    #[test]
+    fn parameter_lookup() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets/{petId}:
+    parameters:
+    - { name: petId, in: path, schema: { $ref: '#/components/schemas/Id' } }
+    - { name: verbose, in: query, schema: { type: boolean } }
+    get:
+      operationId: getPet
+      parameters:
+      - { name: verbose, in: query, required: true, schema: { type: integer } }
+      - name: filter
+        in: query
+        content: { application/json: { schema: { type: object } } }
+      responses: {}
+components:
+  schemas:
+    Id: { type: string, format: uuid }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let schema = spec
+            .get_schema_for_parameter("getPet", "petId", ParamLoc::Path)
+            .unwrap();
+        assert_eq!(schema.format, Some(KnownFormat::Uuid));
+        assert_eq!(
+            spec.get_schema_for_parameter("getPet", "verbose", ParamLoc::Query),
+            Some(Schema::integer())
+        );
+        assert_eq!(
+            spec.get_schema_for_parameter("getPet", "filter", ParamLoc::Query)
+                .unwrap()
+                .schema_type,
+            Some(SchemaType::Object)
+        );
+        assert_eq!(
+            spec.get_schema_for_parameter("getPet", "petId", ParamLoc::Query),
+            None
+        );
+        assert_eq!(
+            spec.get_schema_for_parameter("listPets", "petId", ParamLoc::Path),
+            None
+        );
 
-        s.end()
+        assert_eq!(
+            spec.parameter_is_required("getPet", "petId", ParamLoc::Path),
+            Some(true)
+        );
+        assert_eq!(
+            spec.parameter_is_required("getPet", "verbose", ParamLoc::Query),
+            Some(true)
+        );
+        assert_eq!(
+            spec.parameter_is_required("getPet", "filter", ParamLoc::Query),
+            Some(false)
+        );
+        assert_eq!(
+            spec.parameter_is_required("getPet", "limit", ParamLoc::Query),
+            None
+        );
     }
 }
@@ -1,7 +1,11 @@
+use derive_more::{Display, Error};
 use http::Method;
 use serde::{Deserialize, Serialize};
 
-use super::{ObjectOrReference, Operation, Parameter, Server};
+use super::{
+    server::template_variables, FromRef, ObjectOrReference, Operation, ParamLoc, Parameter, Ref,
+    RefError, RefType, Server, Spec,
+};
 
 /// Describes the operations available on a single path.
 ///
@@ -102,8 +106,464 @@ impl PathItem {
         push_method!(head, HEAD);
         push_method!(patch, PATCH);
         push_method!(trace, TRACE);
-        push_method!(trace, TRACE);
 
         methods
     }
+
+    /// Resolves the parameters of an operation on this path, including those inherited from the
+    /// path item.
+    ///
+    /// Operation parameters override path item parameters with the same name and location.
+    pub fn effective_parameters(
+        &self,
+        operation: &Operation,
+        spec: &Spec,
+    ) -> Result<Vec<Parameter>, RefError> {
+        let resolve = |params: &[ObjectOrReference<Parameter>]| {
+            params
+                .iter()
+                .map(|oor| oor.resolve(spec))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let mut params = resolve(&self.parameters)?;
+
+        for param in resolve(&operation.parameters)? {
+            params.retain(|p| p.name != param.name || p.location != param.location);
+            params.push(param);
+        }
+
+        Ok(params)
+    }
+
+    /// Iterates mutably over the operations defined on this path.
+    pub(crate) fn operations_mut(&mut self) -> impl Iterator<Item = &mut Operation> {
+        [
+            &mut self.get,
+            &mut self.put,
+            &mut self.post,
+            &mut self.delete,
+            &mut self.options,
+            &mut self.head,
+            &mut self.patch,
+            &mut self.trace,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Removes the operations for which `keep` returns false.
+    pub(crate) fn retain_operations(&mut self, mut keep: impl FnMut(&Operation) -> bool) {
+        for op in [
+            &mut self.get,
+            &mut self.put,
+            &mut self.post,
+            &mut self.delete,
+            &mut self.options,
+            &mut self.head,
+            &mut self.patch,
+            &mut self.trace,
+        ] {
+            if op.as_ref().map_or(false, |op| !keep(op)) {
+                *op = None;
+            }
+        }
+    }
+}
+
+impl FromRef for PathItem {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        let refpath = path.parse::<Ref>()?;
+
+        match refpath.kind {
+            RefType::PathItem => spec
+                .components
+                .as_ref()
+                .and_then(|cs| cs.path_items.get(&refpath.name))
+                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
+                .and_then(|oor| oor.resolve(spec)),
+
+            typ => Err(RefError::MismatchedType(typ, RefType::PathItem)),
+        }
+    }
+}
+
+/// Errors adding a path to a spec.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum PathError {
+    #[display(fmt = "Unbalanced braces in path template {}", _0)]
+    UnbalancedBraces(#[error(not(source))] String),
+
+    #[display(fmt = "Empty variable name in path template {}", _0)]
+    EmptyVariable(#[error(not(source))] String),
+
+    /// The path is already defined, possibly with different variable names.
+    #[display(fmt = "Path {} conflicts with existing path {}", template, existing)]
+    Duplicate { template: String, existing: String },
+
+    #[display(fmt = "Path variable {} of {} has no path parameter", name, template)]
+    MissingPathParameter { template: String, name: String },
+}
+
+/// A path item with its references resolved and inherited values applied, as returned by
+/// [`Spec::resolve_path_item`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedPathItem {
+    /// Path template, e.g. `/pets/{petId}`.
+    pub path: String,
+
+    /// The path item, or the component path item it refers to, with the parameters of the path
+    /// item and its operations resolved inline.
+    pub path_item: PathItem,
+
+    /// Servers of the path item, or else the root servers, or else the implied `/` server.
+    pub servers: Vec<Server>,
+
+    /// The operations of the path item, in the order of [`PathItem::methods`].
+    pub operations: Vec<ResolvedOperation>,
+}
+
+/// An operation of a [`ResolvedPathItem`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedOperation {
+    pub method: Method,
+
+    /// The operation, with its parameters resolved inline.
+    pub operation: Operation,
+
+    /// Parameters of the operation, including those inherited from the path item, as returned
+    /// by [`PathItem::effective_parameters`].
+    pub parameters: Vec<Parameter>,
+
+    /// Servers of the operation, or else those of the path item.
+    pub servers: Vec<Server>,
+}
+
+impl Spec {
+    /// Returns a resolved view of the path item for the given path template, for routing and
+    /// validating requests without chasing references.
+    ///
+    /// Returns `None` if the path does not exist or if a reference to a path item or parameter
+    /// cannot be resolved.
+    pub fn resolve_path_item(&self, template: &str) -> Option<ResolvedPathItem> {
+        let path_item = self.paths.get(template)?;
+
+        let mut path_item = match &path_item.reference {
+            Some(ref_path) => PathItem::from_ref(self, ref_path).ok()?,
+            None => path_item.clone(),
+        };
+
+        let servers = match [&path_item.servers, &self.servers]
+            .into_iter()
+            .find(|servers| !servers.is_empty())
+        {
+            Some(servers) => servers.clone(),
+            None => vec![Server {
+                url: "/".to_owned(),
+                ..Server::default()
+            }],
+        };
+
+        let operations = path_item
+            .methods()
+            .into_iter()
+            .map(|(method, operation)| {
+                let parameters = path_item.effective_parameters(operation, self).ok()?;
+
+                let servers = if operation.servers.is_empty() {
+                    servers.clone()
+                } else {
+                    operation.servers.clone()
+                };
+
+                let mut operation = operation.clone();
+                operation.parameters = resolve_inline(&operation.parameters, self)?;
+
+                Some(ResolvedOperation {
+                    method,
+                    operation,
+                    parameters,
+                    servers,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        path_item.parameters = resolve_inline(&path_item.parameters, self)?;
+        for operation in path_item.operations_mut() {
+            operation.parameters = resolve_inline(&operation.parameters, self)?;
+        }
+
+        Some(ResolvedPathItem {
+            path: template.to_owned(),
+            path_item,
+            servers,
+            operations,
+        })
+    }
+
+    /// Adds a path item to the spec.
+    ///
+    /// Fails if the path template is malformed, if an existing path matches the same URLs
+    /// (e.g., `/pets/{id}` and `/pets/{petId}`), or if a template variable is not declared as an
+    /// `in: path` parameter of the path item or one of its operations.
+    pub fn add_path(&mut self, template: &str, path_item: PathItem) -> Result<(), PathError> {
+        let shape = template_shape(template)?;
+
+        if let Some(existing) = self
+            .paths
+            .keys()
+            .find(|existing| template_shape(existing).ok().as_ref() == Some(&shape))
+        {
+            return Err(PathError::Duplicate {
+                template: template.to_owned(),
+                existing: existing.clone(),
+            });
+        }
+
+        let path_params = path_item
+            .parameters
+            .iter()
+            .chain(
+                path_item
+                    .methods()
+                    .into_iter()
+                    .flat_map(|(_, op)| &op.parameters),
+            )
+            .filter_map(|oor| oor.resolve(self).ok())
+            .filter(|param| param.location == ParamLoc::Path)
+            .map(|param| param.name)
+            .collect::<Vec<_>>();
+
+        for name in template_variables(template) {
+            if !path_params.iter().any(|param| param == name) {
+                return Err(PathError::MissingPathParameter {
+                    template: template.to_owned(),
+                    name: name.to_owned(),
+                });
+            }
+        }
+
+        self.paths.insert(template.to_owned(), path_item);
+        Ok(())
+    }
+}
+
+/// Resolves a list of parameters, keeping them as inline objects.
+fn resolve_inline(
+    params: &[ObjectOrReference<Parameter>],
+    spec: &Spec,
+) -> Option<Vec<ObjectOrReference<Parameter>>> {
+    params
+        .iter()
+        .map(|oor| oor.resolve(spec).ok().map(ObjectOrReference::Object))
+        .collect()
+}
+
+/// Validates a path template, returning it with variable names removed (e.g., `/pets/{}`).
+fn template_shape(template: &str) -> Result<String, PathError> {
+    let mut shape = String::with_capacity(template.len());
+    let mut variable = None::<String>;
+
+    for c in template.chars() {
+        match (c, &mut variable) {
+            ('{', None) => variable = Some(String::new()),
+            ('}', Some(name)) if name.is_empty() => {
+                return Err(PathError::EmptyVariable(template.to_owned()))
+            }
+            ('}', Some(_)) => {
+                shape.push_str("{}");
+                variable = None;
+            }
+            ('{' | '}', _) => return Err(PathError::UnbalancedBraces(template.to_owned())),
+            (c, Some(name)) => name.push(c),
+            (c, None) => shape.push(c),
+        }
+    }
+
+    match variable {
+        Some(_) => Err(PathError::UnbalancedBraces(template.to_owned())),
+        None => Ok(shape),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_param(name: &str) -> ObjectOrReference<Parameter> {
+        let mut param = Parameter::default();
+        param.name = name.to_owned();
+        param.location = ParamLoc::Path;
+        param.required = Some(true);
+
+        ObjectOrReference::Object(param)
+    }
+
+    #[test]
+    fn add_paths() {
+        let mut spec = Spec::default();
+
+        let pet = PathItem {
+            parameters: vec![path_param("id")],
+            ..PathItem::default()
+        };
+        spec.add_path("/pets/{id}", pet).unwrap();
+        assert!(spec.paths.contains_key("/pets/{id}"));
+
+        let other_pet = PathItem {
+            get: Some(Operation {
+                parameters: vec![path_param("petId")],
+                ..Operation::default()
+            }),
+            ..PathItem::default()
+        };
+        assert_eq!(
+            spec.add_path("/pets/{petId}", other_pet).unwrap_err(),
+            PathError::Duplicate {
+                template: "/pets/{petId}".to_owned(),
+                existing: "/pets/{id}".to_owned(),
+            }
+        );
+
+        assert_eq!(
+            spec.add_path("/owners/{id}", PathItem::default())
+                .unwrap_err(),
+            PathError::MissingPathParameter {
+                template: "/owners/{id}".to_owned(),
+                name: "id".to_owned(),
+            }
+        );
+        assert_eq!(
+            spec.add_path("/owners/{id", PathItem::default())
+                .unwrap_err(),
+            PathError::UnbalancedBraces("/owners/{id".to_owned())
+        );
+        assert_eq!(
+            spec.add_path("/owners}", PathItem::default()).unwrap_err(),
+            PathError::UnbalancedBraces("/owners}".to_owned())
+        );
+        assert_eq!(
+            spec.add_path("/owners/{}", PathItem::default())
+                .unwrap_err(),
+            PathError::EmptyVariable("/owners/{}".to_owned())
+        );
+    }
+
+    #[test]
+    fn operation_parameters_override_path_parameters() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets:
+    parameters:
+    - $ref: '#/components/parameters/Limit'
+    - { name: limit, in: header }
+    - { name: sort, in: query }
+    get:
+      parameters:
+      - { name: limit, in: query, required: true }
+      responses: {}
+components:
+  parameters:
+    Limit: { name: limit, in: query }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let path_item = &spec.paths["/pets"];
+        let params = path_item
+            .effective_parameters(path_item.get.as_ref().unwrap(), &spec)
+            .unwrap();
+
+        let params = params
+            .iter()
+            .map(|param| (param.name.as_str(), param.location.clone(), param.required))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            params,
+            [
+                ("limit", ParamLoc::Header, None),
+                ("sort", ParamLoc::Query, None),
+                ("limit", ParamLoc::Query, Some(true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolved_path_items() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+servers: [{ url: "https://api.example.com" }]
+paths:
+  /pets:
+    $ref: '#/components/pathItems/Pets'
+  /pets/{petId}:
+    servers: [{ url: "https://pets.example.com" }]
+    parameters:
+    - $ref: '#/components/parameters/PetId'
+    get:
+      servers: [{ url: "https://cdn.example.com" }]
+      parameters:
+      - { name: verbose, in: query }
+      responses: {}
+    delete: { responses: {} }
+  /broken:
+    parameters:
+    - $ref: '#/components/parameters/Missing'
+components:
+  parameters:
+    PetId: { name: petId, in: path, required: true }
+  pathItems:
+    Pets:
+      get: { operationId: listPets, responses: {} }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let resolved = spec.resolve_path_item("/pets/{petId}").unwrap();
+        assert_eq!(resolved.path, "/pets/{petId}");
+        assert_eq!(resolved.servers[0].url, "https://pets.example.com");
+        assert!(matches!(
+            &resolved.path_item.parameters[0],
+            ObjectOrReference::Object(param) if param.name == "petId"
+        ));
+
+        let summary = resolved
+            .operations
+            .iter()
+            .map(|op| {
+                let params = op
+                    .parameters
+                    .iter()
+                    .map(|param| param.name.as_str())
+                    .collect::<Vec<_>>();
+                (op.method.clone(), params, op.servers[0].url.as_str())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (
+                    Method::GET,
+                    vec!["petId", "verbose"],
+                    "https://cdn.example.com"
+                ),
+                (Method::DELETE, vec!["petId"], "https://pets.example.com"),
+            ]
+        );
+
+        let resolved = spec.resolve_path_item("/pets").unwrap();
+        assert_eq!(resolved.servers[0].url, "https://api.example.com");
+        assert_eq!(
+            resolved.operations[0].operation.operation_id.as_deref(),
+            Some("listPets")
+        );
+
+        assert!(spec.resolve_path_item("/broken").is_none());
+        assert!(spec.resolve_path_item("/stores").is_none());
+    }
 }
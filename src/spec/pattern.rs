@@ -0,0 +1,278 @@
+//! ECMA-262 regular expressions used by schema `pattern`s.
+
+use std::{cell::RefCell, collections::HashMap};
+
+use derive_more::{Display, Error};
+use regex::Regex;
+
+use super::{ObjectOrReference, Schema};
+
+/// Number of compiled patterns kept per thread by [`compile_ecma_regex_cached`].
+const REGEX_CACHE_CAPACITY: usize = 256;
+
+thread_local! {
+    static REGEX_CACHE: RefCell<HashMap<String, Regex>> = RefCell::new(HashMap::new());
+}
+
+/// Errors compiling a schema `pattern`.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum PatternError {
+    #[display(fmt = "Schema has no pattern")]
+    Missing,
+
+    #[display(fmt = "Invalid pattern {:?}: {}", pattern, reason)]
+    Invalid { pattern: String, reason: String },
+}
+
+impl Schema {
+    /// Compiles the schema's `pattern`, converting ECMA-262 syntax to that of the [`regex`] crate
+    /// on a best-effort basis.
+    ///
+    /// Character class escapes (`\d`, `\w`) are restricted to ASCII as in ECMA-262, and syntax
+    /// with no equivalent (e.g., `\cJ`, `[^]`) is rewritten. Features the `regex` crate does not
+    /// support, such as lookaround and backreferences, fail to compile.
+    pub fn compile_pattern(&self) -> Result<Regex, PatternError> {
+        match &self.pattern {
+            Some(pattern) => compile_ecma_regex(pattern),
+            None => Err(PatternError::Missing),
+        }
+    }
+
+    /// Returns the `patternProperties` schemas that apply to a property named `key`, i.e. those
+    /// whose patterns match it, in pattern order.
+    ///
+    /// Compiled patterns are cached per thread, so repeated lookups are cheap. Schemas given as
+    /// references are returned unresolved. Fails if any pattern is invalid.
+    pub fn matching_pattern_schemas(
+        &self,
+        key: &str,
+    ) -> Result<Vec<&ObjectOrReference<Schema>>, PatternError> {
+        let matching = self.matching_pattern_properties(key)?;
+        Ok(matching.into_iter().map(|(_, oor)| oor).collect())
+    }
+
+    /// Like [`matching_pattern_schemas`](Self::matching_pattern_schemas), along with the patterns
+    /// that matched.
+    pub(crate) fn matching_pattern_properties(
+        &self,
+        key: &str,
+    ) -> Result<Vec<(&str, &ObjectOrReference<Schema>)>, PatternError> {
+        let mut matching = vec![];
+
+        for (pattern, oor) in &self.pattern_properties {
+            if compile_ecma_regex_cached(pattern)?.is_match(key) {
+                matching.push((pattern.as_str(), oor));
+            }
+        }
+
+        Ok(matching)
+    }
+}
+
+/// Compiles an ECMA-262 regular expression. See [`Schema::compile_pattern`].
+pub(crate) fn compile_ecma_regex(pattern: &str) -> Result<Regex, PatternError> {
+    Regex::new(&ecma_to_rust(pattern)).map_err(|err| PatternError::Invalid {
+        pattern: pattern.to_owned(),
+        reason: err.to_string(),
+    })
+}
+
+/// Compiles an ECMA-262 regular expression, reusing this thread's earlier compilation of the same
+/// pattern if there is one.
+pub(crate) fn compile_ecma_regex_cached(pattern: &str) -> Result<Regex, PatternError> {
+    if let Some(regex) = REGEX_CACHE.with(|cache| cache.borrow().get(pattern).cloned()) {
+        return Ok(regex);
+    }
+
+    let regex = compile_ecma_regex(pattern)?;
+
+    REGEX_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+
+        if cache.len() >= REGEX_CACHE_CAPACITY {
+            cache.clear();
+        }
+
+        cache.insert(pattern.to_owned(), regex.clone());
+    });
+
+    Ok(regex)
+}
+
+/// Rewrites ECMA-262 regex syntax that the `regex` crate rejects or interprets differently.
+fn ecma_to_rust(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+
+        if c == '[' && rest.starts_with(']') {
+            // matches nothing
+            out.push_str(r"[^\x00-\x{10FFFF}]");
+            rest = &rest[1..];
+            continue;
+        }
+
+        if c == '[' && rest.starts_with("^]") {
+            // matches anything
+            out.push_str("(?s:.)");
+            rest = &rest[2..];
+            continue;
+        }
+
+        if c == '(' && rest.starts_with("?<") && !rest[2..].starts_with(['=', '!']) {
+            out.push_str("(?P<");
+            rest = &rest[2..];
+            continue;
+        }
+
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        let escaped = match rest.chars().next() {
+            Some(escaped) => escaped,
+            None => {
+                out.push(c);
+                break;
+            }
+        };
+        rest = &rest[escaped.len_utf8()..];
+
+        match escaped {
+            'd' => out.push_str("[0-9]"),
+            'D' => out.push_str("[^0-9]"),
+            'w' => out.push_str("[0-9A-Za-z_]"),
+            'W' => out.push_str("[^0-9A-Za-z_]"),
+            '/' => out.push('/'),
+            'c' if rest.starts_with(|l: char| l.is_ascii_alphabetic()) => {
+                out.push_str(&format!(r"\x{:02X}", rest.as_bytes()[0] % 32));
+                rest = &rest[1..];
+            }
+            escaped => {
+                out.push(c);
+                out.push(escaped);
+            }
+        }
+    }
+
+    out
+}
+
+/// Deserializes a `pattern`, rejecting those that do not compile.
+#[cfg(feature = "validate-patterns")]
+pub(crate) fn deserialize_checked<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::{de::Error as _, Deserialize as _};
+
+    let pattern = Option::<String>::deserialize(deserializer)?;
+
+    if let Some(pattern) = &pattern {
+        compile_ecma_regex(pattern).map_err(D::Error::custom)?;
+    }
+
+    Ok(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::SchemaType;
+
+    #[test]
+    fn ecma_syntax_conversion() {
+        assert_eq!(ecma_to_rust(r"^\d{3}-\w+$"), r"^[0-9]{3}-[0-9A-Za-z_]+$");
+        assert_eq!(ecma_to_rust(r"[\d_]\/\."), r"[[0-9]_]/\.");
+        assert_eq!(ecma_to_rust(r"(?<year>\d{4})"), r"(?P<year>[0-9]{4})");
+        assert_eq!(ecma_to_rust(r"\cJ[^]"), r"\x0A(?s:.)");
+        assert_eq!(ecma_to_rust(r"a\\d"), r"a\\d");
+    }
+
+    #[test]
+    fn compile_schema_pattern() {
+        let schema = Schema {
+            pattern: Some(r"^\d+$".to_owned()),
+            ..Schema::string()
+        };
+
+        let regex = schema.compile_pattern().unwrap();
+        assert!(regex.is_match("123"));
+        // `\d` only matches ASCII digits
+        assert!(!regex.is_match("١٢٣"));
+
+        assert_eq!(
+            Schema::string().compile_pattern().unwrap_err(),
+            PatternError::Missing
+        );
+
+        let schema = Schema {
+            pattern: Some("(?<=a)b".to_owned()),
+            ..Schema::string()
+        };
+        assert!(matches!(
+            schema.compile_pattern(),
+            Err(PatternError::Invalid { .. })
+        ));
+    }
+
+    #[cfg(feature = "validate-patterns")]
+    #[test]
+    fn invalid_patterns_fail_to_parse() {
+        serde_json::from_value::<Schema>(serde_json::json!({ "pattern": r"^\d+$" })).unwrap();
+        serde_json::from_value::<Schema>(serde_json::json!({ "pattern": "(unclosed" }))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn pattern_property_lookup() {
+        let schema = serde_json::from_value::<Schema>(serde_json::json!({
+            "type": "object",
+            "patternProperties": {
+                "^x-": { "type": "string" },
+                "^x-rate-": { "type": "integer" },
+                "^S_": { "$ref": "#/components/schemas/Label" },
+            },
+        }))
+        .unwrap();
+
+        let types = |key| {
+            schema
+                .matching_pattern_schemas(key)
+                .unwrap()
+                .into_iter()
+                .map(|oor| match oor {
+                    ObjectOrReference::Object(schema) => schema.schema_type,
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            types("x-rate-limit"),
+            [Some(SchemaType::String), Some(SchemaType::Integer)]
+        );
+        assert_eq!(types("x-trace"), [Some(SchemaType::String)]);
+        assert_eq!(types("S_name"), [None]);
+        assert!(types("name").is_empty());
+
+        assert_eq!(
+            schema.matching_pattern_schemas("S_name").unwrap(),
+            [&ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Label".to_owned()
+            }]
+        );
+
+        let invalid = serde_json::from_value::<Schema>(serde_json::json!({
+            "patternProperties": { "(": {} },
+        }))
+        .unwrap();
+        assert!(matches!(
+            invalid.matching_pattern_schemas("name"),
+            Err(PatternError::Invalid { .. })
+        ));
+    }
+}
@@ -0,0 +1,409 @@
+//! Postman collection export.
+
+use serde_json::{json, Value as JsonValue};
+
+use super::{mock::param_value_string, OperationRef, ParamLoc, Server, Spec};
+
+/// Schema URL identifying Postman Collection v2.1 documents.
+const POSTMAN_SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+impl Spec {
+    /// Exports the spec as a [Postman Collection v2.1](https://schema.postman.com/) document.
+    ///
+    /// Requests are grouped in one folder per tag, using the first tag of each operation, in the
+    /// order the tags are declared; untagged requests are placed at the top level. Request URLs
+    /// start with a `{{baseUrl}}` collection variable set to the first server URL, with server
+    /// variables replaced by their defaults.
+    ///
+    /// Path parameters filling a whole path segment become Postman path variables; segments such
+    /// as `{name}.json` are kept as they are. Query and header parameters are filled with
+    /// [example values](crate::Schema::example_value) and disabled unless required. Request
+    /// bodies use the first example of their first media type, or else an example value
+    /// generated from its schema. `Content-Type` and `Accept` headers are set from the first
+    /// media types of the request body and of the first `2XX` response with content.
+    pub fn generate_postman_collection(&self) -> JsonValue {
+        let mut folders = self
+            .tags
+            .iter()
+            .map(|tag| (tag.name.clone(), tag.description.clone(), vec![]))
+            .collect::<Vec<_>>();
+        let mut untagged = vec![];
+
+        for op in self.operation_refs() {
+            let item = self.postman_item(&op);
+
+            let tag = match op.operation.tags.first() {
+                Some(tag) => tag,
+                None => {
+                    untagged.push(item);
+                    continue;
+                }
+            };
+
+            match folders.iter_mut().find(|(name, ..)| name == tag) {
+                Some((.., items)) => items.push(item),
+                None => folders.push((tag.clone(), None, vec![item])),
+            }
+        }
+
+        let mut items = folders
+            .into_iter()
+            .filter(|(.., items)| !items.is_empty())
+            .map(|(name, description, items)| {
+                let mut folder = json!({ "name": name, "item": items });
+                insert_description(&mut folder, description.as_deref());
+                folder
+            })
+            .collect::<Vec<_>>();
+        items.extend(untagged);
+
+        let mut info = json!({
+            "name": self.info.title,
+            "version": self.info.version,
+            "schema": POSTMAN_SCHEMA,
+        });
+        insert_description(&mut info, self.info.description.as_deref());
+
+        let base_url = self.servers.first().map(server_url).unwrap_or_default();
+
+        json!({
+            "info": info,
+            "item": items,
+            "variable": [{ "key": "baseUrl", "value": base_url }],
+        })
+    }
+
+    fn postman_item(&self, op: &OperationRef<'_>) -> JsonValue {
+        let mut headers = vec![];
+        let mut query = vec![];
+        let mut variables = vec![];
+
+        for param in op
+            .path_item
+            .effective_parameters(op.operation, self)
+            .unwrap_or_default()
+        {
+            let value = param
+                .resolved_schema(self)
+                .ok()
+                .flatten()
+                .map(|schema| param_value_string(&schema.example_value(self)))
+                .unwrap_or_default();
+
+            let mut entry = json!({ "key": param.name, "value": value });
+            insert_description(&mut entry, param.description.as_deref());
+
+            if param.location != ParamLoc::Path && param.required != Some(true) {
+                entry["disabled"] = json!(true);
+            }
+
+            match param.location {
+                ParamLoc::Path => variables.push(entry),
+                ParamLoc::Query => query.push(entry),
+                ParamLoc::Header => headers.push(entry),
+                ParamLoc::Cookie => {}
+            }
+        }
+
+        let accept = op
+            .operation
+            .responses
+            .iter()
+            .filter(|(status, _)| status.starts_with('2'))
+            .filter_map(|(_, response)| response.resolve(self).ok())
+            .find_map(|response| response.content.into_keys().next());
+
+        if let Some(accept) = accept {
+            headers.push(json!({ "key": "Accept", "value": accept }));
+        }
+
+        let body = op
+            .operation
+            .request_body
+            .as_ref()
+            .and_then(|body| body.resolve(self).ok())
+            .and_then(|body| body.content.into_iter().next())
+            .map(|(media_type, content)| {
+                headers.push(json!({ "key": "Content-Type", "value": media_type }));
+
+                let example = content
+                    .examples(self)
+                    .into_values()
+                    .find_map(|example| example.value)
+                    .or_else(|| {
+                        let schema = content.schema.as_ref()?.resolve(self).ok()?;
+                        Some(schema.example_value(self))
+                    })
+                    .unwrap_or(JsonValue::Null);
+
+                if is_json_media_type(&media_type) {
+                    json!({
+                        "mode": "raw",
+                        "raw": serde_json::to_string_pretty(&example)
+                            .expect("JSON values should always serialize"),
+                        "options": { "raw": { "language": "json" } },
+                    })
+                } else {
+                    json!({ "mode": "raw", "raw": param_value_string(&example) })
+                }
+            });
+
+        let path = op
+            .path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                // only whole segments can be Postman path variables
+                match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                    Some(name) if !name.contains(['{', '}']) => format!(":{}", name),
+                    _ => segment.to_owned(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let query_string = query
+            .iter()
+            .filter(|entry| entry.get("disabled").is_none())
+            .map(|entry| {
+                format!(
+                    "{}={}",
+                    entry["key"].as_str().unwrap_or_default(),
+                    entry["value"].as_str().unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let mut raw = format!("{{{{baseUrl}}}}/{}", path.join("/"));
+        if !query_string.is_empty() {
+            raw = format!("{}?{}", raw, query_string);
+        }
+
+        let mut url = json!({
+            "raw": raw,
+            "host": ["{{baseUrl}}"],
+            "path": path,
+        });
+        if !query.is_empty() {
+            url["query"] = json!(query);
+        }
+        if !variables.is_empty() {
+            url["variable"] = json!(variables);
+        }
+
+        let mut request = json!({
+            "method": op.method.as_str(),
+            "header": headers,
+            "url": url,
+        });
+        insert_description(&mut request, op.operation.description.as_deref());
+        if let Some(body) = body {
+            request["body"] = body;
+        }
+
+        let name = op
+            .operation
+            .summary
+            .clone()
+            .or_else(|| op.operation.operation_id.clone())
+            .unwrap_or_else(|| format!("{} {}", op.method, op.path));
+
+        json!({ "name": name, "request": request })
+    }
+}
+
+/// Returns the server's URL with its variables replaced by their defaults.
+fn server_url(server: &Server) -> String {
+    server
+        .variables
+        .iter()
+        .fold(server.url.clone(), |url, (name, variable)| {
+            url.replace(&format!("{{{}}}", name), &variable.default)
+        })
+}
+
+fn insert_description(item: &mut JsonValue, description: Option<&str>) {
+    if let (JsonValue::Object(item), Some(description)) = (item, description) {
+        item.insert("description".to_owned(), json!(description));
+    }
+}
+
+fn is_json_media_type(media_type: &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    essence.eq_ignore_ascii_case("application/json") || essence.ends_with("+json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn postman_collection() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1.2", description: Pet store. }
+servers:
+- url: "https://{env}.example.com/v1"
+  variables: { env: { default: api } }
+tags:
+- { name: stores }
+- { name: pets, description: Everything about pets. }
+paths:
+  /pets/{petId}:
+    parameters:
+    - { name: petId, in: path, required: true, description: Pet ID., schema: { type: integer } }
+    get:
+      summary: Get a pet
+      tags: [pets]
+      parameters:
+      - { name: verbose, in: query, required: true, schema: { type: boolean } }
+      - { name: fields, in: query, description: Fields to include., schema: { type: string, examples: [name] } }
+      - { name: X-Request-Id, in: header, required: true, schema: { type: string, format: uuid } }
+      responses:
+        "200":
+          content:
+            application/json: { schema: { type: object } }
+        default:
+          content:
+            application/problem+json: { schema: { type: object } }
+    put:
+      operationId: updatePet
+      tags: [pets, admin]
+      requestBody:
+        content:
+          application/json:
+            schema: { type: object }
+            example: { name: Rex }
+      responses: {}
+  /health:
+    get:
+      description: Checks health.
+      requestBody:
+        content:
+          text/plain: { schema: { type: string, examples: [ping] } }
+      responses: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let collection = spec.generate_postman_collection();
+
+        assert_eq!(
+            collection["info"],
+            json!({
+                "name": "Pets",
+                "version": "1.2",
+                "description": "Pet store.",
+                "schema": POSTMAN_SCHEMA,
+            })
+        );
+        assert_eq!(
+            collection["variable"],
+            json!([{ "key": "baseUrl", "value": "https://api.example.com/v1" }])
+        );
+
+        let items = collection["item"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+
+        let pets = &items[0];
+        assert_eq!(pets["name"], "pets");
+        assert_eq!(pets["description"], "Everything about pets.");
+        assert_eq!(pets["item"].as_array().unwrap().len(), 2);
+
+        let get_pet = &pets["item"][0];
+        assert_eq!(get_pet["name"], "Get a pet");
+        assert_eq!(
+            get_pet["request"],
+            json!({
+                "method": "GET",
+                "header": [
+                    {
+                        "key": "X-Request-Id",
+                        "value": "00000000-0000-0000-0000-000000000000",
+                    },
+                    { "key": "Accept", "value": "application/json" },
+                ],
+                "url": {
+                    "raw": "{{baseUrl}}/pets/:petId?verbose=true",
+                    "host": ["{{baseUrl}}"],
+                    "path": ["pets", ":petId"],
+                    "query": [
+                        { "key": "verbose", "value": "true" },
+                        {
+                            "key": "fields",
+                            "value": "name",
+                            "description": "Fields to include.",
+                            "disabled": true,
+                        },
+                    ],
+                    "variable": [{ "key": "petId", "value": "0", "description": "Pet ID." }],
+                },
+            })
+        );
+
+        let update_pet = &pets["item"][1];
+        assert_eq!(update_pet["name"], "updatePet");
+        assert_eq!(
+            update_pet["request"]["header"],
+            json!([{ "key": "Content-Type", "value": "application/json" }])
+        );
+        assert_eq!(
+            update_pet["request"]["body"],
+            json!({
+                "mode": "raw",
+                "raw": "{\n  \"name\": \"Rex\"\n}",
+                "options": { "raw": { "language": "json" } },
+            })
+        );
+
+        let health = &items[1];
+        assert_eq!(health["name"], "GET /health");
+        assert_eq!(health["request"]["description"], "Checks health.");
+        assert_eq!(health["request"]["url"]["raw"], "{{baseUrl}}/health");
+        assert_eq!(
+            health["request"]["body"],
+            json!({ "mode": "raw", "raw": "ping" })
+        );
+    }
+
+    #[test]
+    fn postman_partial_path_segments() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Files, version: "1" }
+paths:
+  /files/{name}.json:
+    get:
+      parameters:
+      - { name: name, in: path, required: true, schema: { type: string } }
+      responses: {}
+  /posts/{id}-{slug}/comments/{commentId}:
+    get:
+      parameters:
+      - { name: id, in: path, required: true, schema: { type: integer } }
+      - { name: slug, in: path, required: true, schema: { type: string } }
+      - { name: commentId, in: path, required: true, schema: { type: integer } }
+      responses: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let collection = spec.generate_postman_collection();
+        let paths = collection["item"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["request"]["url"]["path"].clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            [
+                json!(["files", "{name}.json"]),
+                json!(["posts", "{id}-{slug}", "comments", ":commentId"]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Removal of operations and unused components, e.g. before publishing a spec.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value as JsonValue;
+
+use super::{Operation, Ref, RefType, Spec};
+
+impl Spec {
+    /// Returns a copy of the spec without the operations tagged with `tag`.
+    ///
+    /// Path items left without operations are removed, as are components no longer referenced
+    /// (see [`remove_extension_marked_operations`](Self::remove_extension_marked_operations)).
+    pub fn remove_tagged_operations(&self, tag: &str) -> Spec {
+        self.remove_operations(|op| op.tags.iter().any(|t| t == tag))
+    }
+
+    /// Returns a copy of the spec without the operations whose extension `key` equals `value`,
+    /// e.g. `x-internal: true`.
+    ///
+    /// Path items left without operations are removed. Then, components that are not referenced
+    /// by the rest of the spec are removed; security schemes are always kept.
+    pub fn remove_extension_marked_operations(&self, key: &str, value: &JsonValue) -> Spec {
+        self.remove_operations(|op| op.extensions.get(key) == Some(value))
+    }
+
+    fn remove_operations(&self, mut remove: impl FnMut(&Operation) -> bool) -> Spec {
+        let mut spec = self.clone();
+
+        spec.paths.retain(|_, path_item| {
+            let had_operations = path_item.methods().into_iter().next().is_some();
+            path_item.retain_operations(|op| !remove(op));
+            !had_operations || path_item.methods().into_iter().next().is_some()
+        });
+
+        spec.prune_unused_components();
+        spec
+    }
+
+    /// Removes components that are not referenced, directly or through other components, from
+    /// outside the components object. Security schemes are kept since they are referenced by
+    /// name.
+    fn prune_unused_components(&mut self) {
+        let mut components = match self.components.take() {
+            Some(components) => components,
+            None => return,
+        };
+
+        let components_json = serde_json::to_value(&components).unwrap_or_default();
+
+        let mut pending = vec![];
+        collect_refs(
+            &serde_json::to_value(&*self).unwrap_or_default(),
+            &mut pending,
+        );
+
+        let mut used = BTreeSet::new();
+
+        while let Some(ref_path) = pending.pop() {
+            let r = match ref_path.parse::<Ref>() {
+                Ok(r) if r.source.is_empty() => r,
+                _ => continue,
+            };
+
+            let pointer = format!("/{}/{}", r.kind.component_path(), escape(&r.name));
+
+            if used.insert((r.kind.component_path(), r.name)) {
+                if let Some(component) = components_json.pointer(&pointer) {
+                    collect_refs(component, &mut pending);
+                }
+            }
+        }
+
+        fn retain<T>(
+            map: &mut BTreeMap<String, T>,
+            kind: RefType,
+            used: &BTreeSet<(&str, String)>,
+        ) {
+            map.retain(|name, _| used.contains(&(kind.component_path(), name.clone())));
+        }
+
+        retain(&mut components.schemas, RefType::Schema, &used);
+        retain(&mut components.responses, RefType::Response, &used);
+        retain(&mut components.parameters, RefType::Parameter, &used);
+        retain(&mut components.examples, RefType::Example, &used);
+        retain(&mut components.request_bodies, RefType::RequestBody, &used);
+        retain(&mut components.headers, RefType::Header, &used);
+        retain(&mut components.path_items, RefType::PathItem, &used);
+        retain(&mut components.links, RefType::Link, &used);
+        retain(&mut components.callbacks, RefType::Callback, &used);
+
+        self.components = Some(components);
+    }
+}
+
+/// Collects the values of all `$ref` keys in a JSON document, and the schemas targeted by
+/// discriminator mappings.
+fn collect_refs(val: &JsonValue, refs: &mut Vec<String>) {
+    match val {
+        JsonValue::Object(obj) => {
+            for (key, val) in obj {
+                match (key.as_str(), val) {
+                    ("$ref", JsonValue::String(ref_path)) => refs.push(ref_path.clone()),
+                    ("discriminator", JsonValue::Object(discriminator)) => {
+                        let mapping = discriminator.get("mapping").and_then(JsonValue::as_object);
+
+                        for target in mapping
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|(_, target)| target.as_str())
+                        {
+                            // mapping values are either references or schema names
+                            match target.parse::<Ref>() {
+                                Ok(_) => refs.push(target.to_owned()),
+                                Err(_) => refs.push(format!("#/components/schemas/{}", target)),
+                            }
+                        }
+
+                        collect_refs(val, refs);
+                    }
+                    _ => collect_refs(val, refs),
+                }
+            }
+        }
+        JsonValue::Array(vals) => {
+            for val in vals {
+                collect_refs(val, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    const SPEC: &str = r#"openapi: "3.1.0"
+info: { title: Internal, version: "1" }
+paths:
+  /pets:
+    get:
+      responses:
+        "200":
+          description: Pets.
+          content:
+            application/json:
+              schema: { $ref: '#/components/schemas/Pet' }
+    put:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              oneOf:
+              - { $ref: '#/components/schemas/Dog' }
+              - { $ref: '#/components/schemas/Cat' }
+              discriminator:
+                propertyName: kind
+                mapping:
+                  dog: '#/components/schemas/Dog'
+                  cat: Cat
+                  bird: '#/components/schemas/Bird'
+                  fish: Fish
+      responses: {}
+    delete:
+      tags: [internal]
+      x-internal: true
+      parameters:
+      - $ref: '#/components/parameters/Force'
+      responses: {}
+  /admin:
+    post:
+      tags: [internal]
+      x-internal: true
+      requestBody:
+        $ref: '#/components/requestBodies/Admin'
+      responses: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        owner: { $ref: '#/components/schemas/Owner' }
+    Owner: { type: string }
+    AdminAction: { type: string }
+    Dog: { type: object }
+    Cat: { type: object }
+    Bird: { type: object }
+    Fish: { type: object }
+  parameters:
+    Force: { name: force, in: query, schema: { type: boolean } }
+  requestBodies:
+    Admin:
+      content:
+        application/json:
+          schema: { $ref: '#/components/schemas/AdminAction' }
+  securitySchemes:
+    key: { type: apiKey, name: key, in: header }
+"#;
+
+    #[test]
+    fn removes_operations_and_unused_components() {
+        let spec = crate::from_reader(SPEC.as_bytes()).unwrap();
+
+        let by_tag = spec.remove_tagged_operations("internal");
+        let by_extension = spec.remove_extension_marked_operations("x-internal", &json!(true));
+        assert_eq!(by_tag, by_extension);
+
+        let public = by_tag;
+        assert_eq!(public.paths.keys().collect::<Vec<_>>(), ["/pets"]);
+        assert!(public.paths["/pets"].get.is_some());
+        assert!(public.paths["/pets"].delete.is_none());
+
+        let components = public.components.unwrap();
+        assert_eq!(
+            components.schemas.keys().collect::<Vec<_>>(),
+            ["Bird", "Cat", "Dog", "Fish", "Owner", "Pet"]
+        );
+        assert!(components.parameters.is_empty());
+        assert!(components.request_bodies.is_empty());
+        assert_eq!(components.security_schemes.len(), 1);
+
+        // the original spec is untouched
+        assert_eq!(spec.paths.len(), 2);
+        assert_eq!(
+            spec.paths["/admin"].post.as_ref().unwrap().extensions["x-internal"],
+            json!(true)
+        );
+    }
+}
@@ -0,0 +1,370 @@
+//! Detection of redundant component schemas.
+
+use std::collections::BTreeMap;
+
+use derive_more::Display;
+
+use super::{ObjectOrReference, Ref, RefType, Schema, SchemaOrBool, SchemaType, Spec};
+
+/// A pair of component schemas found by [`Spec::validate_no_redundant_schemas`].
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum RedundantSchemaWarning {
+    /// The schemas have the same structure, so they could be merged.
+    #[display(fmt = "schemas `{}` and `{}` are identical", name1, name2)]
+    Identical { name1: String, name2: String },
+
+    /// Every value of `subset` is also a value of `superset`, suggesting that `subset` should
+    /// extend `superset` through `allOf`.
+    #[display(fmt = "schema `{}` is a subset of `{}`", subset, superset)]
+    Subset { superset: String, subset: String },
+}
+
+impl Spec {
+    /// Reports pairs of component schemas that are identical, ignoring metadata, or where one
+    /// accepts a strict subset of the values of the other, as told by [`Schema::is_superset_of`].
+    ///
+    /// Subsets are only reported for supersets declaring properties, and not for schemas that
+    /// already extend the superset through `allOf`. Components that are references to other
+    /// schemas are skipped.
+    pub fn validate_no_redundant_schemas(&self) -> Vec<RedundantSchemaWarning> {
+        let schemas = self
+            .components
+            .iter()
+            .flat_map(|components| &components.schemas)
+            .filter_map(|(name, schema)| match schema {
+                ObjectOrReference::Object(schema) => Some((name, schema.clone_without_metadata())),
+                ObjectOrReference::Ref { .. } | ObjectOrReference::SchemaRef { .. } => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut warnings = vec![];
+
+        for (i, (name1, schema1)) in schemas.iter().enumerate() {
+            for (name2, schema2) in &schemas[i + 1..] {
+                let superset1 = schema1.is_superset_of(schema2, self);
+                let superset2 = schema2.is_superset_of(schema1, self);
+
+                let subset = |superset: &Schema, subset: &Schema, superset_name: &str| {
+                    !superset.properties.is_empty() && !extends(subset, superset_name)
+                };
+
+                if schema1 == schema2 || (superset1 && superset2) {
+                    warnings.push(RedundantSchemaWarning::Identical {
+                        name1: (*name1).clone(),
+                        name2: (*name2).clone(),
+                    });
+                } else if superset1 && subset(schema1, schema2, name1) {
+                    warnings.push(RedundantSchemaWarning::Subset {
+                        superset: (*name1).clone(),
+                        subset: (*name2).clone(),
+                    });
+                } else if superset2 && subset(schema2, schema1, name2) {
+                    warnings.push(RedundantSchemaWarning::Subset {
+                        superset: (*name2).clone(),
+                        subset: (*name1).clone(),
+                    });
+                }
+            }
+        }
+
+        warnings
+    }
+}
+
+impl Schema {
+    /// Returns true if every value valid against `other` is also valid against this schema.
+    ///
+    /// The comparison is structural and conservative. Metadata is ignored and `allOf` branches
+    /// are merged, but keywords without a subset relation, such as `oneOf` or `not`, must be
+    /// equal in both schemas unless absent from this one. References are resolved against
+    /// `spec`; unresolvable references only match equal ones.
+    pub fn is_superset_of(&self, other: &Schema, spec: &Spec) -> bool {
+        is_superset(self, other, spec, &mut vec![])
+    }
+}
+
+/// Returns true if the schema extends the named component schema through `allOf`.
+fn extends(schema: &Schema, name: &str) -> bool {
+    schema.all_of.iter().any(|branch| {
+        branch
+            .ref_path()
+            .and_then(|ref_path| ref_path.parse::<Ref>().ok())
+            .map_or(false, |r| r.kind == RefType::Schema && r.name == name)
+    })
+}
+
+/// `visiting` holds the pairs of references being compared, either of which may be an inline
+/// schema; pairs met again are assumed to be supersets, so that recursive schemas terminate.
+fn is_superset(
+    sup: &Schema,
+    sub: &Schema,
+    spec: &Spec,
+    visiting: &mut Vec<(Option<String>, Option<String>)>,
+) -> bool {
+    let normalize = |schema: &Schema| {
+        schema
+            .merged_all_of(spec)
+            .unwrap_or_else(|| schema.clone())
+            .clone_without_metadata()
+    };
+
+    let (sup, sub) = (normalize(sup), normalize(sub));
+
+    if sup.is_empty() || sup == sub {
+        return true;
+    }
+
+    let type_ok = match (sup.schema_type, sub.schema_type) {
+        (None, _) => true,
+        (Some(SchemaType::Number), Some(SchemaType::Integer)) => true,
+        (sup_type, sub_type) => sup_type == sub_type,
+    };
+
+    let enum_ok = sup.enum_values.is_empty()
+        || (!sub.enum_values.is_empty()
+            && sub
+                .enum_values
+                .iter()
+                .all(|value| sup.enum_values.contains(value)));
+
+    let ((sup_min, sup_exclusive_min), (sub_min, sub_exclusive_min)) =
+        (sup.minimum_bounds(), sub.minimum_bounds());
+    let ((sup_max, sup_exclusive_max), (sub_max, sub_exclusive_max)) =
+        (sup.maximum_bounds(), sub.maximum_bounds());
+
+    let bounds_ok = at_least(sup_min, sub_min)
+        && at_least(sup_exclusive_min, sub_exclusive_min)
+        && at_most(sup_max, sub_max)
+        && at_most(sup_exclusive_max, sub_exclusive_max)
+        && at_least(sup.min_length, sub.min_length)
+        && at_most(sup.max_length, sub.max_length)
+        && at_least(sup.min_items, sub.min_items)
+        && at_most(sup.max_items, sub.max_items)
+        && at_least(sup.min_properties, sub.min_properties)
+        && at_most(sup.max_properties, sub.max_properties);
+
+    let required_ok = sup
+        .required
+        .iter()
+        .all(|field| sub.required.contains(field));
+
+    let additional_ok = match sup.additional_properties.as_deref() {
+        None | Some(ObjectOrReference::Object(SchemaOrBool::Bool(true))) => true,
+        additional => {
+            sub.additional_properties.as_deref() == additional
+                && sub
+                    .properties
+                    .keys()
+                    .all(|name| sup.properties.contains_key(name))
+        }
+    };
+
+    let rest_ok = {
+        let rest = without_compared_keywords(&sup);
+        rest == Schema::default() || rest == without_compared_keywords(&sub)
+    };
+
+    if !(type_ok && enum_ok && bounds_ok && required_ok && additional_ok && rest_ok) {
+        return false;
+    }
+
+    let properties_ok = sup.properties.iter().all(|(name, sup_prop)| {
+        match sub.properties.get(name) {
+            Some(sub_prop) => is_superset_ref(sup_prop, sub_prop, spec, visiting),
+            // values of `sub` may have any value for the property
+            None => sup_prop.resolve(spec).map_or(false, |prop| prop.is_empty()),
+        }
+    });
+
+    let items_ok = match (&sup.items, &sub.items) {
+        (None, _) => true,
+        (Some(sup_items), Some(sub_items)) => is_superset_ref(sup_items, sub_items, spec, visiting),
+        (Some(_), None) => false,
+    };
+
+    properties_ok && items_ok
+}
+
+fn is_superset_ref(
+    sup: &ObjectOrReference<Schema>,
+    sub: &ObjectOrReference<Schema>,
+    spec: &Spec,
+    visiting: &mut Vec<(Option<String>, Option<String>)>,
+) -> bool {
+    if let (
+        ObjectOrReference::Ref { ref_path: sup_path },
+        ObjectOrReference::Ref { ref_path: sub_path },
+    ) = (sup, sub)
+    {
+        if sup_path == sub_path {
+            return true;
+        }
+    }
+
+    let pair = (
+        sup.ref_path().map(str::to_owned),
+        sub.ref_path().map(str::to_owned),
+    );
+
+    if pair != (None, None) && visiting.contains(&pair) {
+        return true;
+    }
+
+    let (sup, sub) = match (sup.resolve(spec), sub.resolve(spec)) {
+        (Ok(sup), Ok(sub)) => (sup, sub),
+        _ => return false,
+    };
+
+    visiting.push(pair);
+    let result = is_superset(&sup, &sub, spec, visiting);
+    visiting.pop();
+    result
+}
+
+/// Clears the keywords compared individually by [`is_superset`].
+fn without_compared_keywords(schema: &Schema) -> Schema {
+    Schema {
+        schema_type: None,
+        enum_values: vec![],
+        minimum: None,
+        exclusive_minimum: None,
+        maximum: None,
+        exclusive_maximum: None,
+        min_length: None,
+        max_length: None,
+        min_items: None,
+        max_items: None,
+        min_properties: None,
+        max_properties: None,
+        required: vec![],
+        properties: BTreeMap::new(),
+        additional_properties: None,
+        items: None,
+        ..schema.clone()
+    }
+}
+
+/// Returns true if the lower bound `sub` is at least as strict as `sup`.
+fn at_least<T: PartialOrd>(sup: Option<T>, sub: Option<T>) -> bool {
+    match (sup, sub) {
+        (None, _) => true,
+        (Some(sup), Some(sub)) => sub >= sup,
+        (Some(_), None) => false,
+    }
+}
+
+/// Returns true if the upper bound `sub` is at least as strict as `sup`.
+fn at_most<T: PartialOrd>(sup: Option<T>, sub: Option<T>) -> bool {
+    match (sup, sub) {
+        (None, _) => true,
+        (Some(sup), Some(sub)) => sub <= sup,
+        (Some(_), None) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redundant_schemas() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  schemas:
+    Pet:
+      title: Pet
+      type: object
+      required: [name]
+      properties: { name: { type: string } }
+    Animal:
+      description: An animal.
+      type: object
+      required: [name]
+      properties: { name: { type: string, description: Name of the animal. } }
+    NamedPet:
+      type: object
+      required: [name, age]
+      properties: { name: { type: string, minLength: 1 }, age: { type: integer } }
+    Dog:
+      allOf:
+      - $ref: '#/components/schemas/Pet'
+      - properties: { bark: { type: boolean } }
+    Closed:
+      type: object
+      properties: { name: { type: string } }
+      additionalProperties: false
+    Id: { type: string }
+    Email: { type: string, format: email }
+    Tree:
+      type: object
+      properties:
+        children: { type: array, items: { $ref: '#/components/schemas/Tree' } }
+    Forest:
+      type: object
+      required: [children]
+      properties:
+        children: { type: array, items: { $ref: '#/components/schemas/Forest' } }
+    Chain:
+      type: object
+      properties:
+        next: { $ref: '#/components/schemas/Chain', description: The next link. }
+    List:
+      type: object
+      required: [next]
+      properties:
+        next: { $ref: '#/components/schemas/List', description: The next node. }
+    PetAlias: { $ref: '#/components/schemas/Pet' }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let subset = |superset: &str, subset: &str| RedundantSchemaWarning::Subset {
+            superset: superset.to_owned(),
+            subset: subset.to_owned(),
+        };
+
+        let warnings = spec.validate_no_redundant_schemas();
+        assert_eq!(
+            warnings,
+            [
+                subset("Animal", "Dog"),
+                subset("Animal", "NamedPet"),
+                RedundantSchemaWarning::Identical {
+                    name1: "Animal".to_owned(),
+                    name2: "Pet".to_owned(),
+                },
+                subset("Chain", "List"),
+                subset("Tree", "Forest"),
+                subset("Pet", "NamedPet"),
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "schema `Dog` is a subset of `Animal`"
+        );
+        assert_eq!(
+            warnings[2].to_string(),
+            "schemas `Animal` and `Pet` are identical"
+        );
+
+        let schema = |name: &str| {
+            spec.components.as_ref().unwrap().schemas[name]
+                .resolve(&spec)
+                .unwrap()
+        };
+
+        assert!(schema("Id").is_superset_of(&schema("Email"), &spec));
+        assert!(!schema("Email").is_superset_of(&schema("Id"), &spec));
+        assert!(schema("Pet").is_superset_of(&schema("Dog"), &spec));
+        assert!(!schema("Closed").is_superset_of(&schema("Pet"), &spec));
+        assert!(Schema::default().is_superset_of(&schema("Tree"), &spec));
+        assert_eq!(
+            schema("Animal").clone_without_metadata(),
+            schema("Pet").clone_without_metadata()
+        );
+    }
+}
@@ -1,10 +1,10 @@
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 use derive_more::{Display, Error};
 use log::trace;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use super::Spec;
 
@@ -12,16 +12,49 @@ static RE_REF: Lazy<Regex> = Lazy::new(|| {
     Regex::new("^(?P<source>[^#]*)#/components/(?P<type>[^/]+)/(?P<name>.+)$").unwrap()
 });
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+static RE_MODULE_PATH: Lazy<Regex> = Lazy::new(|| Regex::new("[a-z_][a-z0-9_]*::").unwrap());
+
+/// Either an inline object or a reference to one.
+///
+/// References (de)serialize as `{"$ref": "..."}`; objects use the representation of `T`.
+///
+/// A `$ref` with sibling keywords, as OpenAPI 3.1 allows in schemas, deserializes as a
+/// [`SchemaRef`](Self::SchemaRef) if the siblings form a valid `T`. Otherwise, as for a parameter
+/// reference with only a `description`, the siblings are ignored as in OpenAPI 3.0.
+///
+/// Displays as `$ref: '<path>'` for references, with a `with overrides` suffix if they have
+/// sibling keywords, and `inline <type name>` for objects.
+#[derive(Clone, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum ObjectOrReference<T> {
     Ref {
         #[serde(rename = "$ref")]
         ref_path: String,
     },
+
+    /// A reference with sibling keywords, which apply alongside the referenced object. See
+    /// [`FromRef::with_overrides`].
+    SchemaRef {
+        #[serde(rename = "$ref")]
+        ref_path: String,
+
+        #[serde(flatten)]
+        overrides: T,
+    },
+
     Object(T),
 }
 
+impl<T> ObjectOrReference<T> {
+    /// Returns the reference path of a [`Ref`](Self::Ref) or [`SchemaRef`](Self::SchemaRef).
+    pub fn ref_path(&self) -> Option<&str> {
+        match self {
+            Self::Ref { ref_path } | Self::SchemaRef { ref_path, .. } => Some(ref_path),
+            Self::Object(_) => None,
+        }
+    }
+}
+
 impl<T> ObjectOrReference<T>
 where
     T: FromRef,
@@ -30,21 +63,127 @@ where
         match self {
             Self::Object(component) => Ok(component.clone()),
             Self::Ref { ref_path } => T::from_ref(spec, ref_path),
+            Self::SchemaRef {
+                ref_path,
+                overrides,
+            } => Ok(T::from_ref(spec, ref_path)?.with_overrides(overrides)),
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ObjectOrReference<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct BareRef {
+            #[serde(rename = "$ref")]
+            ref_path: String,
+        }
+
+        #[derive(Deserialize)]
+        struct RefWithSiblings<T> {
+            #[serde(rename = "$ref")]
+            ref_path: String,
+
+            #[serde(flatten)]
+            overrides: T,
+        }
+
+        #[derive(Deserialize)]
+        struct RefIgnoringSiblings {
+            #[serde(rename = "$ref")]
+            ref_path: String,
+        }
+
+        // tried in order, so that siblings are only ignored if they do not form a `T`
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr<T> {
+            Ref(BareRef),
+            SchemaRef(RefWithSiblings<T>),
+            Object(T),
+            RefIgnoringSiblings(RefIgnoringSiblings),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Ref(BareRef { ref_path })
+            | Repr::RefIgnoringSiblings(RefIgnoringSiblings { ref_path }) => Self::Ref { ref_path },
+            Repr::SchemaRef(RefWithSiblings {
+                ref_path,
+                overrides,
+            }) => Self::SchemaRef {
+                ref_path,
+                overrides,
+            },
+            Repr::Object(object) => Self::Object(object),
+        })
+    }
+}
+
+impl<T> fmt::Display for ObjectOrReference<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ref { ref_path } => write!(f, "$ref: '{}'", ref_path),
+            Self::SchemaRef { ref_path, .. } => write!(f, "$ref: '{}' with overrides", ref_path),
+            Self::Object(_) => {
+                // strip module paths, including those of generic arguments
+                let type_name = RE_MODULE_PATH.replace_all(std::any::type_name::<T>(), "");
+                write!(f, "inline {}", type_name)
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ObjectOrReference<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ref { ref_path } => f
+                .debug_struct("ObjectOrReference::Ref")
+                .field("ref_path", ref_path)
+                .finish(),
+            Self::SchemaRef {
+                ref_path,
+                overrides,
+            } => f
+                .debug_struct("ObjectOrReference::SchemaRef")
+                .field("ref_path", ref_path)
+                .field("overrides", overrides)
+                .finish(),
+            Self::Object(object) => f
+                .debug_tuple("ObjectOrReference::Object")
+                .field(object)
+                .finish(),
         }
     }
 }
 
+impl<T> From<T> for ObjectOrReference<T> {
+    fn from(object: T) -> Self {
+        Self::Object(object)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Display, Error)]
 pub enum RefError {
     #[display(fmt = "Invalid type: {}", _0)]
     InvalidType(#[error(not(source))] String),
 
-    #[display(fmt = "Mismatched type: cannot reference a {} as a {}", _0, _1)]
+    #[display(
+        fmt = "Mismatched type: expected a reference to a {}, found a reference to a {}",
+        _1,
+        _0
+    )]
     MismatchedType(RefType, RefType),
 
     // TODO: use some kind of path structure
     #[display(fmt = "Unresolvable path: {}", _0)]
     Unresolvable(#[error(not(source))] String),
+
+    #[display(fmt = "Circular reference: {}", _0)]
+    Circular(#[error(not(source))] String),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Display)]
@@ -58,6 +197,7 @@ pub enum RefType {
     SecurityScheme,
     Link,
     Callback,
+    PathItem,
 }
 
 impl FromStr for RefType {
@@ -74,34 +214,236 @@ impl FromStr for RefType {
             "securitySchemes" => Self::SecurityScheme,
             "links" => Self::Link,
             "callbacks" => Self::Callback,
+            "pathItems" => Self::PathItem,
             typ => return Err(RefError::InvalidType(typ.to_owned())),
         })
     }
 }
 
-#[derive(Debug, Clone)]
+impl RefType {
+    /// Returns the key of this kind of component in the Components Object, e.g.
+    /// `securitySchemes`.
+    pub fn component_path(&self) -> &'static str {
+        match self {
+            Self::Schema => "schemas",
+            Self::Response => "responses",
+            Self::Parameter => "parameters",
+            Self::Example => "examples",
+            Self::RequestBody => "requestBodies",
+            Self::Header => "headers",
+            Self::SecurityScheme => "securitySchemes",
+            Self::Link => "links",
+            Self::Callback => "callbacks",
+            Self::PathItem => "pathItems",
+        }
+    }
+}
+
+/// A parsed reference to a component, e.g. `#/components/schemas/Pet`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Ref {
+    /// Document containing the component; empty for the current document.
     pub source: String,
     pub kind: RefType,
     pub name: String,
 }
 
+impl Ref {
+    /// Creates a reference to a component in the current document.
+    pub fn new(kind: RefType, name: &str) -> Self {
+        Self {
+            source: String::new(),
+            kind,
+            name: name.to_owned(),
+        }
+    }
+
+    pub fn kind(&self) -> RefType {
+        self.kind
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Formats the location of the component within its document as a
+    /// [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901), e.g. `/components/schemas/Pet`.
+    pub fn to_json_pointer(&self) -> String {
+        format!(
+            "/components/{}/{}",
+            self.kind.component_path(),
+            self.name.replace('~', "~0").replace('/', "~1")
+        )
+    }
+}
+
+impl fmt::Display for Ref {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.source, self.to_json_pointer())
+    }
+}
+
 impl FromStr for Ref {
     type Err = RefError;
 
     fn from_str(path: &str) -> Result<Self, Self::Err> {
-        let parts = RE_REF.captures(path).unwrap();
+        let parts = RE_REF
+            .captures(path)
+            .ok_or_else(|| RefError::Unresolvable(path.to_owned()))?;
 
         trace!("creating Ref: {}/{}", &parts["type"], &parts["name"]);
 
         Ok(Self {
             source: parts["source"].to_owned(),
             kind: parts["type"].parse()?,
-            name: parts["name"].to_owned(),
+            name: parts["name"].replace("~1", "/").replace("~0", "~"),
         })
     }
 }
 
+/// Components that can be looked up by reference.
+///
+/// Every component type implements this with the same signature, so generic code can resolve
+/// references with `T: FromRef`.
 pub trait FromRef: Clone {
     fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError>;
+
+    /// Applies the sibling keywords of an [`ObjectOrReference::SchemaRef`] to the referenced
+    /// object. Only schemas combine them; other components are returned unchanged by default.
+    fn with_overrides(self, _overrides: &Self) -> Self {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::spec::Schema;
+
+    #[test]
+    fn object_or_reference_serde() {
+        let oor = ObjectOrReference::<Vec<u8>>::Ref {
+            ref_path: "#/components/schemas/Bytes".to_owned(),
+        };
+        let json = serde_json::to_value(&oor).unwrap();
+        assert_eq!(json, json!({ "$ref": "#/components/schemas/Bytes" }));
+        assert_eq!(
+            serde_json::from_value::<ObjectOrReference<_>>(json).unwrap(),
+            oor
+        );
+
+        let oor = ObjectOrReference::Object(vec![1_u8, 2]);
+        let json = serde_json::to_value(&oor).unwrap();
+        assert_eq!(json, json!([1, 2]));
+        assert_eq!(
+            serde_json::from_value::<ObjectOrReference<_>>(json).unwrap(),
+            oor
+        );
+    }
+
+    #[test]
+    fn ref_round_trip() {
+        let r = Ref::new(RefType::RequestBody, "a/b");
+        assert_eq!(r.kind(), RefType::RequestBody);
+        assert_eq!(r.name(), "a/b");
+        assert_eq!(r.to_json_pointer(), "/components/requestBodies/a~1b");
+        assert_eq!(r.to_string(), "#/components/requestBodies/a~1b");
+        assert_eq!(r.to_string().parse::<Ref>().unwrap(), r);
+
+        let r = "other.yml#/components/schemas/Pet".parse::<Ref>().unwrap();
+        assert_eq!(r.source, "other.yml");
+        assert_eq!(r.to_string(), "other.yml#/components/schemas/Pet");
+
+        assert!("#/definitions/Pet".parse::<Ref>().is_err());
+    }
+
+    #[test]
+    fn ref_types() {
+        let kinds = [
+            RefType::Schema,
+            RefType::Response,
+            RefType::Parameter,
+            RefType::Example,
+            RefType::RequestBody,
+            RefType::Header,
+            RefType::SecurityScheme,
+            RefType::Link,
+            RefType::Callback,
+            RefType::PathItem,
+        ];
+
+        for kind in kinds {
+            assert_eq!(kind.component_path().parse::<RefType>().unwrap(), kind);
+        }
+
+        assert_eq!(
+            RefError::MismatchedType(RefType::Parameter, RefType::Schema).to_string(),
+            "Mismatched type: expected a reference to a Schema, found a reference to a Parameter"
+        );
+    }
+
+    #[test]
+    fn from_ref_is_generic() {
+        use crate::spec::{Example, Header, Parameter, Response, Schema};
+
+        fn resolve<T: FromRef>(spec: &Spec, name: &str) -> Result<T, RefError> {
+            ObjectOrReference::<T>::Ref {
+                ref_path: name.to_owned(),
+            }
+            .resolve(spec)
+        }
+
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  schemas:
+    Pet: { type: object }
+  responses:
+    NotFound: { description: Not found. }
+  parameters:
+    limit: { name: limit, in: query }
+  examples:
+    cat: { value: Tom }
+  headers:
+    RateLimit: { schema: { type: integer } }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert!(resolve::<Schema>(&spec, "#/components/schemas/Pet").is_ok());
+        assert!(resolve::<Response>(&spec, "#/components/responses/NotFound").is_ok());
+        assert!(resolve::<Parameter>(&spec, "#/components/parameters/limit").is_ok());
+        assert!(resolve::<Example>(&spec, "#/components/examples/cat").is_ok());
+        assert!(resolve::<Header>(&spec, "#/components/headers/RateLimit").is_ok());
+
+        assert_eq!(
+            resolve::<Example>(&spec, "#/components/examples/dog").unwrap_err(),
+            RefError::Unresolvable("#/components/examples/dog".to_owned())
+        );
+    }
+
+    #[test]
+    fn object_or_reference_formatting() {
+        let oor = ObjectOrReference::<Schema>::Ref {
+            ref_path: "#/components/schemas/Pet".to_owned(),
+        };
+        assert_eq!(oor.to_string(), "$ref: '#/components/schemas/Pet'");
+        assert_eq!(
+            format!("{:?}", oor),
+            r##"ObjectOrReference::Ref { ref_path: "#/components/schemas/Pet" }"##
+        );
+
+        let oor = ObjectOrReference::Object(Schema::default());
+        assert_eq!(oor.to_string(), "inline Schema");
+        assert!(format!("{:?}", oor).starts_with("ObjectOrReference::Object(Schema {"));
+
+        let oor = ObjectOrReference::Object(vec![1_u8, 2]);
+        assert_eq!(oor.to_string(), "inline Vec<u8>");
+        assert_eq!(format!("{:?}", oor), "ObjectOrReference::Object([1, 2])");
+    }
 }
@@ -0,0 +1,184 @@
+//! Strategies for resolving schema `$ref`s.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use super::{FromRef, ObjectOrReference, RefError, Schema, Spec};
+
+/// Resolves schema references.
+///
+/// Implementations decouple reference resolution from [`Spec`], allowing schemas to reference
+/// other documents, and validators to be tested with mock resolvers.
+pub trait RefResolver {
+    fn resolve_schema(&self, path: &str) -> Result<Schema, RefError>;
+}
+
+impl<R: RefResolver + ?Sized> RefResolver for &R {
+    fn resolve_schema(&self, path: &str) -> Result<Schema, RefError> {
+        (**self).resolve_schema(path)
+    }
+}
+
+/// Resolves references to the `components` of a spec.
+#[derive(Debug, Clone, Copy)]
+pub struct SpecRefResolver<'a> {
+    spec: &'a Spec,
+}
+
+impl<'a> SpecRefResolver<'a> {
+    pub fn new(spec: &'a Spec) -> Self {
+        Self { spec }
+    }
+}
+
+impl RefResolver for SpecRefResolver<'_> {
+    fn resolve_schema(&self, path: &str) -> Result<Schema, RefError> {
+        Schema::from_ref(self.spec, path)
+    }
+}
+
+/// Resolves references to schemas in YAML or JSON files, e.g. `schemas/pet.yml` or
+/// `common.yml#/components/schemas/Pet`.
+///
+/// Relative file paths are resolved against a base directory. References within the loaded
+/// schema are returned as-is.
+#[derive(Debug, Clone)]
+pub struct FileRefResolver {
+    base_dir: PathBuf,
+}
+
+impl FileRefResolver {
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+        }
+    }
+
+    fn load(&self, file: &Path, pointer: &str) -> Option<Schema> {
+        let file = File::open(self.base_dir.join(file)).ok()?;
+        let doc = serde_yaml::from_reader::<_, serde_json::Value>(file).ok()?;
+        let value = doc.pointer(pointer)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+impl RefResolver for FileRefResolver {
+    fn resolve_schema(&self, path: &str) -> Result<Schema, RefError> {
+        let (file, pointer) = path.split_once('#').unwrap_or((path, ""));
+
+        if file.is_empty() {
+            return Err(RefError::Unresolvable(path.to_owned()));
+        }
+
+        self.load(Path::new(file), pointer)
+            .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
+    }
+}
+
+/// Wraps a resolver, caching the schemas it resolves.
+#[derive(Debug)]
+pub struct CachingRefResolver<R> {
+    inner: R,
+    cache: RefCell<HashMap<String, Schema>>,
+}
+
+impl<R: RefResolver> CachingRefResolver<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: RefResolver> RefResolver for CachingRefResolver<R> {
+    fn resolve_schema(&self, path: &str) -> Result<Schema, RefError> {
+        if let Some(schema) = self.cache.borrow().get(path) {
+            return Ok(schema.clone());
+        }
+
+        let schema = self.inner.resolve_schema(path)?;
+        self.cache
+            .borrow_mut()
+            .insert(path.to_owned(), schema.clone());
+
+        Ok(schema)
+    }
+}
+
+impl ObjectOrReference<Schema> {
+    /// Resolves the schema using a custom [`RefResolver`].
+    pub fn resolve_with(&self, resolver: &dyn RefResolver) -> Result<Schema, RefError> {
+        match self {
+            Self::Object(schema) => Ok(schema.clone()),
+            Self::Ref { ref_path } => resolver.resolve_schema(ref_path),
+            Self::SchemaRef {
+                ref_path,
+                overrides,
+            } => Ok(resolver.resolve_schema(ref_path)?.with_overrides(overrides)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct CountingResolver {
+        calls: Cell<usize>,
+    }
+
+    impl RefResolver for CountingResolver {
+        fn resolve_schema(&self, path: &str) -> Result<Schema, RefError> {
+            self.calls.set(self.calls.get() + 1);
+
+            Ok(Schema {
+                title: Some(path.to_owned()),
+                ..Schema::default()
+            })
+        }
+    }
+
+    #[test]
+    fn caching_resolver_resolves_once() {
+        let resolver = CachingRefResolver::new(CountingResolver::default());
+        let oor = ObjectOrReference::<Schema>::Ref {
+            ref_path: "#/components/schemas/Pet".to_owned(),
+        };
+
+        for _ in 0..3 {
+            let schema = oor.resolve_with(&resolver).unwrap();
+            assert_eq!(schema.title.as_deref(), Some("#/components/schemas/Pet"));
+        }
+
+        assert_eq!(resolver.into_inner().calls.get(), 1);
+    }
+
+    #[test]
+    fn file_resolver_loads_schemas() {
+        let resolver = FileRefResolver::new("data/oas-samples");
+
+        let schema = resolver
+            .resolve_schema("pet-store.yml#/components/schemas/Pet")
+            .unwrap();
+        assert!(schema.required.contains(&"name".to_owned()));
+
+        resolver
+            .resolve_schema("pet-store.yml#/components/schemas/Nope")
+            .unwrap_err();
+        resolver
+            .resolve_schema("#/components/schemas/Pet")
+            .unwrap_err();
+    }
+}
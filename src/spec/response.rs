@@ -1,8 +1,16 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
+use derive_more::{Display, Error};
+use http::Method;
 use serde::{Deserialize, Serialize};
 
-use super::{FromRef, Header, Link, MediaType, ObjectOrReference, Ref, RefError, RefType, Spec};
+use super::{
+    FromRef, Header, Link, MediaType, ObjectOrReference, OperationRef, Ref, RefError, RefType,
+    Schema, Spec,
+};
+
+/// Media types of response bodies that are not described by schemas worth generating types for.
+const UNTYPED_MEDIA_TYPES: &[&str] = &["text/plain", "application/octet-stream"];
 
 /// Describes a single response from an API Operation, including design-time, static `links`
 /// to operations based on the response.
@@ -55,3 +63,286 @@ impl FromRef for Response {
         }
     }
 }
+
+/// The way in which the responses of an operation are incomplete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum MissingResponseKind {
+    /// The operation does not declare any responses.
+    #[display(fmt = "no responses")]
+    NoResponses,
+
+    /// The operation declares neither a `default` nor a `4XX` response.
+    #[display(fmt = "no default or 4XX response")]
+    NoErrorResponse,
+
+    /// The operation declares a `default` response but no `2XX` response.
+    #[display(fmt = "no success response")]
+    NoSuccessResponse,
+}
+
+/// An operation with incomplete responses.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+#[display(fmt = "{} {} has {}", method, path, kind)]
+pub struct MissingResponseError {
+    pub path: String,
+
+    pub method: Method,
+
+    pub kind: MissingResponseKind,
+}
+
+impl Spec {
+    /// Checks that each operation declares responses covering both success and failure.
+    ///
+    /// Status code ranges (e.g., `2XX`) count as their status codes. An operation without any
+    /// responses is only reported as [`MissingResponseKind::NoResponses`].
+    pub fn check_response_completeness(&self) -> Vec<MissingResponseError> {
+        let mut errors = vec![];
+
+        for op in self.operation_refs() {
+            let responses = &op.operation.responses;
+
+            let mut push = |kind| {
+                errors.push(MissingResponseError {
+                    path: op.path.to_owned(),
+                    method: op.method.clone(),
+                    kind,
+                })
+            };
+
+            if responses.is_empty() {
+                push(MissingResponseKind::NoResponses);
+                continue;
+            }
+
+            let has_default = responses.contains_key("default");
+            let has_status_class = |class| responses.keys().any(|code| code.starts_with(class));
+
+            if !has_default && !has_status_class('4') {
+                push(MissingResponseKind::NoErrorResponse);
+            }
+
+            if has_default && !has_status_class('2') {
+                push(MissingResponseKind::NoSuccessResponse);
+            }
+        }
+
+        errors
+    }
+
+    /// Returns the response keys (e.g., `200`, `2XX` or `default`) declared by any operation.
+    pub fn all_response_codes(&self) -> BTreeSet<String> {
+        self.operation_refs()
+            .flat_map(|op| op.operation.responses.keys().cloned())
+            .collect()
+    }
+
+    /// Returns the operations declaring a response under the given key.
+    ///
+    /// Keys are compared case-insensitively, so `2xx` finds `2XX` responses, but a status code
+    /// does not match the range containing it.
+    pub fn operations_by_response_code(&self, code: &str) -> Vec<OperationRef<'_>> {
+        self.operation_refs()
+            .filter(|op| {
+                op.operation
+                    .responses
+                    .keys()
+                    .any(|key| key.eq_ignore_ascii_case(code))
+            })
+            .collect()
+    }
+
+    /// Returns the resolved response body schemas of all operations, keyed by operation ID and
+    /// response key joined with an underscore (e.g., `getPet_200` or `getPet_default`).
+    ///
+    /// Each response contributes the schema of its first media type, in alphabetical order, other
+    /// than `text/plain` and `application/octet-stream`. Operations without an ID, responses
+    /// without such a schema, and unresolvable responses or schemas are skipped.
+    pub fn build_response_type_map(&self) -> BTreeMap<String, Schema> {
+        let mut types = BTreeMap::new();
+
+        for op in self.operation_refs() {
+            let operation_id = match &op.operation.operation_id {
+                Some(operation_id) => operation_id,
+                None => continue,
+            };
+
+            for (code, response) in &op.operation.responses {
+                let response = match response.resolve(self) {
+                    Ok(response) => response,
+                    Err(_) => continue,
+                };
+
+                let schema = response
+                    .content
+                    .iter()
+                    .filter(|(media_type, _)| !is_untyped_media_type(media_type))
+                    .find_map(|(_, media_type)| media_type.schema.as_ref())
+                    .and_then(|schema| schema.resolve(self).ok());
+
+                if let Some(schema) = schema {
+                    types.insert(format!("{}_{}", operation_id, code), schema);
+                }
+            }
+        }
+
+        types
+    }
+}
+
+/// Returns true for media types listed in [`UNTYPED_MEDIA_TYPES`], ignoring case and parameters.
+fn is_untyped_media_type(media_type: &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+
+    UNTYPED_MEDIA_TYPES
+        .iter()
+        .any(|untyped| untyped.eq_ignore_ascii_case(essence))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn response_completeness() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets:
+    get:
+      responses:
+        "200": { description: OK }
+        "404": { description: Not found }
+    post:
+      responses:
+        "201": { description: Created }
+    put:
+      responses:
+        default: { description: Anything }
+    delete:
+      responses: {}
+  /stores:
+    get:
+      responses:
+        2XX: { description: OK }
+        default: { description: Error }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let errors = spec
+            .check_response_completeness()
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            [
+                "PUT /pets has no success response",
+                "POST /pets has no default or 4XX response",
+                "DELETE /pets has no responses",
+            ]
+        );
+    }
+
+    #[test]
+    fn response_codes() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets:
+    get:
+      responses:
+        "200": { description: OK }
+        "404": { description: Not found }
+    post:
+      responses:
+        "201": { description: Created }
+        4XX: { description: Client error }
+  /stores:
+    get:
+      responses:
+        "200": { description: OK }
+        default: { description: Error }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.all_response_codes().into_iter().collect::<Vec<_>>(),
+            ["200", "201", "404", "4XX", "default"]
+        );
+
+        let ops = |code| {
+            spec.operations_by_response_code(code)
+                .into_iter()
+                .map(|op| format!("{} {}", op.method, op.path))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ops("200"), ["GET /pets", "GET /stores"]);
+        assert_eq!(ops("4xx"), ["POST /pets"]);
+        assert_eq!(ops("default"), ["GET /stores"]);
+        assert!(ops("500").is_empty());
+    }
+
+    #[test]
+    fn response_type_map() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths:
+  /pets/{id}:
+    get:
+      operationId: getPet
+      responses:
+        "200":
+          content:
+            application/json: { schema: { $ref: '#/components/schemas/Pet' } }
+        "404": { $ref: '#/components/responses/NotFound' }
+        "204": { description: No content }
+        default:
+          content:
+            text/plain; charset=utf-8: { schema: { type: string } }
+    put:
+      responses:
+        "200":
+          content:
+            application/json: { schema: { $ref: '#/components/schemas/Pet' } }
+  /pets/{id}/photo:
+    get:
+      operationId: getPhoto
+      responses:
+        "200":
+          content:
+            application/octet-stream: { schema: { type: string, format: binary } }
+            image/png: { schema: { type: string, contentMediaType: image/png } }
+components:
+  schemas:
+    Pet: { type: object, title: Pet }
+    Error: { type: object, title: Error }
+  responses:
+    NotFound:
+      description: Not found
+      content:
+        application/json: { schema: { $ref: '#/components/schemas/Error' } }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let types = spec.build_response_type_map();
+        assert_eq!(
+            types.keys().map(String::as_str).collect::<Vec<_>>(),
+            ["getPet_200", "getPet_404", "getPhoto_200"]
+        );
+        assert_eq!(types["getPet_200"].title.as_deref(), Some("Pet"));
+        assert_eq!(types["getPet_404"].title.as_deref(), Some("Error"));
+        assert_eq!(
+            types["getPhoto_200"].content_media_type.as_deref(),
+            Some("image/png")
+        );
+    }
+}
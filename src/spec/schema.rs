@@ -1,11 +1,25 @@
 //! Schema specification for [OpenAPI 3.1.0](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md)
 
-use std::collections::BTreeMap;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Bound,
+};
 
 use derive_more::{Display, Error};
+use log::error;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use crate::spec::{FromRef, ObjectOrReference, Ref, RefError, RefType, Spec};
+use crate::spec::{
+    infer::nullable,
+    minimal_example::{minimal_integer, minimal_number},
+    Discriminator, ExclusiveBound, FromRef, Header, NumericBound, ObjectOrReference, Parameter,
+    Ref, RefError, RefResolver, RefType, Spec, SpecRefResolver,
+};
 
 /// Schema Errors
 #[derive(Debug, Clone, PartialEq, Display, Error)]
@@ -18,22 +32,81 @@ pub enum Error {
 
     #[display(fmt = "Required fields specified on a non-object schema")]
     RequiredSpecifiedOnNonObject,
+
+    #[display(fmt = "Invalid pattern: {}", _0)]
+    InvalidPattern(#[error(not(source))] String),
+}
+
+/// Where a schema is used, which determines whether `readOnly` and `writeOnly` properties may
+/// appear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum SchemaUsage {
+    /// Sent by the client; `readOnly` properties are not allowed.
+    #[display(fmt = "request")]
+    Request,
+
+    /// Sent by the server; `writeOnly` properties are not allowed.
+    #[display(fmt = "response")]
+    Response,
+}
+
+/// Properties that are not allowed for a [`SchemaUsage`].
+#[derive(Debug, Clone, PartialEq, Error)]
+pub struct CompatibilityError {
+    pub usage: SchemaUsage,
+
+    /// JSON Pointers to the offending properties (e.g., `/owner/id`), with `*` standing in for
+    /// any array index.
+    pub properties: Vec<String>,
+}
+
+impl fmt::Display for CompatibilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match self.usage {
+            SchemaUsage::Request => "readOnly",
+            SchemaUsage::Response => "writeOnly",
+        };
+
+        write!(
+            f,
+            "{} properties not allowed in {}: {}",
+            marker,
+            self.usage,
+            self.properties.join(", ")
+        )
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Type {
+    #[display(fmt = "boolean")]
     Boolean,
+
+    #[display(fmt = "integer")]
     Integer,
+
+    #[display(fmt = "number")]
     Number,
+
+    #[display(fmt = "string")]
     String,
+
+    #[display(fmt = "array")]
     Array,
+
+    #[display(fmt = "object")]
     Object,
+
+    #[display(fmt = "null")]
     Null,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+/// Content encoding of a string schema (`contentEncoding`).
+///
+/// Unrecognized encodings are kept as [`Encoding::Other`] so they survive round trips.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Encoding {
     Base16,
     Hex,
@@ -41,11 +114,155 @@ pub enum Encoding {
     Base32Hex,
     Base64,
     Base64Url,
-
-    #[serde(rename = "quoted-printable")]
     QuotedPrintable,
+    Other(String),
+}
+
+impl Encoding {
+    /// Returns the encoding's `contentEncoding` value.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Base16 => "base16",
+            Self::Hex => "hex",
+            Self::Base32 => "base32",
+            Self::Base32Hex => "base32hex",
+            Self::Base64 => "base64",
+            Self::Base64Url => "base64url",
+            Self::QuotedPrintable => "quoted-printable",
+            Self::Other(encoding) => encoding,
+        }
+    }
+
+    /// Maps synonymous encodings to a single variant; `hex` is treated as `base16`.
+    pub fn normalize(&self) -> Encoding {
+        match self {
+            Self::Hex => Self::Base16,
+            encoding => encoding.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<String> for Encoding {
+    fn from(encoding: String) -> Self {
+        match encoding.as_str() {
+            "base16" => Self::Base16,
+            "hex" => Self::Hex,
+            "base32" => Self::Base32,
+            "base32hex" => Self::Base32Hex,
+            "base64" => Self::Base64,
+            "base64url" => Self::Base64Url,
+            "quoted-printable" => Self::QuotedPrintable,
+            _ => Self::Other(encoding),
+        }
+    }
+}
+
+impl From<Encoding> for String {
+    fn from(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Other(encoding) => encoding,
+            encoding => encoding.as_str().to_owned(),
+        }
+    }
+}
+
+/// Format of a schema's values (`format`).
+///
+/// Formats defined by OpenAPI and JSON Schema have their own variants, while any other format is
+/// kept as [`KnownFormat::Custom`] so it survives round trips.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum KnownFormat {
+    Int32,
+    Int64,
+    Float,
+    Double,
+    Byte,
+    Binary,
+    Date,
+    DateTime,
+    Password,
+    Uuid,
+    Email,
+    Hostname,
+    Uri,
+    UriRef,
+    Ipv4,
+    Ipv6,
+    Custom(String),
+}
+
+impl KnownFormat {
+    /// Returns the format's `format` value.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Int32 => "int32",
+            Self::Int64 => "int64",
+            Self::Float => "float",
+            Self::Double => "double",
+            Self::Byte => "byte",
+            Self::Binary => "binary",
+            Self::Date => "date",
+            Self::DateTime => "date-time",
+            Self::Password => "password",
+            Self::Uuid => "uuid",
+            Self::Email => "email",
+            Self::Hostname => "hostname",
+            Self::Uri => "uri",
+            Self::UriRef => "uri-reference",
+            Self::Ipv4 => "ipv4",
+            Self::Ipv6 => "ipv6",
+            Self::Custom(format) => format,
+        }
+    }
+}
+
+impl fmt::Display for KnownFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
+impl From<String> for KnownFormat {
+    fn from(format: String) -> Self {
+        match format.as_str() {
+            "int32" => Self::Int32,
+            "int64" => Self::Int64,
+            "float" => Self::Float,
+            "double" => Self::Double,
+            "byte" => Self::Byte,
+            "binary" => Self::Binary,
+            "date" => Self::Date,
+            "date-time" => Self::DateTime,
+            "password" => Self::Password,
+            "uuid" => Self::Uuid,
+            "email" => Self::Email,
+            "hostname" => Self::Hostname,
+            "uri" => Self::Uri,
+            "uri-reference" => Self::UriRef,
+            "ipv4" => Self::Ipv4,
+            "ipv6" => Self::Ipv6,
+            _ => Self::Custom(format),
+        }
+    }
+}
+
+impl From<KnownFormat> for String {
+    fn from(format: KnownFormat) -> Self {
+        match format {
+            KnownFormat::Custom(format) => format,
+            format => format.as_str().to_owned(),
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum SchemaOrBool {
@@ -53,6 +270,44 @@ pub enum SchemaOrBool {
     Bool(bool),
 }
 
+impl SchemaOrBool {
+    /// Allows any value; equivalent to `true`.
+    pub fn any() -> Self {
+        Self::Bool(true)
+    }
+
+    /// Allows no values; equivalent to `false`.
+    pub fn none() -> Self {
+        Self::Bool(false)
+    }
+
+    /// Returns the schema, if this is not a boolean.
+    pub fn as_schema(&self) -> Option<&Schema> {
+        match self {
+            Self::Schema(schema) => Some(schema),
+            Self::Bool(_) => None,
+        }
+    }
+
+    /// Returns the schema mutably, if this is not a boolean.
+    pub fn as_schema_mut(&mut self) -> Option<&mut Schema> {
+        match self {
+            Self::Schema(schema) => Some(schema),
+            Self::Bool(_) => None,
+        }
+    }
+
+    /// Returns true if some values may be allowed, i.e., this is `true` or a schema.
+    pub fn is_allowed(&self) -> bool {
+        !self.is_forbidden()
+    }
+
+    /// Returns true if this is `false`.
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, Self::Bool(false))
+    }
+}
+
 // FIXME: Verify against OpenAPI 3.1
 /// The Schema Object allows the definition of input and output data types.
 /// These types can be objects, but also primitives and arrays.
@@ -66,6 +321,31 @@ pub enum SchemaOrBool {
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#schemaObject>.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Schema {
+    //
+    // dialect
+    //
+    #[serde(rename = "$schema")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_dialect: Option<String>,
+
+    /// Plain name identifying this schema within the document (`$anchor`).
+    #[serde(rename = "$anchor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+
+    /// Reference resolved within the dynamic scope of the validated value (`$dynamicRef`).
+    ///
+    /// Dynamic scoping is not implemented, so validating values against a schema using it fails
+    /// with an `UnsupportedKeyword` error.
+    #[serde(rename = "$dynamicRef")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dynamic_ref: Option<String>,
+
+    /// Name identifying this schema as a target of `$dynamicRef`s (`$dynamicAnchor`).
+    #[serde(rename = "$dynamicAnchor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dynamic_anchor: Option<String>,
+
     //
     // display metadata
     //
@@ -75,6 +355,11 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// Notes for schema authors, ignored by validators.
+    #[serde(rename = "$comment")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
     //
     // type
     //
@@ -82,6 +367,10 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema_type: Option<Type>,
 
+    /// Allows `null` values in OpenAPI 3.0 schemas; superseded by the `null` type in 3.1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
     //
     // structure
     //
@@ -92,10 +381,22 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<ObjectOrReference<Schema>>>,
 
+    #[serde(default)]
+    #[serde(rename = "prefixItems")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub prefix_items: Vec<ObjectOrReference<Schema>>,
+
     #[serde(default)]
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub properties: BTreeMap<String, ObjectOrReference<Schema>>,
 
+    /// Schemas for properties whose names match a regular expression (`patternProperties`).
+    /// See [`Schema::matching_pattern_schemas`].
+    #[serde(default)]
+    #[serde(rename = "patternProperties")]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub pattern_properties: BTreeMap<String, ObjectOrReference<Schema>>,
+
     #[serde(rename = "additionalProperties")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub additional_properties: Option<Box<ObjectOrReference<SchemaOrBool>>>,
@@ -108,6 +409,11 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_media_type: Option<String>,
 
+    /// Schema of the decoded content, when `contentEncoding` and `contentMediaType` are set.
+    #[serde(rename = "contentSchema")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_schema: Option<Box<ObjectOrReference<Schema>>>,
+
     //
     // additional metadata
     //
@@ -122,14 +428,27 @@ pub struct Schema {
     // validation requirements
     //
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub format: Option<String>,
+    pub format: Option<KnownFormat>,
 
     #[serde(default)]
     #[serde(rename = "enum")]
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub enum_values: Vec<String>,
+    pub enum_values: Vec<serde_json::Value>,
+
+    /// The only allowed value. Unlike other optional keywords, `const: null` is kept as
+    /// `Some(Value::Null)`.
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[serde(rename = "const")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub const_value: Option<serde_json::Value>,
 
+    /// Regular expression in ECMA-262 syntax. See [`Schema::compile_pattern`].
+    #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "validate-patterns",
+        serde(deserialize_with = "crate::spec::pattern::deserialize_checked")
+    )]
     pub pattern: Option<String>,
 
     #[serde(rename = "multipleOf")]
@@ -137,18 +456,20 @@ pub struct Schema {
     pub multiple_of: Option<serde_json::Number>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub minimum: Option<serde_json::Number>,
+    pub minimum: Option<NumericBound>,
 
+    /// See [`Schema::maximum_bounds`].
     #[serde(rename = "exclusiveMaximum")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub exclusive_maximum: Option<serde_json::Number>,
+    pub exclusive_maximum: Option<ExclusiveBound>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub maximum: Option<serde_json::Number>,
+    pub maximum: Option<NumericBound>,
 
+    /// See [`Schema::minimum_bounds`].
     #[serde(rename = "exclusiveMinimum")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub exclusive_minimum: Option<serde_json::Number>,
+    pub exclusive_minimum: Option<ExclusiveBound>,
 
     #[serde(rename = "minLength")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -186,6 +507,9 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub write_only: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
     //
     // composition
     //
@@ -203,6 +527,1193 @@ pub struct Schema {
     #[serde(rename = "anyOf")]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub any_of: Vec<ObjectOrReference<Schema>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not: Option<Box<ObjectOrReference<Schema>>>,
+
+    /// Condition (`if`) selecting whether `then` or `else` applies. Conditions are not yet
+    /// validated.
+    #[serde(rename = "if")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub if_schema: Option<Box<ObjectOrReference<Schema>>>,
+
+    #[serde(rename = "then")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub then_schema: Option<Box<ObjectOrReference<Schema>>>,
+
+    #[serde(rename = "else")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub else_schema: Option<Box<ObjectOrReference<Schema>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Discriminator>,
+
+    /// Keywords not otherwise supported, kept so that schemas round-trip losslessly. Includes
+    /// extensions such as `x-stoplight-id` along with keywords like `unevaluatedProperties`,
+    /// which are not validated.
+    #[serde(flatten)]
+    pub unknown_keywords: BTreeMap<String, serde_json::Value>,
+}
+
+/// Depth past which generated examples leave out optional properties and array items, so that
+/// recursive schemas terminate.
+const EXAMPLE_DEPTH_LIMIT: usize = 8;
+
+/// Matches CommonMark ATX headings, code spans, bold and italic text, and inline links.
+static RE_MARKDOWN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?m)^ {0,3}#{1,6}(\s|$)",
+        r"|`[^`]+`",
+        r"|\*\*\S[^*]*\*\*|__\S[^_]*__",
+        r"|(^|[^\w*])\*[^\s*][^*]*\*([^\w*]|$)",
+        r"|(^|\W)_[^\s_][^_]*_(\W|$)",
+        r"|\[[^\]]+\]\([^)\s]+\)",
+    ))
+    .unwrap()
+});
+
+impl Schema {
+    /// Produces a value for this schema, suitable for mock requests and responses.
+    ///
+    /// Uses the first of `examples`, `default`, `const` or `enum` that is present, otherwise generates a
+    /// value from the schema's type, format and bounds. Numbers are the value closest to zero that
+    /// satisfies the bounds and `multipleOf`, as in
+    /// [`minimum_valid_example`](Self::minimum_valid_example). `oneOf` and `anyOf` use their first
+    /// branch; `allOf` branches are merged.
+    pub fn example_value(&self, spec: &Spec) -> serde_json::Value {
+        self.example_value_at_depth(spec, 0)
+    }
+
+    /// Hashes the schema's JSON representation.
+    ///
+    /// The hash is deterministic across runs and platforms, so it can be persisted, e.g. as a
+    /// cache key. Schemas with the same JSON representation produce equal hashes.
+    pub fn content_hash(&self) -> u64 {
+        fnv1a_hash(&self.canonical_json())
+    }
+
+    /// Serializes the schema to JSON; object keys are always sorted.
+    fn canonical_json(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("schemas should always serialize to JSON")
+    }
+
+    /// Clones the schema without its metadata (`title`, `description`, `$comment`, `default`,
+    /// `examples` and `deprecated`), including that of its inline subschemas, for comparing
+    /// schemas by structure.
+    pub fn clone_without_metadata(&self) -> Schema {
+        let mut schema = self.clone();
+        schema.strip_metadata();
+        schema
+    }
+
+    fn strip_metadata(&mut self) {
+        self.title = None;
+        self.description = None;
+        self.comment = None;
+        self.default = None;
+        self.examples.clear();
+        self.deprecated = None;
+
+        let subschemas = self
+            .items
+            .as_deref_mut()
+            .into_iter()
+            .chain(&mut self.prefix_items)
+            .chain(self.properties.values_mut())
+            .chain(self.pattern_properties.values_mut())
+            .chain(&mut self.all_of)
+            .chain(&mut self.one_of)
+            .chain(&mut self.any_of)
+            .chain(self.not.as_deref_mut())
+            .chain(self.if_schema.as_deref_mut())
+            .chain(self.then_schema.as_deref_mut())
+            .chain(self.else_schema.as_deref_mut())
+            .chain(self.content_schema.as_deref_mut());
+
+        for sub in subschemas {
+            if let ObjectOrReference::Object(sub)
+            | ObjectOrReference::SchemaRef { overrides: sub, .. } = sub
+            {
+                sub.strip_metadata();
+            }
+        }
+
+        if let Some(ObjectOrReference::Object(SchemaOrBool::Schema(additional))) =
+            self.additional_properties.as_deref_mut()
+        {
+            additional.strip_metadata();
+        }
+    }
+
+    /// Checks that no property is marked `readOnly` in a request schema or `writeOnly` in a
+    /// response schema.
+    ///
+    /// Nested properties are checked through `items` and composition keywords. Unresolvable
+    /// references are skipped.
+    pub fn is_compatible_with(
+        &self,
+        usage: SchemaUsage,
+        spec: &Spec,
+    ) -> Result<(), CompatibilityError> {
+        let mut properties = vec![];
+        self.collect_incompatible(usage, spec, "", &mut vec![], &mut properties);
+
+        if properties.is_empty() {
+            Ok(())
+        } else {
+            Err(CompatibilityError { usage, properties })
+        }
+    }
+
+    /// Returns true if the description appears to contain CommonMark markup: headings, bold or
+    /// italic text, code spans or links.
+    ///
+    /// This is a heuristic; e.g., a description using asterisks for multiplication may be
+    /// reported as Markdown.
+    pub fn has_markdown_description(&self) -> bool {
+        self.description
+            .as_deref()
+            .map_or(false, |description| RE_MARKDOWN.is_match(description))
+    }
+
+    /// Returns true if the schema imposes no constraints and so accepts any value, like `{}`.
+    ///
+    /// Annotations such as `title`, `description`, `default` and `examples` are ignored, as is a
+    /// `discriminator`, which only applies to `oneOf` branches. `readOnly` and `writeOnly` count
+    /// as constraints since they are enforced when validating requests and responses.
+    pub fn is_empty(&self) -> bool {
+        self.dynamic_ref.is_none()
+            && self.schema_type.is_none()
+            && self.nullable.is_none()
+            && self.required.is_empty()
+            && self.items.is_none()
+            && self.prefix_items.is_empty()
+            && self.properties.is_empty()
+            && self.pattern_properties.is_empty()
+            && self.additional_properties.is_none()
+            && self.content_encoding.is_none()
+            && self.content_media_type.is_none()
+            && self.content_schema.is_none()
+            && self.format.is_none()
+            && self.enum_values.is_empty()
+            && self.const_value.is_none()
+            && self.pattern.is_none()
+            && self.multiple_of.is_none()
+            && self.minimum.is_none()
+            && self.exclusive_maximum.is_none()
+            && self.maximum.is_none()
+            && self.exclusive_minimum.is_none()
+            && self.min_length.is_none()
+            && self.max_length.is_none()
+            && self.min_items.is_none()
+            && self.max_items.is_none()
+            && self.unique_items.is_none()
+            && self.max_properties.is_none()
+            && self.min_properties.is_none()
+            && self.read_only.is_none()
+            && self.write_only.is_none()
+            && self.all_of.is_empty()
+            && self.one_of.is_empty()
+            && self.any_of.is_empty()
+            && self.not.is_none()
+            && self.if_schema.is_none()
+            && self.then_schema.is_none()
+            && self.else_schema.is_none()
+    }
+
+    /// Returns the schema of `additionalProperties`, unless it is absent or a boolean.
+    pub(crate) fn additional_properties_schema(&self) -> Option<ObjectOrReference<Schema>> {
+        Some(match self.additional_properties.as_deref()? {
+            ObjectOrReference::Object(additional) => {
+                ObjectOrReference::Object(additional.as_schema()?.clone())
+            }
+            ObjectOrReference::Ref { ref_path } => ObjectOrReference::Ref {
+                ref_path: ref_path.clone(),
+            },
+            ObjectOrReference::SchemaRef {
+                ref_path,
+                overrides,
+            } => match overrides.as_schema() {
+                Some(overrides) => ObjectOrReference::SchemaRef {
+                    ref_path: ref_path.clone(),
+                    overrides: overrides.clone(),
+                },
+                None => ObjectOrReference::Ref {
+                    ref_path: ref_path.clone(),
+                },
+            },
+        })
+    }
+
+    /// Returns true if `null` is allowed, either by the `null` type or OpenAPI 3.0's
+    /// `nullable`.
+    pub fn is_nullable(&self) -> bool {
+        self.nullable == Some(true) || self.schema_type == Some(Type::Null)
+    }
+
+    /// Returns true if the schema accepts integers: its type is `integer`, or its type is unset
+    /// and `multipleOf` is a whole number.
+    pub fn is_integer_compatible(&self) -> bool {
+        match self.schema_type {
+            Some(Type::Integer) => true,
+            Some(_) => false,
+            None => self
+                .multiple_of
+                .as_ref()
+                .map_or(false, |num| num.is_u64() || num.is_i64()),
+        }
+    }
+
+    /// Returns true if the schema accepts numbers, i.e., its type is `integer` or `number`.
+    pub fn is_numeric_compatible(&self) -> bool {
+        self.schema_type == Some(Type::Number) || self.is_integer_compatible()
+    }
+
+    /// Returns true if the schema's type is `string`.
+    pub fn is_string_compatible(&self) -> bool {
+        self.schema_type == Some(Type::String)
+    }
+
+    /// Returns true if the schema's type is `array`.
+    pub fn is_array_compatible(&self) -> bool {
+        self.schema_type == Some(Type::Array)
+    }
+
+    /// Returns true if the schema's type is `object`.
+    pub fn is_object_compatible(&self) -> bool {
+        self.schema_type == Some(Type::Object)
+    }
+
+    /// Iterates over the schema's direct subschemas: `items`, `prefixItems`, `properties`,
+    /// `patternProperties`, `allOf`, `oneOf`, `anyOf`, `not`, `if`, `then`, `else` and `contentSchema`.
+    pub(crate) fn subschemas(&self) -> impl Iterator<Item = &ObjectOrReference<Schema>> {
+        self.items
+            .as_deref()
+            .into_iter()
+            .chain(&self.prefix_items)
+            .chain(self.properties.values())
+            .chain(self.pattern_properties.values())
+            .chain(&self.all_of)
+            .chain(&self.one_of)
+            .chain(&self.any_of)
+            .chain(self.not.as_deref())
+            .chain(self.if_schema.as_deref())
+            .chain(self.then_schema.as_deref())
+            .chain(self.else_schema.as_deref())
+            .chain(self.content_schema.as_deref())
+    }
+
+    /// Returns the values allowed by the `enum`s of this schema and its composition keywords.
+    ///
+    /// `allOf` branches intersect the allowed values while `oneOf` and `anyOf` branches unite
+    /// them. Returns an empty list if no enum constrains the schema, or if the constraints
+    /// cannot be satisfied together. Unresolvable references are treated as unconstrained.
+    pub fn all_enum_values(&self, spec: &Spec) -> Vec<serde_json::Value> {
+        self.effective_enum_values(spec).unwrap_or_default()
+    }
+
+    /// Returns the allowed enum values, or `None` if the schema is unconstrained.
+    fn effective_enum_values(&self, spec: &Spec) -> Option<Vec<serde_json::Value>> {
+        let branch_values = |oor: &ObjectOrReference<Schema>| {
+            oor.resolve(spec)
+                .ok()
+                .and_then(|schema| schema.effective_enum_values(spec))
+        };
+
+        let mut allowed = (!self.enum_values.is_empty()).then(|| self.enum_values.clone());
+
+        for values in self.all_of.iter().filter_map(branch_values) {
+            allowed = Some(match allowed {
+                Some(allowed) => allowed.into_iter().filter(|v| values.contains(v)).collect(),
+                None => values,
+            });
+        }
+
+        for branches in [&self.one_of, &self.any_of] {
+            if branches.is_empty() {
+                continue;
+            }
+
+            // a single unconstrained branch allows any value
+            let values = match branches
+                .iter()
+                .map(branch_values)
+                .collect::<Option<Vec<_>>>()
+            {
+                Some(values) => values,
+                None => continue,
+            };
+
+            let mut union = Vec::<serde_json::Value>::new();
+            for value in values.into_iter().flatten() {
+                if !union.contains(&value) {
+                    union.push(value);
+                }
+            }
+
+            allowed = Some(match allowed {
+                Some(allowed) => allowed.into_iter().filter(|v| union.contains(v)).collect(),
+                None => union,
+            });
+        }
+
+        allowed
+    }
+
+    /// Merges the `allOf` branches of this schema, recursively, into a single schema.
+    ///
+    /// Properties and required fields are combined, enums are intersected and the strictest
+    /// bounds are kept. Other keywords keep the first value found, starting with this schema's.
+    pub fn merge_all_of(&self, resolver: &dyn RefResolver) -> Result<Schema, RefError> {
+        let mut merged = Schema {
+            all_of: vec![],
+            ..self.clone()
+        };
+
+        for branch in &self.all_of {
+            let branch = branch.resolve_with(resolver)?.merge_all_of(resolver)?;
+            merged.merge(branch);
+        }
+
+        Ok(merged)
+    }
+
+    /// Returns the inclusive and exclusive lower bounds, i.e. `minimum` and `exclusiveMinimum`.
+    ///
+    /// An OpenAPI 3.0 `exclusiveMinimum: true` makes `minimum` the exclusive bound instead.
+    pub fn minimum_bounds(&self) -> (Option<NumericBound>, Option<NumericBound>) {
+        split_exclusive_bound(self.minimum, self.exclusive_minimum)
+    }
+
+    /// Returns the inclusive and exclusive upper bounds, i.e. `maximum` and `exclusiveMaximum`.
+    ///
+    /// An OpenAPI 3.0 `exclusiveMaximum: true` makes `maximum` the exclusive bound instead.
+    pub fn maximum_bounds(&self) -> (Option<NumericBound>, Option<NumericBound>) {
+        split_exclusive_bound(self.maximum, self.exclusive_maximum)
+    }
+
+    /// Returns the inclusive range of integers allowed by the schema's bounds, merged with those
+    /// of its `allOf` branches.
+    ///
+    /// Exclusive and fractional bounds are converted to the nearest allowed integer, and bounds
+    /// are tightened to multiples of an integer `multipleOf`. If no such multiple fits in an
+    /// `i64`, the range is empty, with an upper bound of `Excluded(i64::MIN)` or a lower bound of
+    /// `Excluded(i64::MAX)`.
+    ///
+    /// Returns `None` if the schema is not integer-typed, has no `minimum`, `maximum`,
+    /// `exclusiveMinimum` or `exclusiveMaximum`, or if an `allOf` branch cannot be resolved.
+    pub fn integer_range(&self, spec: &Spec) -> Option<(Bound<i64>, Bound<i64>)> {
+        let merged = self.merged_all_of(spec)?;
+
+        if !merged.is_integer_compatible() {
+            return None;
+        }
+
+        let (minimum, exclusive_minimum) = merged.minimum_bounds();
+        let (maximum, exclusive_maximum) = merged.maximum_bounds();
+
+        let lower = [
+            minimum.map(|min| match min {
+                NumericBound::Integer(min) => min,
+                NumericBound::Float(min) => min.ceil() as i64,
+            }),
+            exclusive_minimum.map(|min| match min {
+                NumericBound::Integer(min) => min.saturating_add(1),
+                NumericBound::Float(min) => (min.floor() as i64).saturating_add(1),
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .max();
+
+        let upper = [
+            maximum.map(|max| match max {
+                NumericBound::Integer(max) => max,
+                NumericBound::Float(max) => max.floor() as i64,
+            }),
+            exclusive_maximum.map(|max| match max {
+                NumericBound::Integer(max) => max.saturating_sub(1),
+                NumericBound::Float(max) => (max.ceil() as i64).saturating_sub(1),
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+
+        if lower.is_none() && upper.is_none() {
+            return None;
+        }
+
+        let step = merged
+            .multiple_of
+            .as_ref()
+            .and_then(serde_json::Number::as_i64)
+            .filter(|step| *step > 0);
+
+        let bound = |num: Option<i64>| num.map_or(Bound::Unbounded, Bound::Included);
+
+        // without a representable multiple beyond a bound, the range is empty
+        let lower = match (lower, step) {
+            (Some(lower), Some(step)) if lower.rem_euclid(step) != 0 => lower
+                .checked_add(step - lower.rem_euclid(step))
+                .map_or(Bound::Excluded(i64::MAX), Bound::Included),
+            (lower, _) => bound(lower),
+        };
+        let upper = match (upper, step) {
+            (Some(upper), Some(step)) => upper
+                .checked_sub(upper.rem_euclid(step))
+                .map_or(Bound::Excluded(i64::MIN), Bound::Included),
+            (upper, _) => bound(upper),
+        };
+
+        Some((lower, upper))
+    }
+
+    /// Returns the tightest `maxLength` of the schema and its `allOf` branches.
+    ///
+    /// Returns `None` if no `maxLength` applies, or if an `allOf` branch cannot be resolved.
+    pub fn effective_max_length(&self, spec: &Spec) -> Option<u64> {
+        self.merged_all_of(spec)?.max_length
+    }
+
+    /// Returns the tightest `minLength` of the schema and its `allOf` branches.
+    ///
+    /// Returns `None` if no `minLength` applies, or if an `allOf` branch cannot be resolved.
+    pub fn effective_min_length(&self, spec: &Spec) -> Option<u64> {
+        self.merged_all_of(spec)?.min_length
+    }
+
+    /// Returns the tightest `maximum` of the schema and its `allOf` branches.
+    ///
+    /// `exclusiveMaximum` is not taken into account. Returns `None` if no `maximum` applies, or if
+    /// an `allOf` branch cannot be resolved.
+    pub fn effective_maximum(&self, spec: &Spec) -> Option<NumericBound> {
+        self.merged_all_of(spec)?.maximum
+    }
+
+    /// Returns the tightest `minimum` of the schema and its `allOf` branches.
+    ///
+    /// `exclusiveMinimum` is not taken into account. Returns `None` if no `minimum` applies, or if
+    /// an `allOf` branch cannot be resolved.
+    pub fn effective_minimum(&self, spec: &Spec) -> Option<NumericBound> {
+        self.merged_all_of(spec)?.minimum
+    }
+
+    pub(crate) fn merged_all_of(&self, spec: &Spec) -> Option<Schema> {
+        self.merge_all_of(&SpecRefResolver::new(spec)).ok()
+    }
+
+    /// Fills in missing optional properties of `value` with their `default`s, returning the
+    /// number of defaults applied.
+    ///
+    /// Properties and defaults of `allOf` branches are merged in, and defaults are applied
+    /// within nested objects and array items present in `value`. Subschemas that cannot be
+    /// resolved are skipped.
+    pub fn apply_defaults(&self, value: &mut serde_json::Value, spec: &Spec) -> usize {
+        let resolver = SpecRefResolver::new(spec);
+
+        let schema = match self.merge_all_of(&resolver) {
+            Ok(schema) => schema,
+            Err(_) => return 0,
+        };
+
+        let mut applied = 0;
+
+        match value {
+            serde_json::Value::Object(map) => {
+                for (name, oor) in &schema.properties {
+                    let prop = match oor.resolve(spec) {
+                        Ok(prop) => prop,
+                        Err(_) => continue,
+                    };
+
+                    match map.get_mut(name) {
+                        Some(value) => applied += prop.apply_defaults(value, spec),
+                        None if !schema.required.contains(name) => {
+                            let default = prop.merged_all_of(spec).and_then(|prop| prop.default);
+
+                            if let Some(default) = default {
+                                map.insert(name.clone(), default);
+                                applied += 1;
+                            }
+                        }
+                        None => {}
+                    }
+                }
+            }
+
+            serde_json::Value::Array(items) => {
+                if let Some(Ok(item_schema)) = schema.items.as_ref().map(|oor| oor.resolve(spec)) {
+                    for item in items {
+                        applied += item_schema.apply_defaults(item, spec);
+                    }
+                }
+            }
+
+            _ => {}
+        }
+
+        applied
+    }
+
+    /// Resolves a reference to a component of any type to a schema.
+    ///
+    /// References to parameters and headers resolve to their schemas. References to other
+    /// kinds of component fail with [`RefError::MismatchedType`], as do parameters and headers
+    /// with no schema.
+    pub fn resolve_ref_path(ref_path: &str, spec: &Spec) -> Result<Schema, RefError> {
+        let kind = ref_path.parse::<Ref>()?.kind();
+
+        let schema = match kind {
+            RefType::Schema => return Schema::from_ref(spec, ref_path),
+            RefType::Parameter => Parameter::from_ref(spec, ref_path)?.schema,
+            RefType::Header => Header::from_ref(spec, ref_path)?.schema,
+            typ => return Err(RefError::MismatchedType(typ, RefType::Schema)),
+        };
+
+        schema
+            .ok_or(RefError::MismatchedType(kind, RefType::Schema))?
+            .resolve(spec)
+    }
+
+    fn merge(&mut self, other: Schema) {
+        macro_rules! first {
+            ($($field:ident),+ $(,)?) => {
+                $(
+                    if self.$field.is_none() {
+                        self.$field = other.$field;
+                    }
+                )+
+            };
+        }
+
+        macro_rules! strictest {
+            ($pick:path: $($field:ident),+ $(,)?) => {
+                $(
+                    self.$field = match (self.$field.take(), other.$field) {
+                        (Some(a), Some(b)) => Some($pick(a, b)),
+                        (a, b) => a.or(b),
+                    };
+                )+
+            };
+        }
+
+        fn larger_num(a: NumericBound, b: NumericBound) -> NumericBound {
+            if b > a {
+                b
+            } else {
+                a
+            }
+        }
+
+        fn smaller_num(a: NumericBound, b: NumericBound) -> NumericBound {
+            if b < a {
+                b
+            } else {
+                a
+            }
+        }
+
+        // OpenAPI 3.0's boolean exclusive bounds are merged in their OpenAPI 3.1 form
+        let stricter = |pick: fn(NumericBound, NumericBound) -> NumericBound, a, b| match (a, b) {
+            (Some(a), Some(b)) => Some(pick(a, b)),
+            (a, b) => a.or(b),
+        };
+
+        let ((min_a, exclusive_min_a), (min_b, exclusive_min_b)) =
+            (self.minimum_bounds(), other.minimum_bounds());
+        self.minimum = stricter(larger_num, min_a, min_b);
+        self.exclusive_minimum =
+            stricter(larger_num, exclusive_min_a, exclusive_min_b).map(ExclusiveBound::Bound);
+
+        let ((max_a, exclusive_max_a), (max_b, exclusive_max_b)) =
+            (self.maximum_bounds(), other.maximum_bounds());
+        self.maximum = stricter(smaller_num, max_a, max_b);
+        self.exclusive_maximum =
+            stricter(smaller_num, exclusive_max_a, exclusive_max_b).map(ExclusiveBound::Bound);
+
+        first!(
+            schema_dialect,
+            anchor,
+            dynamic_ref,
+            dynamic_anchor,
+            title,
+            description,
+            comment,
+            schema_type,
+            nullable,
+            items,
+            additional_properties,
+            content_encoding,
+            content_media_type,
+            content_schema,
+            default,
+            const_value,
+            format,
+            pattern,
+            multiple_of,
+            unique_items,
+            read_only,
+            write_only,
+            deprecated,
+            not,
+            if_schema,
+            then_schema,
+            else_schema,
+            discriminator,
+        );
+
+        strictest!(std::cmp::max: min_length, min_items, min_properties);
+        strictest!(std::cmp::min: max_length, max_items, max_properties);
+
+        for (name, prop) in other.properties {
+            self.properties.entry(name).or_insert(prop);
+        }
+
+        for (pattern, prop) in other.pattern_properties {
+            self.pattern_properties.entry(pattern).or_insert(prop);
+        }
+
+        for (keyword, value) in other.unknown_keywords {
+            self.unknown_keywords.entry(keyword).or_insert(value);
+        }
+
+        for field in other.required {
+            if !self.required.contains(&field) {
+                self.required.push(field);
+            }
+        }
+
+        if self.enum_values.is_empty() {
+            self.enum_values = other.enum_values;
+        } else if !other.enum_values.is_empty() {
+            self.enum_values
+                .retain(|val| other.enum_values.contains(val));
+        }
+
+        if self.examples.is_empty() {
+            self.examples = other.examples;
+        }
+
+        if self.prefix_items.is_empty() {
+            self.prefix_items = other.prefix_items;
+        }
+
+        if self.one_of.is_empty() {
+            self.one_of = other.one_of;
+        }
+
+        if self.any_of.is_empty() {
+            self.any_of = other.any_of;
+        }
+    }
+
+    fn collect_incompatible(
+        &self,
+        usage: SchemaUsage,
+        spec: &Spec,
+        pointer: &str,
+        visiting: &mut Vec<String>,
+        properties: &mut Vec<String>,
+    ) {
+        let mut visit = |oor: &ObjectOrReference<Schema>, pointer: &str, is_property: bool| {
+            if let Some(ref_path) = oor.ref_path() {
+                // stop at recursive references
+                if visiting.iter().any(|visited| visited == ref_path) {
+                    return;
+                }
+                visiting.push(ref_path.to_owned());
+            }
+
+            match oor.resolve(spec) {
+                Ok(schema) => {
+                    let marked = match usage {
+                        SchemaUsage::Request => schema.read_only,
+                        SchemaUsage::Response => schema.write_only,
+                    };
+
+                    if is_property && marked == Some(true) {
+                        properties.push(pointer.to_owned());
+                    }
+
+                    schema.collect_incompatible(usage, spec, pointer, visiting, properties)
+                }
+                Err(err) => error!("{}", err),
+            }
+
+            if oor.ref_path().is_some() {
+                visiting.pop();
+            }
+        };
+
+        for (name, oor) in &self.properties {
+            let pointer = format!("{}/{}", pointer, name.replace('~', "~0").replace('/', "~1"));
+            visit(oor, &pointer, true);
+        }
+
+        if let Some(items) = &self.items {
+            visit(items, &format!("{}/*", pointer), false);
+        }
+
+        for oor in self.all_of.iter().chain(&self.one_of).chain(&self.any_of) {
+            visit(oor, pointer, false);
+        }
+    }
+
+    fn example_value_at_depth(&self, spec: &Spec, depth: usize) -> serde_json::Value {
+        use serde_json::{json, Map, Value};
+
+        if let Some(example) = self.examples.first() {
+            return example.clone();
+        }
+
+        if let Some(default) = &self.default {
+            return default.clone();
+        }
+
+        if let Some(value) = self
+            .const_value
+            .as_ref()
+            .or_else(|| self.enum_values.first())
+        {
+            return value.clone();
+        }
+
+        if depth > EXAMPLE_DEPTH_LIMIT * 2 {
+            return Value::Null;
+        }
+
+        let sub_example = |oor: &ObjectOrReference<Schema>| {
+            oor.resolve(spec)
+                .map(|schema| schema.example_value_at_depth(spec, depth + 1))
+                .unwrap_or(Value::Null)
+        };
+
+        if let Some(branch) = self.one_of.first().or_else(|| self.any_of.first()) {
+            return sub_example(branch);
+        }
+
+        let mut value = match self.schema_type {
+            Some(Type::Boolean) => Value::Bool(true),
+
+            // unsatisfiable bounds have no valid value to offer
+            Some(Type::Integer) => minimal_integer(self, spec).unwrap_or_else(|_| json!(0)),
+            Some(Type::Number) => minimal_number(self).unwrap_or_else(|_| json!(0.0)),
+
+            Some(Type::String) => {
+                let mut string = example_string(self.format.as_ref())
+                    .unwrap_or("string")
+                    .to_owned();
+
+                if let Some(min) = self.min_length {
+                    while (string.chars().count() as u64) < min {
+                        string.push('a');
+                    }
+                }
+
+                if let Some(max) = self.max_length {
+                    string = string.chars().take(max as usize).collect();
+                }
+
+                Value::String(string)
+            }
+
+            Some(Type::Array) => {
+                let count = match (self.min_items, self.max_items) {
+                    (Some(min), _) if min > 0 => min,
+                    (_, Some(0)) => 0,
+                    _ if depth >= EXAMPLE_DEPTH_LIMIT => 0,
+                    _ => 1,
+                };
+
+                let item = self
+                    .items
+                    .as_deref()
+                    .map(sub_example)
+                    .unwrap_or(Value::Null);
+
+                Value::Array(vec![item; count as usize])
+            }
+
+            Some(Type::Object) => Value::Object(Map::new()),
+
+            Some(Type::Null) => Value::Null,
+
+            None if !self.properties.is_empty() => Value::Object(Map::new()),
+
+            None => Value::Null,
+        };
+
+        if let Value::Object(obj) = &mut value {
+            for (prop, oor) in &self.properties {
+                if depth >= EXAMPLE_DEPTH_LIMIT && !self.required.contains(prop) {
+                    continue;
+                }
+
+                obj.insert(prop.clone(), sub_example(oor));
+            }
+        }
+
+        for branch in &self.all_of {
+            match (&mut value, sub_example(branch)) {
+                (Value::Object(obj), Value::Object(branch_obj)) => obj.extend(branch_obj),
+                (Value::Null, branch_value) => value = branch_value,
+                _ => {}
+            }
+        }
+
+        value
+    }
+}
+
+/// Splits a bound and its exclusive counterpart into inclusive and exclusive bounds.
+fn split_exclusive_bound(
+    bound: Option<NumericBound>,
+    exclusive: Option<ExclusiveBound>,
+) -> (Option<NumericBound>, Option<NumericBound>) {
+    match exclusive {
+        Some(ExclusiveBound::Bound(exclusive)) => (bound, Some(exclusive)),
+        Some(ExclusiveBound::Flag(true)) => (None, bound),
+        Some(ExclusiveBound::Flag(false)) | None => (bound, None),
+    }
+}
+
+/// Deserializes a present value, including `null`, as `Some`.
+fn deserialize_some<'de, D>(deserializer: D) -> Result<Option<serde_json::Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde_json::Value::deserialize(deserializer).map(Some)
+}
+
+/// Hashes bytes with 64-bit FNV-1a, which is stable across runs and platforms.
+pub(crate) fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Returns a sample string for a string `format`, if it is a format with a known syntax.
+pub(crate) fn example_string(format: Option<&KnownFormat>) -> Option<&'static str> {
+    let example = match format.map(KnownFormat::as_str) {
+        Some("date") => "2024-01-01",
+        Some("date-time") => "2024-01-01T00:00:00Z",
+        Some("time") => "00:00:00Z",
+        Some("email") => "user@example.com",
+        Some("hostname") => "example.com",
+        Some("ipv4") => "192.0.2.1",
+        Some("ipv6") => "2001:db8::1",
+        Some("uri") | Some("url") => "https://example.com",
+        Some("uuid") => "00000000-0000-0000-0000-000000000000",
+        Some("byte") => "c3RyaW5n",
+        Some("password") => "password",
+        _ => return None,
+    };
+
+    Some(example)
+}
+
+/// Constructors and builder methods for creating schemas programmatically.
+///
+/// ```
+/// use oas3::spec::Schema;
+///
+/// let base = Schema::object().with_property("id", Schema::integer());
+/// let extended = Schema::object()
+///     .with_all_of(base)
+///     .with_property("extra", Schema::string());
+///
+/// assert_eq!(extended.all_of.len(), 1);
+/// assert!(extended.properties.contains_key("extra"));
+/// ```
+impl Schema {
+    /// Creates a schema of the given type.
+    pub fn of_type(schema_type: Type) -> Self {
+        Self {
+            schema_type: Some(schema_type),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a `boolean` schema.
+    pub fn boolean() -> Self {
+        Self::of_type(Type::Boolean)
+    }
+
+    /// Creates an `integer` schema.
+    pub fn integer() -> Self {
+        Self::of_type(Type::Integer)
+    }
+
+    /// Creates a `number` schema.
+    pub fn number() -> Self {
+        Self::of_type(Type::Number)
+    }
+
+    /// Creates a `string` schema.
+    pub fn string() -> Self {
+        Self::of_type(Type::String)
+    }
+
+    /// Creates an `array` schema with the given item schema.
+    pub fn array(items: impl Into<ObjectOrReference<Schema>>) -> Self {
+        Self {
+            items: Some(Box::new(items.into())),
+            ..Self::of_type(Type::Array)
+        }
+    }
+
+    /// Creates an `object` schema with no properties.
+    pub fn object() -> Self {
+        Self::of_type(Type::Object)
+    }
+
+    /// Creates a schema that matches all of the given schemas.
+    pub fn all_of(schemas: Vec<Schema>) -> Self {
+        Self {
+            all_of: schemas.into_iter().map(ObjectOrReference::Object).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a schema that matches exactly one of the given schemas.
+    pub fn one_of(schemas: Vec<Schema>) -> Self {
+        Self {
+            one_of: schemas.into_iter().map(ObjectOrReference::Object).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a schema that matches any of the given schemas.
+    pub fn any_of(schemas: Vec<Schema>) -> Self {
+        Self {
+            any_of: schemas.into_iter().map(ObjectOrReference::Object).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds a property, replacing any existing property with the same name.
+    pub fn add_property(
+        &mut self,
+        name: impl Into<String>,
+        schema: impl Into<ObjectOrReference<Schema>>,
+    ) -> &mut Self {
+        self.properties.insert(name.into(), schema.into());
+        self
+    }
+
+    /// Adds an `allOf` branch.
+    pub fn add_all_of(&mut self, schema: impl Into<ObjectOrReference<Schema>>) -> &mut Self {
+        self.all_of.push(schema.into());
+        self
+    }
+
+    /// Adds a `oneOf` branch.
+    pub fn add_one_of(&mut self, schema: impl Into<ObjectOrReference<Schema>>) -> &mut Self {
+        self.one_of.push(schema.into());
+        self
+    }
+
+    /// Adds an `anyOf` branch.
+    pub fn add_any_of(&mut self, schema: impl Into<ObjectOrReference<Schema>>) -> &mut Self {
+        self.any_of.push(schema.into());
+        self
+    }
+
+    /// Adds a property, replacing any existing property with the same name.
+    pub fn with_property(
+        mut self,
+        name: impl Into<String>,
+        schema: impl Into<ObjectOrReference<Schema>>,
+    ) -> Self {
+        self.add_property(name, schema);
+        self
+    }
+
+    /// Adds an `allOf` branch.
+    pub fn with_all_of(mut self, schema: impl Into<ObjectOrReference<Schema>>) -> Self {
+        self.add_all_of(schema);
+        self
+    }
+
+    /// Adds a `oneOf` branch.
+    pub fn with_one_of(mut self, schema: impl Into<ObjectOrReference<Schema>>) -> Self {
+        self.add_one_of(schema);
+        self
+    }
+
+    /// Adds an `anyOf` branch.
+    pub fn with_any_of(mut self, schema: impl Into<ObjectOrReference<Schema>>) -> Self {
+        self.add_any_of(schema);
+        self
+    }
+
+    /// Sets the schema that values must not match.
+    pub fn with_not(mut self, schema: impl Into<ObjectOrReference<Schema>>) -> Self {
+        self.not = Some(Box::new(schema.into()));
+        self
+    }
+
+    /// Creates a schema for a Rust type name, e.g. `i64` or `Vec<Option<String>>`.
+    ///
+    /// Supports strings, primitive integers and floats, `bool`, `Vec<T>` and `Option<T>`, which
+    /// becomes `anyOf: [T, {type: null}]` as in [`infer_from_values`](Self::infer_from_values).
+    /// Unsigned integers have a `minimum` of 0.
+    /// Returns `None` for other types.
+    pub fn from_type_name(type_name: &str) -> Option<Schema> {
+        let type_name = type_name.trim();
+
+        let generic_arg = |wrapper: &str| {
+            type_name
+                .strip_prefix(wrapper)?
+                .trim_start()
+                .strip_prefix('<')?
+                .strip_suffix('>')
+        };
+
+        if let Some(item) = generic_arg("Vec") {
+            return Schema::from_type_name(item).map(Schema::array);
+        }
+
+        if let Some(inner) = generic_arg("Option") {
+            return Schema::from_type_name(inner).map(nullable);
+        }
+
+        let with_format = |schema: Schema, format: KnownFormat| Schema {
+            format: Some(format),
+            ..schema
+        };
+        let unsigned = |format: KnownFormat| Schema {
+            minimum: Some(NumericBound::Integer(0)),
+            ..with_format(Schema::integer(), format)
+        };
+
+        let schema = match type_name {
+            "String" | "str" | "&str" | "char" => Schema::string(),
+            "bool" => Schema::boolean(),
+            "i8" | "i16" | "i32" => with_format(Schema::integer(), KnownFormat::Int32),
+            "i64" | "isize" => with_format(Schema::integer(), KnownFormat::Int64),
+            "u8" | "u16" => unsigned(KnownFormat::Int32),
+            "u32" | "u64" | "usize" => unsigned(KnownFormat::Int64),
+            "f32" => with_format(Schema::number(), KnownFormat::Float),
+            "f64" => with_format(Schema::number(), KnownFormat::Double),
+            _ => return None,
+        };
+
+        Some(schema)
+    }
+}
+
+/// Wraps a [`Schema`] so it can be used in hashed and sorted collections.
+///
+/// Keys compare by the schema's JSON representation and hash using [`Schema::content_hash`].
+#[derive(Debug, Clone)]
+pub struct SchemaKey(pub Schema);
+
+impl From<Schema> for SchemaKey {
+    fn from(schema: Schema) -> Self {
+        Self(schema)
+    }
+}
+
+impl Hash for SchemaKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.0.content_hash());
+    }
+}
+
+impl PartialEq for SchemaKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.canonical_json() == other.0.canonical_json()
+    }
+}
+
+impl Eq for SchemaKey {}
+
+impl PartialOrd for SchemaKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SchemaKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.canonical_json().cmp(&other.0.canonical_json())
+    }
+}
+
+impl Spec {
+    /// Returns the required fields of the named component schema, including those inherited
+    /// from its `allOf` branches, recursively, in alphabetical order.
+    ///
+    /// Fails with [`RefError::Circular`] if the schema inherits from itself.
+    pub fn get_all_required_fields_for_schema(&self, name: &str) -> Result<Vec<String>, RefError> {
+        let oor = ObjectOrReference::Ref {
+            ref_path: Ref::new(RefType::Schema, name).to_string(),
+        };
+
+        let mut fields = BTreeSet::new();
+        self.collect_required_fields(&oor, &mut vec![], &mut fields)?;
+
+        Ok(fields.into_iter().collect())
+    }
+
+    fn collect_required_fields(
+        &self,
+        oor: &ObjectOrReference<Schema>,
+        visiting: &mut Vec<String>,
+        fields: &mut BTreeSet<String>,
+    ) -> Result<(), RefError> {
+        let ref_path = match oor {
+            ObjectOrReference::Object(schema) => {
+                return self.collect_schema_required_fields(schema, visiting, fields)
+            }
+            ObjectOrReference::Ref { ref_path } => ref_path,
+            ObjectOrReference::SchemaRef {
+                ref_path,
+                overrides,
+            } => {
+                self.collect_schema_required_fields(overrides, visiting, fields)?;
+                ref_path
+            }
+        };
+
+        if visiting.contains(ref_path) {
+            return Err(RefError::Circular(ref_path.clone()));
+        }
+
+        // look up the component without resolving it, so that cycles are detected here
+        let refpath = ref_path.parse::<Ref>()?;
+        let component = match refpath.kind {
+            RefType::Schema => self
+                .components
+                .as_ref()
+                .and_then(|cs| cs.schemas.get(&refpath.name))
+                .ok_or_else(|| RefError::Unresolvable(ref_path.clone()))?,
+            typ => return Err(RefError::MismatchedType(typ, RefType::Schema)),
+        };
+
+        visiting.push(ref_path.clone());
+        self.collect_required_fields(component, visiting, fields)?;
+        visiting.pop();
+
+        Ok(())
+    }
+
+    fn collect_schema_required_fields(
+        &self,
+        schema: &Schema,
+        visiting: &mut Vec<String>,
+        fields: &mut BTreeSet<String>,
+    ) -> Result<(), RefError> {
+        fields.extend(schema.required.iter().cloned());
+
+        for branch in &schema.all_of {
+            self.collect_required_fields(branch, visiting, fields)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl FromRef for Schema {
@@ -220,4 +1731,738 @@ impl FromRef for Schema {
             typ => Err(RefError::MismatchedType(typ, RefType::Schema)),
         }
     }
+
+    /// Combines the sibling keywords with the referenced schema as
+    /// [`merge_all_of`](Schema::merge_all_of) does.
+    fn with_overrides(self, overrides: &Self) -> Self {
+        let mut merged = overrides.clone();
+        merged.merge(self);
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_and_write_only_compatibility() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Users, version: "1" }
+paths: {}
+components:
+  schemas:
+    User:
+      type: object
+      properties:
+        id: { type: integer, readOnly: true }
+        password: { type: string, writeOnly: true }
+        friends: { type: array, items: { $ref: '#/components/schemas/User' } }
+        name: { type: string }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let user = ObjectOrReference::<Schema>::Ref {
+            ref_path: "#/components/schemas/User".to_owned(),
+        }
+        .resolve(&spec)
+        .unwrap();
+
+        let err = user
+            .is_compatible_with(SchemaUsage::Request, &spec)
+            .unwrap_err();
+        assert_eq!(err.properties, ["/friends/*/id", "/id"]);
+        assert_eq!(
+            err.to_string(),
+            "readOnly properties not allowed in request: /friends/*/id, /id"
+        );
+
+        let err = user
+            .is_compatible_with(SchemaUsage::Response, &spec)
+            .unwrap_err();
+        assert_eq!(err.properties, ["/friends/*/password", "/password"]);
+
+        let name = user.properties["name"].resolve(&spec).unwrap();
+        name.is_compatible_with(SchemaUsage::Request, &spec)
+            .unwrap();
+    }
+
+    #[test]
+    fn schema_keys_dedupe_by_content() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let string = Schema {
+            schema_type: Some(Type::String),
+            ..Schema::default()
+        };
+        let email = Schema {
+            format: Some(KnownFormat::Email),
+            ..string.clone()
+        };
+
+        assert_eq!(string.content_hash(), string.clone().content_hash());
+        assert_ne!(string.content_hash(), email.content_hash());
+
+        let schemas = [string.clone(), email.clone(), string.clone()];
+
+        let set = schemas
+            .iter()
+            .cloned()
+            .map(SchemaKey)
+            .collect::<HashSet<_>>();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&SchemaKey(email)));
+
+        let set = schemas.into_iter().map(SchemaKey).collect::<BTreeSet<_>>();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn composition_builders() {
+        let base = ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Base".to_owned(),
+        };
+
+        let schema = Schema::object()
+            .with_all_of(base.clone())
+            .with_property("extra", Schema::string())
+            .with_not(Schema::boolean());
+
+        assert_eq!(schema.schema_type, Some(Type::Object));
+        assert_eq!(schema.all_of, [base]);
+        assert_eq!(
+            schema.properties["extra"],
+            ObjectOrReference::Object(Schema::string())
+        );
+        assert_eq!(
+            schema.not.as_deref(),
+            Some(&ObjectOrReference::Object(Schema::boolean()))
+        );
+
+        let mut schema = Schema::one_of(vec![Schema::string(), Schema::integer()]);
+        schema.add_one_of(Schema::array(Schema::string()));
+        assert_eq!(schema.schema_type, None);
+        assert_eq!(schema.one_of.len(), 3);
+
+        let schema = Schema::any_of(vec![Schema::number()]).with_any_of(Schema::boolean());
+        assert_eq!(
+            schema.any_of,
+            [
+                ObjectOrReference::Object(Schema::number()),
+                ObjectOrReference::Object(Schema::boolean())
+            ]
+        );
+
+        let schema = Schema::all_of(vec![Schema::object()]);
+        assert_eq!(schema.all_of.len(), 1);
+    }
+
+    #[test]
+    fn encodings_round_trip() {
+        for encoding in ["base16", "hex", "base64url", "quoted-printable", "7bit"] {
+            let parsed = serde_json::from_value::<Encoding>(encoding.into()).unwrap();
+            assert_eq!(parsed.as_str(), encoding);
+            assert_eq!(serde_json::to_value(&parsed).unwrap(), encoding);
+        }
+
+        assert_eq!(
+            Encoding::from("7bit".to_owned()),
+            Encoding::Other("7bit".to_owned())
+        );
+        assert_eq!(Encoding::Hex.normalize(), Encoding::Base16);
+        assert_eq!(Encoding::Base64.normalize(), Encoding::Base64);
+    }
+
+    #[test]
+    fn markdown_descriptions() {
+        let with_description = |description: &str| Schema {
+            description: Some(description.to_owned()),
+            ..Schema::default()
+        };
+
+        for markdown in [
+            "# Pet",
+            "Summary\n\n## Details",
+            "The **unique** identifier.",
+            "The __unique__ identifier.",
+            "An *optional* name.",
+            "An _optional_ name.",
+            "Set to `null` to clear.",
+            "See [the docs](https://example.com/docs).",
+        ] {
+            assert!(
+                with_description(markdown).has_markdown_description(),
+                "{}",
+                markdown
+            );
+        }
+
+        for plain in [
+            "The pet's name.",
+            "Matches user_id and pet_id.",
+            "Costs 2 * 3 * 4 credits.",
+            "Issue #42 tracks this.",
+            "Use [brackets] freely.",
+        ] {
+            assert!(
+                !with_description(plain).has_markdown_description(),
+                "{}",
+                plain
+            );
+        }
+
+        assert!(!Schema::default().has_markdown_description());
+    }
+
+    #[test]
+    fn empty_schemas() {
+        assert!(Schema::default().is_empty());
+
+        let annotated = serde_json::from_value::<Schema>(serde_json::json!({
+            "title": "Anything",
+            "description": "Accepts any value.",
+            "examples": [1, "one"],
+            "deprecated": true,
+        }))
+        .unwrap();
+        assert!(annotated.is_empty());
+
+        assert!(!Schema::string().is_empty());
+        assert!(!Schema::all_of(vec![Schema::default()]).is_empty());
+        assert!(!Schema {
+            min_length: Some(1),
+            ..Schema::default()
+        }
+        .is_empty());
+    }
+
+    #[test]
+    fn integer_ranges() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Ranges, version: "1" }
+paths: {}
+components:
+  schemas:
+    Positive: { type: integer, exclusiveMinimum: 0 }
+    Age:
+      type: integer
+      maximum: 150.5
+      multipleOf: 5
+      allOf:
+      - $ref: '#/components/schemas/Positive'
+    Score: { type: integer, minimum: -7, exclusiveMaximum: 100, multipleOf: 10 }
+    Ratio: { type: number, minimum: 0 }
+    Id: { type: integer }
+    Low: { type: integer, maximum: -9223372036854775807, multipleOf: 10 }
+    High: { type: integer, minimum: 9223372036854775806, multipleOf: 10 }
+    Legacy: { type: integer, minimum: 0, exclusiveMinimum: true, maximum: 9, exclusiveMaximum: false }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let range = |name: &str| {
+            Schema::from_ref(&spec, &format!("#/components/schemas/{}", name))
+                .unwrap()
+                .integer_range(&spec)
+        };
+
+        assert_eq!(
+            range("Positive"),
+            Some((Bound::Included(1), Bound::Unbounded))
+        );
+        assert_eq!(
+            range("Age"),
+            Some((Bound::Included(5), Bound::Included(150)))
+        );
+        assert_eq!(
+            range("Score"),
+            Some((Bound::Included(0), Bound::Included(90)))
+        );
+        assert_eq!(range("Ratio"), None);
+        assert_eq!(range("Id"), None);
+        assert_eq!(
+            range("Low"),
+            Some((Bound::Unbounded, Bound::Excluded(i64::MIN)))
+        );
+        assert_eq!(
+            range("High"),
+            Some((Bound::Excluded(i64::MAX), Bound::Unbounded))
+        );
+        assert_eq!(
+            range("Legacy"),
+            Some((Bound::Included(1), Bound::Included(9)))
+        );
+    }
+
+    #[test]
+    fn effective_bounds_through_all_of() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Bounds, version: "1" }
+paths: {}
+components:
+  schemas:
+    Name: { type: string, minLength: 1, maxLength: 64 }
+    ShortName:
+      maxLength: 16
+      allOf:
+      - $ref: '#/components/schemas/Name'
+      - { minLength: 2, maxLength: 32 }
+    Percent: { type: number, minimum: 0, maximum: 100 }
+    Discount:
+      maximum: 50.5
+      allOf:
+      - $ref: '#/components/schemas/Percent'
+    Broken:
+      allOf:
+      - $ref: '#/components/schemas/Missing'
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let schema = |name: &str| {
+            Schema::from_ref(&spec, &format!("#/components/schemas/{}", name)).unwrap()
+        };
+
+        let short_name = schema("ShortName");
+        assert_eq!(short_name.effective_max_length(&spec), Some(16));
+        assert_eq!(short_name.effective_min_length(&spec), Some(2));
+
+        let discount = schema("Discount");
+        assert_eq!(
+            discount.effective_maximum(&spec),
+            Some(NumericBound::Float(50.5))
+        );
+        assert_eq!(
+            discount.effective_minimum(&spec),
+            Some(NumericBound::Integer(0))
+        );
+        assert_eq!(discount.effective_max_length(&spec), None);
+
+        assert_eq!(schema("Broken").effective_minimum(&spec), None);
+    }
+
+    #[test]
+    fn apply_defaults_to_values() {
+        use serde_json::json;
+
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pages, version: "1" }
+paths: {}
+components:
+  schemas:
+    Paging:
+      type: object
+      properties:
+        size: { type: integer, default: 20 }
+    Query:
+      type: object
+      required: [term]
+      allOf:
+      - $ref: '#/components/schemas/Paging'
+      properties:
+        term: { type: string, default: "*" }
+        sort: { type: string, default: asc }
+        filters:
+          type: array
+          items:
+            type: object
+            properties:
+              op: { type: string, default: eq }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let query = Schema::from_ref(&spec, "#/components/schemas/Query").unwrap();
+
+        let mut value = json!({ "filters": [{ "field": "name" }, { "op": "ne" }] });
+        assert_eq!(query.apply_defaults(&mut value, &spec), 3);
+        assert_eq!(
+            value,
+            json!({
+                "size": 20,
+                "sort": "asc",
+                "filters": [{ "field": "name", "op": "eq" }, { "op": "ne" }],
+            })
+        );
+
+        assert_eq!(query.apply_defaults(&mut value, &spec), 0);
+        assert_eq!(query.apply_defaults(&mut json!("text"), &spec), 0);
+    }
+
+    #[test]
+    fn formats_round_trip() {
+        for format in ["int32", "date-time", "uri-reference", "ipv6", "time"] {
+            let parsed = serde_json::from_value::<KnownFormat>(format.into()).unwrap();
+            assert_eq!(parsed.as_str(), format);
+            assert_eq!(serde_json::to_value(&parsed).unwrap(), format);
+        }
+
+        assert_eq!(KnownFormat::from("uuid".to_owned()), KnownFormat::Uuid);
+        assert_eq!(
+            KnownFormat::from("uuuid".to_owned()),
+            KnownFormat::Custom("uuuid".to_owned())
+        );
+    }
+
+    #[test]
+    fn schema_or_bool_helpers() {
+        assert!(SchemaOrBool::any().is_allowed());
+        assert!(SchemaOrBool::none().is_forbidden());
+        assert!(SchemaOrBool::any().as_schema().is_none());
+
+        let mut sob = SchemaOrBool::Schema(Schema::string());
+        assert!(sob.is_allowed());
+        assert!(!sob.is_forbidden());
+        assert_eq!(sob.as_schema(), Some(&Schema::string()));
+
+        sob.as_schema_mut().unwrap().min_length = Some(1);
+        assert_eq!(sob.as_schema().unwrap().min_length, Some(1));
+    }
+
+    #[test]
+    fn type_compatibility() {
+        assert!(Schema::integer().is_integer_compatible());
+        assert!(Schema::integer().is_numeric_compatible());
+        assert!(!Schema::number().is_integer_compatible());
+        assert!(Schema::number().is_numeric_compatible());
+        assert!(!Schema::string().is_numeric_compatible());
+
+        let schema = Schema {
+            multiple_of: Some(1.into()),
+            ..Schema::default()
+        };
+        assert!(schema.is_integer_compatible());
+        assert!(schema.is_numeric_compatible());
+        assert!(!Schema::default().is_integer_compatible());
+
+        assert!(Schema::string().is_string_compatible());
+        assert!(Schema::array(Schema::string()).is_array_compatible());
+        assert!(Schema::object().is_object_compatible());
+        assert!(!Schema::object().is_array_compatible());
+
+        assert!(!Schema::string().is_nullable());
+        assert!(Schema::of_type(Type::Null).is_nullable());
+        let schema = Schema {
+            nullable: Some(true),
+            ..Schema::string()
+        };
+        assert!(schema.is_nullable());
+    }
+
+    #[test]
+    fn comment_round_trip() {
+        let json = serde_json::json!({ "type": "string", "$comment": "keep in sync with v2" });
+
+        let schema = serde_json::from_value::<Schema>(json.clone()).unwrap();
+        assert_eq!(schema.comment.as_deref(), Some("keep in sync with v2"));
+        assert_eq!(serde_json::to_value(&schema).unwrap(), json);
+    }
+
+    #[test]
+    fn unknown_keywords_round_trip() {
+        let json = serde_json::json!({
+            "type": "object",
+            "x-stoplight-id": "abc123",
+            "unevaluatedProperties": false,
+            "properties": { "kind": { "const": "pet", "x-order": 1 } }
+        });
+
+        let schema = serde_json::from_value::<Schema>(json.clone()).unwrap();
+        assert_eq!(
+            schema.unknown_keywords.keys().collect::<Vec<_>>(),
+            ["unevaluatedProperties", "x-stoplight-id"]
+        );
+        assert_eq!(schema.schema_type, Some(Type::Object));
+        assert_eq!(serde_json::to_value(&schema).unwrap(), json);
+
+        let kind = match &schema.properties["kind"] {
+            ObjectOrReference::Object(kind) => kind,
+            _ => panic!("kind should be inline"),
+        };
+        assert_eq!(kind.const_value, Some(serde_json::json!("pet")));
+        assert_eq!(kind.unknown_keywords["x-order"], 1);
+        assert!(!kind.is_empty());
+
+        let annotated = serde_json::from_value::<Schema>(serde_json::json!({ "x-order": 1 }));
+        assert!(annotated.unwrap().is_empty());
+
+        let null = serde_json::json!({ "const": null });
+        let schema = serde_json::from_value::<Schema>(null.clone()).unwrap();
+        assert_eq!(schema.const_value, Some(serde_json::Value::Null));
+        assert_eq!(serde_json::to_value(&schema).unwrap(), null);
+    }
+
+    #[test]
+    fn dynamic_ref_round_trip() {
+        let json = serde_json::json!({
+            "$dynamicAnchor": "node",
+            "type": "object",
+            "properties": { "children": { "type": "array", "items": { "$dynamicRef": "#node" } } }
+        });
+
+        let schema = serde_json::from_value::<Schema>(json.clone()).unwrap();
+        assert_eq!(schema.dynamic_anchor.as_deref(), Some("node"));
+        assert_eq!(serde_json::to_value(&schema).unwrap(), json);
+    }
+
+    #[test]
+    fn types_and_encodings_as_keys() {
+        use std::collections::HashSet;
+
+        let types = [Type::String, Type::Integer, Type::String]
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(types.len(), 2);
+
+        let encodings = [
+            Encoding::Base64,
+            Encoding::Other("7bit".to_owned()),
+            Encoding::Other("7bit".to_owned()),
+        ]
+        .into_iter()
+        .collect::<HashSet<_>>();
+        assert_eq!(encodings.len(), 2);
+    }
+
+    #[test]
+    fn ref_with_sibling_keywords() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name: { type: string }
+    Owner:
+      type: object
+      properties:
+        pet: { $ref: '#/components/schemas/Pet', description: The pet., deprecated: true }
+  headers:
+    Limit: { schema: { type: integer } }
+    Alias: { $ref: '#/components/headers/Limit', description: Ignored. }
+  parameters:
+    limit: { name: limit, in: query }
+    alias: { $ref: '#/components/parameters/limit', description: Ignored. }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+        let components = spec.components.as_ref().unwrap();
+
+        let owner = components.schemas["Owner"].resolve(&spec).unwrap();
+        let pet = &owner.properties["pet"];
+        match pet {
+            ObjectOrReference::SchemaRef {
+                ref_path,
+                overrides,
+            } => {
+                assert_eq!(ref_path, "#/components/schemas/Pet");
+                assert_eq!(overrides.description.as_deref(), Some("The pet."));
+            }
+            _ => panic!("sibling keywords were dropped"),
+        }
+
+        let json = serde_json::to_value(pet).unwrap();
+        assert_eq!(json["$ref"], "#/components/schemas/Pet");
+        assert_eq!(json["deprecated"], true);
+        assert_eq!(
+            serde_json::from_value::<ObjectOrReference<Schema>>(json).unwrap(),
+            *pet
+        );
+
+        let resolved = pet.resolve(&spec).unwrap();
+        assert_eq!(resolved.description.as_deref(), Some("The pet."));
+        assert_eq!(resolved.deprecated, Some(true));
+        assert_eq!(resolved.required, vec!["name".to_owned()]);
+
+        // other components do not apply sibling keywords
+        let alias = components.headers["Alias"].resolve(&spec).unwrap();
+        assert_eq!(alias.description, None);
+
+        // siblings that do not form a component are ignored
+        assert_eq!(
+            components.parameters["alias"],
+            ObjectOrReference::Ref {
+                ref_path: "#/components/parameters/limit".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn enum_values_through_composition() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  schemas:
+    Color: { enum: [red, green, blue] }
+    Warm: { enum: [red, orange] }
+    Any: { type: string }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let color = || ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Color".to_owned(),
+        };
+        let warm = || ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Warm".to_owned(),
+        };
+        let any = || ObjectOrReference::Ref {
+            ref_path: "#/components/schemas/Any".to_owned(),
+        };
+
+        let schema = Schema::default().with_all_of(color()).with_all_of(warm());
+        assert_eq!(schema.all_enum_values(&spec), [serde_json::json!("red")]);
+
+        let schema = Schema::default().with_one_of(color()).with_one_of(warm());
+        assert_eq!(
+            schema.all_enum_values(&spec),
+            ["red", "green", "blue", "orange"].map(serde_json::Value::from)
+        );
+
+        let schema = Schema::default().with_one_of(color()).with_one_of(any());
+        assert!(schema.all_enum_values(&spec).is_empty());
+
+        let schema = Schema {
+            enum_values: vec![serde_json::json!("green")],
+            ..Schema::default()
+        }
+        .with_all_of(warm());
+        assert!(schema.all_enum_values(&spec).is_empty());
+    }
+
+    #[test]
+    fn schemas_from_type_names() {
+        let int64 = Schema {
+            format: Some(KnownFormat::Int64),
+            ..Schema::integer()
+        };
+
+        assert_eq!(Schema::from_type_name("String"), Some(Schema::string()));
+        assert_eq!(Schema::from_type_name("i64"), Some(int64.clone()));
+        assert_eq!(
+            Schema::from_type_name("u16").unwrap().minimum,
+            Some(NumericBound::Integer(0))
+        );
+        assert_eq!(
+            Schema::from_type_name("f32").unwrap().format,
+            Some(KnownFormat::Float)
+        );
+        assert_eq!(
+            Schema::from_type_name("Vec< Option<i64> >"),
+            Some(Schema::array(Schema {
+                any_of: vec![int64.into(), Schema::of_type(Type::Null).into()],
+                ..Schema::default()
+            }))
+        );
+
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Types, version: "1" }
+paths: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+        let schema = Schema::from_type_name("Option<String>").unwrap();
+        assert!(schema.strict_validates(&serde_json::Value::Null, &spec));
+        assert!(schema.strict_validates(&serde_json::json!("pet"), &spec));
+        assert!(!schema.strict_validates(&serde_json::json!(1), &spec));
+        assert_eq!(Schema::from_type_name("HashMap<String, i32>"), None);
+        assert_eq!(Schema::from_type_name("Vec<Pet>"), None);
+    }
+
+    #[test]
+    fn resolve_ref_paths_of_any_type() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  schemas:
+    Id: { type: string, format: uuid }
+  parameters:
+    PetId: { name: petId, in: path, schema: { $ref: '#/components/schemas/Id' } }
+    Untyped: { name: q, in: query }
+  headers:
+    Limit: { schema: { type: integer } }
+  examples:
+    Pet: { value: {} }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let id = Schema::resolve_ref_path("#/components/schemas/Id", &spec).unwrap();
+        assert_eq!(id.format, Some(KnownFormat::Uuid));
+        assert_eq!(
+            Schema::resolve_ref_path("#/components/parameters/PetId", &spec).unwrap(),
+            id
+        );
+        assert_eq!(
+            Schema::resolve_ref_path("#/components/headers/Limit", &spec).unwrap(),
+            Schema::integer()
+        );
+        assert_eq!(
+            Schema::resolve_ref_path("#/components/parameters/Untyped", &spec).unwrap_err(),
+            RefError::MismatchedType(RefType::Parameter, RefType::Schema)
+        );
+        assert_eq!(
+            Schema::resolve_ref_path("#/components/examples/Pet", &spec).unwrap_err(),
+            RefError::MismatchedType(RefType::Example, RefType::Schema)
+        );
+    }
+
+    #[test]
+    fn required_fields_through_all_of() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  schemas:
+    Entity: { required: [id] }
+    Named: { allOf: [{ $ref: '#/components/schemas/Entity' }], required: [name] }
+    Pet:
+      allOf:
+      - $ref: '#/components/schemas/Named'
+      - $ref: '#/components/schemas/Entity'
+      - { required: [species, name] }
+      required: [age]
+    Loop: { allOf: [{ $ref: '#/components/schemas/Pool' }] }
+    Pool: { $ref: '#/components/schemas/Loop', required: [x] }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.get_all_required_fields_for_schema("Pet").unwrap(),
+            ["age", "id", "name", "species"]
+        );
+        assert_eq!(
+            spec.get_all_required_fields_for_schema("Loop").unwrap_err(),
+            RefError::Circular("#/components/schemas/Loop".to_owned())
+        );
+        assert_eq!(
+            spec.get_all_required_fields_for_schema("Nope").unwrap_err(),
+            RefError::Unresolvable("#/components/schemas/Nope".to_owned())
+        );
+    }
 }
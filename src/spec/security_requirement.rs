@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use derive_more::{Display, Error};
+
+use super::{OperationRef, SecurityScheme, Spec};
+
+/// Lists the required security schemes to execute an operation.
+///
+/// Each name MUST correspond to a security scheme which is declared in the
+/// [Security Schemes](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#componentsSecuritySchemes)
+/// under the Components Object. The value is a list of scope names required for the execution,
+/// which is empty for security schemes that do not use scopes.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#securityRequirementObject>.
+pub type SecurityRequirement = BTreeMap<String, Vec<String>>;
+
+/// A security requirement whose OAuth2 scopes do not match its security scheme.
+///
+/// `operation_id` is `None` for spec-level requirements and operations without an ID.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum OAuth2ScopeError {
+    /// The requirement names a security scheme that is not declared in the components.
+    #[display(fmt = "Security scheme {} is not declared", scheme_name)]
+    UndeclaredScheme {
+        operation_id: Option<String>,
+        scheme_name: String,
+    },
+
+    /// The requirement lists a scope that none of the OAuth2 scheme's flows define.
+    #[display(
+        fmt = "Scope {} is not defined by security scheme {}",
+        scope_name,
+        scheme_name
+    )]
+    UndefinedScope {
+        operation_id: Option<String>,
+        scheme_name: String,
+        scope_name: String,
+    },
+}
+
+impl Spec {
+    /// Adds a spec-level security requirement, which applies to all operations that do not
+    /// declare their own `security`. Does nothing if the requirement is already present.
+    pub fn add_security_globally(&mut self, requirement: SecurityRequirement) {
+        if !self.security.contains(&requirement) {
+            self.security.push(requirement);
+        }
+    }
+
+    /// Sets `security` to the given requirement on every operation that does not declare its
+    /// own.
+    ///
+    /// Operations with an explicit `security` are left unchanged, including those with an
+    /// empty list, which opt out of security.
+    pub fn require_auth_on_all_operations(&mut self, requirement: SecurityRequirement) {
+        let ops = self
+            .paths
+            .values_mut()
+            .flat_map(|path_item| path_item.operations_mut());
+
+        for op in ops {
+            if op.security.is_none() {
+                op.security = Some(vec![requirement.clone()]);
+            }
+        }
+    }
+
+    /// Checks that the scopes listed by spec-level and operation security requirements are
+    /// defined by the flows of their OAuth2 security schemes.
+    ///
+    /// Scopes of other kinds of security scheme are not checked, since OpenAPI 3.1 lets them
+    /// list arbitrary roles. Requirements naming undeclared or unresolvable schemes are reported
+    /// as [`OAuth2ScopeError::UndeclaredScheme`].
+    pub fn validate_oauth2_scopes(&self) -> Vec<OAuth2ScopeError> {
+        let root = self.security.iter().map(|requirement| (None, requirement));
+        let ops = self.operation_refs().flat_map(|op| {
+            let operation_id = op.operation.operation_id.as_deref();
+
+            op.operation
+                .security
+                .iter()
+                .flatten()
+                .map(move |requirement| (operation_id, requirement))
+        });
+
+        let mut errors = vec![];
+
+        for (operation_id, requirement) in root.chain(ops) {
+            for (scheme_name, scopes) in requirement {
+                let scheme = self
+                    .components
+                    .as_ref()
+                    .and_then(|components| components.security_schemes.get(scheme_name))
+                    .and_then(|oor| oor.resolve(self).ok());
+
+                let flows = match scheme {
+                    Some(SecurityScheme::OAuth2 { flows }) => flows,
+                    Some(_) => continue,
+                    None => {
+                        errors.push(OAuth2ScopeError::UndeclaredScheme {
+                            operation_id: operation_id.map(str::to_owned),
+                            scheme_name: scheme_name.clone(),
+                        });
+                        continue;
+                    }
+                };
+
+                for scope in scopes.iter().filter(|scope| !flows.has_scope(scope)) {
+                    errors.push(OAuth2ScopeError::UndefinedScope {
+                        operation_id: operation_id.map(str::to_owned),
+                        scheme_name: scheme_name.clone(),
+                        scope_name: scope.clone(),
+                    });
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Returns the operations with no security requirements, either from their own `security`
+    /// or from the spec-level fallback.
+    pub fn operations_without_security(&self) -> Vec<OperationRef<'_>> {
+        self.operation_refs()
+            .filter(|op| op.operation.effective_security(self).is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn enforce_security() {
+        let mut spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Security, version: "1" }
+paths:
+  /pets:
+    get: { responses: {} }
+    post:
+      security: [{ oauth: [write] }]
+      responses: {}
+  /health:
+    get:
+      security: []
+      responses: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let unsecured = |spec: &Spec| {
+            spec.operations_without_security()
+                .iter()
+                .map(|op| format!("{} {}", op.method, op.path))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(unsecured(&spec), ["GET /health", "GET /pets"]);
+
+        let api_key = btreemap! { "apiKey".to_owned() => vec![] };
+
+        spec.add_security_globally(api_key.clone());
+        spec.add_security_globally(api_key.clone());
+        assert_eq!(spec.security.len(), 1);
+        assert_eq!(unsecured(&spec), ["GET /health"]);
+
+        spec.security.clear();
+        spec.require_auth_on_all_operations(api_key.clone());
+
+        let pets = &spec.paths["/pets"];
+        assert_eq!(pets.get.as_ref().unwrap().security, Some(vec![api_key]));
+        assert_eq!(
+            pets.post.as_ref().unwrap().security,
+            Some(vec![
+                btreemap! { "oauth".to_owned() => vec!["write".to_owned()] }
+            ])
+        );
+        assert_eq!(unsecured(&spec), ["GET /health"]);
+    }
+
+    #[test]
+    fn oauth2_scopes() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+security: [{ oauth: [read:pets, admin] }]
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses: {}
+    post:
+      operationId: createPet
+      security: [{ oauth: [write:pets] }, { apiKey: [editor] }, { basic: [] }]
+      responses: {}
+components:
+  securitySchemes:
+    apiKey: { type: apiKey, name: key, in: header }
+    oauth:
+      type: oauth2
+      flows:
+        implicit:
+          authorizationUrl: https://example.com/oauth/authorize
+          scopes: { read:pets: Read pets }
+        clientCredentials:
+          tokenUrl: https://example.com/oauth/token
+          scopes: { write:pets: Write pets }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.validate_oauth2_scopes(),
+            [
+                OAuth2ScopeError::UndefinedScope {
+                    operation_id: None,
+                    scheme_name: "oauth".to_owned(),
+                    scope_name: "admin".to_owned(),
+                },
+                OAuth2ScopeError::UndeclaredScheme {
+                    operation_id: Some("createPet".to_owned()),
+                    scheme_name: "basic".to_owned(),
+                },
+            ]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::Flows;
+use super::{Flows, FromRef, Ref, RefError, RefType, Spec};
 
 /// Defines a security scheme that can be used by the operations. Supported schemes are HTTP
 /// authentication, an API key (either as a header or as a query parameter), OAuth2's common flows
@@ -11,7 +11,6 @@ use super::Flows;
 ///
 /// [RFC6749]: https://tools.ietf.org/html/rfc6749
 /// [OpenID Connect Discovery]: https://tools.ietf.org/html/draft-ietf-oauth-discovery-06
-
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(tag = "type")]
@@ -40,6 +39,23 @@ pub enum SecurityScheme {
     },
 }
 
+impl FromRef for SecurityScheme {
+    fn from_ref(spec: &Spec, path: &str) -> Result<Self, RefError> {
+        let refpath = path.parse::<Ref>()?;
+
+        match refpath.kind {
+            RefType::SecurityScheme => spec
+                .components
+                .as_ref()
+                .and_then(|cs| cs.security_schemes.get(&refpath.name))
+                .ok_or_else(|| RefError::Unresolvable(path.to_owned()))
+                .and_then(|oor| oor.resolve(spec)),
+
+            typ => Err(RefError::MismatchedType(typ, RefType::SecurityScheme)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,7 +64,7 @@ mod tests {
     #[test]
     fn test_http_basic_deser() {
         const HTTP_BASIC_SAMPLE: &str = r#"{"type": "http", "scheme": "basic"}"#;
-        let obj: SecurityScheme = serde_json::from_str(&HTTP_BASIC_SAMPLE).unwrap();
+        let obj: SecurityScheme = serde_json::from_str(HTTP_BASIC_SAMPLE).unwrap();
 
         assert!(matches!(
             obj,
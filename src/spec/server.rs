@@ -1,7 +1,10 @@
 use std::collections::BTreeMap;
 
+use derive_more::{Display, Error};
 use serde::{Deserialize, Serialize};
 
+use super::Spec;
+
 /// An object representing a Server.
 ///
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#serverObject>.
@@ -47,3 +50,220 @@ pub struct ServerVariable {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 }
+
+/// Problems with the variables of a server URL template.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum ServerVariableError {
+    #[display(
+        fmt = "Server variable {} is used in {} but not declared",
+        variable,
+        url
+    )]
+    MissingDeclaration { url: String, variable: String },
+
+    #[display(fmt = "Server variable {} of {} has an empty default", variable, url)]
+    EmptyDefault { url: String, variable: String },
+
+    #[display(
+        fmt = "Default {:?} of server variable {} of {} is not one of its enum values",
+        default,
+        variable,
+        url
+    )]
+    DefaultNotInEnum {
+        url: String,
+        variable: String,
+        default: String,
+    },
+}
+
+impl Server {
+    /// Checks that every variable in the URL template is declared and that every declared
+    /// variable has a usable default.
+    pub fn validate_variables(&self) -> Vec<ServerVariableError> {
+        let mut errors = vec![];
+
+        for variable in template_variables(&self.url) {
+            if !self.variables.contains_key(variable) {
+                errors.push(ServerVariableError::MissingDeclaration {
+                    url: self.url.clone(),
+                    variable: variable.to_owned(),
+                });
+            }
+        }
+
+        for (name, variable) in &self.variables {
+            if variable.default.is_empty() {
+                errors.push(ServerVariableError::EmptyDefault {
+                    url: self.url.clone(),
+                    variable: name.clone(),
+                });
+            } else if !variable.substitutions_enum.is_empty()
+                && !variable.substitutions_enum.contains(&variable.default)
+            {
+                errors.push(ServerVariableError::DefaultNotInEnum {
+                    url: self.url.clone(),
+                    variable: name.clone(),
+                    default: variable.default.clone(),
+                });
+            }
+        }
+
+        errors
+    }
+}
+
+impl Spec {
+    /// Checks the variables of every server in the spec, as collected by
+    /// [`collect_all_servers`](Self::collect_all_servers).
+    pub fn validate_server_variables(&self) -> Vec<ServerVariableError> {
+        self.collect_all_servers()
+            .into_iter()
+            .flat_map(Server::validate_variables)
+            .collect()
+    }
+
+    /// Replaces the URL of every root, path item and operation server whose URL is `old_url`,
+    /// returning the number of servers updated.
+    pub fn rewrite_server_url(&mut self, old_url: &str, new_url: &str) -> usize {
+        let mut count = 0;
+
+        let mut rewrite = |servers: &mut Vec<Server>| {
+            for server in servers.iter_mut().filter(|server| server.url == old_url) {
+                server.url = new_url.to_owned();
+                count += 1;
+            }
+        };
+
+        rewrite(&mut self.servers);
+
+        for path_item in self.paths.values_mut() {
+            rewrite(&mut path_item.servers);
+
+            for op in path_item.operations_mut() {
+                rewrite(&mut op.servers);
+            }
+        }
+
+        count
+    }
+
+    /// Replaces the root servers with a single server at the given URL, and removes path item
+    /// and operation servers so that it applies everywhere.
+    pub fn set_primary_server(&mut self, url: &str) {
+        self.servers = vec![Server {
+            url: url.to_owned(),
+            ..Server::default()
+        }];
+
+        for path_item in self.paths.values_mut() {
+            path_item.servers.clear();
+
+            for op in path_item.operations_mut() {
+                op.servers.clear();
+            }
+        }
+    }
+}
+
+/// Returns the names of the `{variable}` placeholders in a URL or path template.
+pub(crate) fn template_variables(template: &str) -> Vec<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}'))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_variable_names() {
+        assert_eq!(
+            template_variables("{scheme}://{host}/v1/{id}"),
+            ["scheme", "host", "id"]
+        );
+        assert!(template_variables("/pets").is_empty());
+    }
+
+    #[test]
+    fn server_variable_validation() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+servers:
+- url: "{scheme}://{host}/v1"
+  variables:
+    scheme: { default: https, enum: [https, wss] }
+- url: "https://{region}.example.com"
+  variables:
+    region: { default: eu, enum: [us] }
+    unused: { default: "" }
+paths: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.validate_server_variables(),
+            [
+                ServerVariableError::MissingDeclaration {
+                    url: "{scheme}://{host}/v1".to_owned(),
+                    variable: "host".to_owned(),
+                },
+                ServerVariableError::DefaultNotInEnum {
+                    url: "https://{region}.example.com".to_owned(),
+                    variable: "region".to_owned(),
+                    default: "eu".to_owned(),
+                },
+                ServerVariableError::EmptyDefault {
+                    url: "https://{region}.example.com".to_owned(),
+                    variable: "unused".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rewrite_servers() {
+        let mut spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+servers:
+- url: http://localhost:8080
+- url: https://staging.example.com
+paths:
+  /pets:
+    servers: [{ url: http://localhost:8080 }]
+    get:
+      servers: [{ url: http://localhost:8080 }, { url: http://localhost:9090 }]
+      responses: {}
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.rewrite_server_url("http://localhost:8080", "https://api.example.com"),
+            3
+        );
+        assert_eq!(spec.rewrite_server_url("http://localhost:8080", "x"), 0);
+        assert_eq!(
+            spec.paths["/pets"].servers[0].url,
+            "https://api.example.com"
+        );
+
+        spec.set_primary_server("https://api.example.com/v2");
+        assert_eq!(
+            spec.collect_all_servers()
+                .into_iter()
+                .map(|server| server.url.as_str())
+                .collect::<Vec<_>>(),
+            ["https://api.example.com/v2"]
+        );
+    }
+}
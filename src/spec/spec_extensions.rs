@@ -0,0 +1,18 @@
+//! Support for [Specification Extensions](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#specificationExtensions).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer};
+
+/// Deserializes the flattened remaining fields of an object, keeping only those prefixed with
+/// `x-`.
+pub(crate) fn deserialize<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, serde_json::Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut extensions = BTreeMap::<String, serde_json::Value>::deserialize(deserializer)?;
+    extensions.retain(|key, _| key.starts_with("x-"));
+    Ok(extensions)
+}
@@ -0,0 +1,185 @@
+//! Property statistics for API design analysis.
+
+use super::{ObjectOrReference, Schema, Spec};
+
+/// Counts of the object properties declared by schemas.
+///
+/// A property is counted once in `total_properties`, once in either `required_count` or
+/// `optional_count`, and in each of `read_only_count`, `write_only_count` and `nullable_count`
+/// that applies to it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PropertyStats {
+    pub total_properties: usize,
+    pub required_count: usize,
+    pub optional_count: usize,
+    pub read_only_count: usize,
+    pub write_only_count: usize,
+    pub nullable_count: usize,
+}
+
+impl PropertyStats {
+    fn add(&mut self, other: PropertyStats) {
+        self.total_properties += other.total_properties;
+        self.required_count += other.required_count;
+        self.optional_count += other.optional_count;
+        self.read_only_count += other.read_only_count;
+        self.write_only_count += other.write_only_count;
+        self.nullable_count += other.nullable_count;
+    }
+}
+
+impl Spec {
+    /// Counts the properties declared by all component schemas.
+    ///
+    /// See [`Spec::property_stats_by_schema`] for the schemas that are counted.
+    pub fn count_required_vs_optional_properties(&self) -> PropertyStats {
+        let mut stats = PropertyStats::default();
+
+        for (_, schema_stats) in self.property_stats_by_schema() {
+            stats.add(schema_stats);
+        }
+
+        stats
+    }
+
+    /// Counts the properties declared by each component schema, in name order.
+    ///
+    /// Properties of inline subschemas (e.g., nested objects and array items) count towards the
+    /// component schema containing them, while referenced schemas are only counted under their
+    /// own name. Unresolvable component schemas and properties are skipped.
+    pub fn property_stats_by_schema(&self) -> Vec<(String, PropertyStats)> {
+        let components = match &self.components {
+            Some(components) => components,
+            None => return vec![],
+        };
+
+        components
+            .schemas
+            .iter()
+            .filter_map(|(name, oor)| {
+                let schema = oor.resolve(self).ok()?;
+
+                let mut stats = PropertyStats::default();
+                self.collect_property_stats(&schema, &mut stats);
+
+                Some((name.clone(), stats))
+            })
+            .collect()
+    }
+
+    fn collect_property_stats(&self, schema: &Schema, stats: &mut PropertyStats) {
+        for (name, property) in &schema.properties {
+            let property = match property.resolve(self) {
+                Ok(property) => property,
+                Err(_) => continue,
+            };
+
+            stats.total_properties += 1;
+
+            if schema.required.contains(name) {
+                stats.required_count += 1;
+            } else {
+                stats.optional_count += 1;
+            }
+
+            if property.read_only == Some(true) {
+                stats.read_only_count += 1;
+            }
+
+            if property.write_only == Some(true) {
+                stats.write_only_count += 1;
+            }
+
+            if property.is_nullable() {
+                stats.nullable_count += 1;
+            }
+        }
+
+        for sub in schema.subschemas() {
+            if let ObjectOrReference::Object(sub)
+            | ObjectOrReference::SchemaRef { overrides: sub, .. } = sub
+            {
+                self.collect_property_stats(sub, stats);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn property_stats() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id, name]
+      properties:
+        id: { type: integer, readOnly: true }
+        name: { type: string }
+        tag: { type: string, nullable: true }
+        owner: { $ref: '#/components/schemas/Owner' }
+        toys:
+          type: array
+          items:
+            type: object
+            properties:
+              name: { type: string }
+    Owner:
+      type: object
+      properties:
+        password: { type: string, writeOnly: true }
+        nickname: { type: string, nullable: true }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let by_schema = spec.property_stats_by_schema();
+        assert_eq!(
+            by_schema,
+            [
+                (
+                    "Owner".to_owned(),
+                    PropertyStats {
+                        total_properties: 2,
+                        required_count: 0,
+                        optional_count: 2,
+                        read_only_count: 0,
+                        write_only_count: 1,
+                        nullable_count: 1,
+                    }
+                ),
+                (
+                    "Pet".to_owned(),
+                    PropertyStats {
+                        total_properties: 6,
+                        required_count: 2,
+                        optional_count: 4,
+                        read_only_count: 1,
+                        write_only_count: 0,
+                        nullable_count: 1,
+                    }
+                ),
+            ]
+        );
+
+        assert_eq!(
+            spec.count_required_vs_optional_properties(),
+            PropertyStats {
+                total_properties: 8,
+                required_count: 2,
+                optional_count: 6,
+                read_only_count: 1,
+                write_only_count: 1,
+                nullable_count: 2,
+            }
+        );
+    }
+}
@@ -1,5 +1,10 @@
+use std::collections::BTreeMap;
+
+use derive_more::{Display, Error};
 use serde::{Deserialize, Serialize};
 
+use super::Spec;
+
 /// Adds metadata to a single tag that is used by the
 /// [Operation Object](https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#operationObject).
 /// It is not mandatory to have a Tag Object per tag defined in the Operation Object instances.
@@ -21,3 +26,115 @@ pub struct Tag {
 
     // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/HEAD/versions/3.1.0.md#specificationExtensions}
 }
+
+/// Mismatches between the root `tags` array and the tags used by operations.
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error)]
+pub enum TagConsistencyError {
+    /// A tag is used by operations but not declared.
+    #[display(fmt = "Tag {} is used but not declared", tag)]
+    Undeclared {
+        tag: String,
+
+        /// Paths of the operations using the tag.
+        paths: Vec<String>,
+    },
+
+    /// A tag is declared but not used by any operation.
+    #[display(fmt = "Tag {} is declared but not used", tag)]
+    Unused { tag: String },
+
+    /// A tag is declared more than once.
+    #[display(fmt = "Tag {} is declared more than once", tag)]
+    Duplicate { tag: String },
+}
+
+impl Spec {
+    /// Checks that the root `tags` array declares each tag used by operations exactly once, and
+    /// only those tags.
+    pub fn check_tag_consistency(&self) -> Vec<TagConsistencyError> {
+        let mut used = BTreeMap::<&str, Vec<String>>::new();
+
+        for op in self.operation_refs() {
+            for tag in &op.operation.tags {
+                let paths = used.entry(tag).or_default();
+
+                if !paths.iter().any(|path| path == op.path) {
+                    paths.push(op.path.to_owned());
+                }
+            }
+        }
+
+        let mut errors = vec![];
+        let mut declared = Vec::<&str>::new();
+
+        for tag in &self.tags {
+            if declared.contains(&tag.name.as_str()) {
+                errors.push(TagConsistencyError::Duplicate {
+                    tag: tag.name.clone(),
+                });
+                continue;
+            }
+
+            declared.push(&tag.name);
+
+            if !used.contains_key(tag.name.as_str()) {
+                errors.push(TagConsistencyError::Unused {
+                    tag: tag.name.clone(),
+                });
+            }
+        }
+
+        for (tag, paths) in used {
+            if !declared.contains(&tag) {
+                errors.push(TagConsistencyError::Undeclared {
+                    tag: tag.to_owned(),
+                    paths,
+                });
+            }
+        }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_consistency() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Pets, version: "1" }
+tags:
+- name: pets
+- name: stores
+- name: pets
+paths:
+  /pets:
+    get: { tags: [pets, animals], responses: {} }
+    post: { tags: [animals], responses: {} }
+  /pets/{petId}:
+    get: { tags: [animals], responses: {} }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            spec.check_tag_consistency(),
+            [
+                TagConsistencyError::Unused {
+                    tag: "stores".to_owned()
+                },
+                TagConsistencyError::Duplicate {
+                    tag: "pets".to_owned()
+                },
+                TagConsistencyError::Undeclared {
+                    tag: "animals".to_owned(),
+                    paths: vec!["/pets".to_owned(), "/pets/{petId}".to_owned()],
+                },
+            ]
+        );
+    }
+}
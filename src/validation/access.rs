@@ -0,0 +1,113 @@
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate, ValidationError, ValidationErrorKind};
+
+/// Where a validated value is sent, which determines whether `readOnly` and `writeOnly`
+/// properties may appear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Values sent by clients; `readOnly` properties must not appear.
+    Request,
+
+    /// Values sent by servers; `writeOnly` properties must not appear.
+    Response,
+}
+
+/// Options used when building validation trees.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationContext {
+    /// Mode used to enforce `readOnly` and `writeOnly`. Neither is enforced if unset.
+    pub mode: Option<ValidationMode>,
+}
+
+impl ValidationContext {
+    /// Context for validating request values.
+    pub fn request() -> Self {
+        Self {
+            mode: Some(ValidationMode::Request),
+        }
+    }
+
+    /// Context for validating response values.
+    pub fn response() -> Self {
+        Self {
+            mode: Some(ValidationMode::Response),
+        }
+    }
+}
+
+/// Checks that object values do not contain properties that are `readOnly` in requests or
+/// `writeOnly` in responses. Non-object values are ignored.
+#[derive(Debug, Clone)]
+pub struct AccessMode {
+    mode: ValidationMode,
+    properties: Vec<String>,
+}
+
+impl AccessMode {
+    pub fn new(mode: ValidationMode, properties: Vec<String>) -> Self {
+        Self { mode, properties }
+    }
+}
+
+impl Validate for AccessMode {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        let obj = match val.as_object() {
+            Some(obj) => obj,
+            None => return Ok(()),
+        };
+
+        let prop = match self.properties.iter().find(|prop| obj.contains_key(*prop)) {
+            Some(prop) => prop,
+            None => return Ok(()),
+        };
+
+        let (kind, detail) = match self.mode {
+            ValidationMode::Request => (
+                ValidationErrorKind::ReadOnlyViolation,
+                format!(
+                    "read-only property \"{}\" is not allowed in a request",
+                    prop
+                ),
+            ),
+            ValidationMode::Response => (
+                ValidationErrorKind::WriteOnlyViolation,
+                format!(
+                    "write-only property \"{}\" is not allowed in a response",
+                    prop
+                ),
+            ),
+        };
+
+        Err(ValidationError::new(&path.extend(prop), kind, detail).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{super::tests::*, *};
+
+    #[test]
+    fn access_mode_validation() {
+        let v = AccessMode::new(ValidationMode::Request, vec![s("id")]);
+
+        valid_vs_invalid!(
+            v,
+            &[&json!({ "name": "milk" }), &OBJ_EMPTY, &INTEGER],
+            &[&json!({ "id": 1, "name": "milk" })],
+        );
+
+        let err = v
+            .validate(&json!({ "id": 1 }), Path::default())
+            .unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/id");
+        assert_eq!(err.kind, ValidationErrorKind::ReadOnlyViolation);
+        assert_eq!(
+            err.detail,
+            "read-only property \"id\" is not allowed in a request"
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Validation of the `default` and `examples` values declared in schemas.
+
+use super::{ValidationError, ValidationTree};
+use crate::{spec::ObjectOrReference, Schema, Spec};
+
+/// A `default` or example value in a component schema that does not match its schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefaultValidationError {
+    /// Name of the component schema.
+    pub schema: String,
+
+    /// JSON Pointer, relative to the component schema, to the (sub)schema declaring the value,
+    /// e.g. `/properties/age`.
+    pub pointer: String,
+
+    /// Index in `examples` of the invalid example, or `None` for `default`.
+    pub example: Option<usize>,
+
+    /// Reasons the value does not match.
+    pub errors: Vec<ValidationError>,
+}
+
+impl Schema {
+    /// Validates `default` against this schema.
+    ///
+    /// Returns `None` if no `default` is set, or if validators could not be built for the
+    /// schema (e.g., due to an invalid `pattern`). Otherwise, returns the validation errors,
+    /// which are empty if the default is valid.
+    pub fn validate_default(&self, spec: &Spec) -> Option<Vec<ValidationError>> {
+        let default = self.default.as_ref()?;
+        let valtree = ValidationTree::from_schema(self, spec).ok()?;
+
+        Some(match valtree.validate(default) {
+            Ok(_) => vec![],
+            Err(err) => err.into_validation_errors(),
+        })
+    }
+
+    /// Validates each of `examples` against this schema.
+    ///
+    /// Returns the indexes of invalid examples along with their validation errors. Returns an
+    /// empty list if validators could not be built for the schema.
+    pub fn validate_examples(&self, spec: &Spec) -> Vec<(usize, Vec<ValidationError>)> {
+        if self.examples.is_empty() {
+            return vec![];
+        }
+
+        let valtree = match ValidationTree::from_schema(self, spec) {
+            Ok(valtree) => valtree,
+            Err(_) => return vec![],
+        };
+
+        self.examples
+            .iter()
+            .enumerate()
+            .filter_map(|(index, example)| {
+                let err = valtree.validate(example).err()?;
+                Some((index, err.into_validation_errors()))
+            })
+            .collect()
+    }
+
+    /// Validates each of `examples` against this schema. Alias of
+    /// [`validate_examples`](Self::validate_examples).
+    pub fn validate_own_examples(&self, spec: &Spec) -> Vec<(usize, Vec<ValidationError>)> {
+        self.validate_examples(spec)
+    }
+}
+
+impl Spec {
+    /// Validates the `default` and `examples` values of all component schemas, including those
+    /// of their inline subschemas, against the schemas declaring them.
+    pub fn validate_all_defaults(&self) -> Vec<DefaultValidationError> {
+        let mut errors = vec![];
+
+        let schemas = self
+            .components
+            .iter()
+            .flat_map(|components| &components.schemas);
+
+        for (name, oor) in schemas {
+            if let ObjectOrReference::Object(schema)
+            | ObjectOrReference::SchemaRef {
+                overrides: schema, ..
+            } = oor
+            {
+                visit_inline_schemas(schema, String::new(), &mut |schema, pointer| {
+                    collect_default_errors(self, name, schema, pointer, &mut errors)
+                });
+            }
+        }
+
+        errors
+    }
+
+    /// Validates the `examples` values of all component schemas, including those of their
+    /// inline subschemas, against the schemas declaring them. See
+    /// [`validate_all_defaults`](Self::validate_all_defaults), which also validates defaults.
+    pub fn validate_all_examples(&self) -> Vec<DefaultValidationError> {
+        self.validate_all_defaults()
+            .into_iter()
+            .filter(|err| err.example.is_some())
+            .collect()
+    }
+}
+
+fn collect_default_errors(
+    spec: &Spec,
+    name: &str,
+    schema: &Schema,
+    pointer: &str,
+    errors: &mut Vec<DefaultValidationError>,
+) {
+    let error = |example, errs| DefaultValidationError {
+        schema: name.to_owned(),
+        pointer: pointer.to_owned(),
+        example,
+        errors: errs,
+    };
+
+    if let Some(errs) = schema
+        .validate_default(spec)
+        .filter(|errs| !errs.is_empty())
+    {
+        errors.push(error(None, errs));
+    }
+
+    for (index, errs) in schema.validate_examples(spec) {
+        errors.push(error(Some(index), errs));
+    }
+}
+
+/// Calls `visit` with a schema and each of its inline subschemas, along with their JSON Pointers
+/// relative to the schema.
+pub(crate) fn visit_inline_schemas(
+    schema: &Schema,
+    pointer: String,
+    visit: &mut dyn FnMut(&Schema, &str),
+) {
+    visit(schema, &pointer);
+
+    // references are skipped; named schemas are visited on their own
+    let mut visit = |oor: &ObjectOrReference<Schema>, sub_pointer: String| {
+        if let ObjectOrReference::Object(sub)
+        | ObjectOrReference::SchemaRef { overrides: sub, .. } = oor
+        {
+            visit_inline_schemas(sub, sub_pointer, visit);
+        }
+    };
+
+    for (prop, oor) in &schema.properties {
+        let prop = prop.replace('~', "~0").replace('/', "~1");
+        visit(oor, format!("{}/properties/{}", pointer, prop));
+    }
+
+    if let Some(items) = &schema.items {
+        visit(items, format!("{}/items", pointer));
+    }
+
+    let composites = [
+        ("prefixItems", &schema.prefix_items),
+        ("allOf", &schema.all_of),
+        ("oneOf", &schema.one_of),
+        ("anyOf", &schema.any_of),
+    ];
+
+    for (keyword, oors) in composites {
+        for (index, oor) in oors.iter().enumerate() {
+            visit(oor, format!("{}/{}/{}", pointer, keyword, index));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{spec::FromRef, validation::ValidationErrorKind};
+
+    #[test]
+    fn defaults_and_examples() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Defaults, version: "1" }
+paths: {}
+components:
+  schemas:
+    Page:
+      type: object
+      properties:
+        size: { type: integer, minimum: 1, default: 0, examples: [10, "ten", 20] }
+        sort: { type: string, enum: [asc, desc], default: asc }
+      examples:
+      - { size: 10 }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let page = Schema::from_ref(&spec, "#/components/schemas/Page").unwrap();
+        assert_eq!(page.validate_default(&spec), None);
+        assert!(page.validate_examples(&spec).is_empty());
+
+        let size = page.properties["size"].resolve(&spec).unwrap();
+        let errs = size.validate_default(&spec).unwrap();
+        assert_eq!(errs[0].kind, ValidationErrorKind::MinimumViolation);
+        let errs = size.validate_examples(&spec);
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].0, 1);
+
+        let sort = page.properties["sort"].resolve(&spec).unwrap();
+        assert_eq!(sort.validate_default(&spec), Some(vec![]));
+
+        let errors = spec.validate_all_examples();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].pointer, "/properties/size");
+        assert_eq!(errors[0].example, Some(1));
+
+        let errors = spec.validate_all_defaults();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].schema, "Page");
+        assert_eq!(errors[0].pointer, "/properties/size");
+        assert_eq!(errors[0].example, None);
+        assert_eq!(errors[1].example, Some(1));
+        assert_eq!(errors[1].errors[0].detail, "expected integer, got string");
+    }
+
+    #[test]
+    fn examples_of_composite_schemas() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Defaults, version: "1" }
+paths: {}
+components:
+  schemas:
+    Named:
+      type: object
+      required: [name]
+      properties: { name: { type: string } }
+    Pet:
+      allOf:
+      - $ref: '#/components/schemas/Named'
+      - { type: object, properties: { age: { type: integer } } }
+      examples:
+      - { name: Rex, age: 3 }
+      - { age: 3 }
+      - { name: Rex, age: three }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let pet = Schema::from_ref(&spec, "#/components/schemas/Pet").unwrap();
+        assert_eq!(
+            pet.validate_own_examples(&spec),
+            pet.validate_examples(&spec)
+        );
+        let errs = pet.validate_examples(&spec);
+        assert_eq!(
+            errs.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert!(errs.iter().all(|(_, errs)| !errs.is_empty()));
+
+        let errors = spec.validate_all_defaults();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| (err.schema.as_str(), err.example))
+                .collect::<Vec<_>>(),
+            [("Pet", Some(1)), ("Pet", Some(2))]
+        );
+    }
+
+    #[test]
+    fn recursive_schemas_are_skipped() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Defaults, version: "1" }
+paths: {}
+components:
+  schemas:
+    Node:
+      type: object
+      default: {}
+      examples: [{ child: {} }]
+      properties:
+        child: { $ref: '#/components/schemas/Node' }
+        size: { type: integer, default: small }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let node = Schema::from_ref(&spec, "#/components/schemas/Node").unwrap();
+        assert_eq!(node.validate_default(&spec), None);
+        assert!(node.validate_examples(&spec).is_empty());
+
+        let errors = spec.validate_all_defaults();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].pointer, "/properties/size");
+    }
+}
@@ -0,0 +1,107 @@
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE},
+    Engine as _,
+};
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate, ValidationError, ValidationErrorKind, ValidationTree};
+use crate::spec::SchemaEncoding as Encoding;
+
+/// Checks that string values are valid in their declared `contentEncoding`.
+///
+/// Only the `base64` and `base64url` encodings are checked. Non-string values are ignored.
+///
+/// If a `contentSchema` is set for JSON content, the decoded string is parsed as JSON and
+/// validated against it.
+#[derive(Debug)]
+pub struct ContentEncoding {
+    encoding: Encoding,
+    json_content: Option<ValidationTree>,
+}
+
+impl ContentEncoding {
+    pub fn new(encoding: Encoding) -> Self {
+        Self {
+            encoding,
+            json_content: None,
+        }
+    }
+
+    /// Validates decoded content as JSON against the given tree.
+    pub fn with_json_content(mut self, valtree: ValidationTree) -> Self {
+        self.json_content = Some(valtree);
+        self
+    }
+}
+
+impl Validate for ContentEncoding {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        let s = match val.as_str() {
+            Some(s) => s,
+            None => return Ok(()),
+        };
+
+        let (engine, name) = match self.encoding {
+            Encoding::Base64 => (&STANDARD, "base64"),
+            Encoding::Base64Url => (&URL_SAFE, "base64url"),
+            _ => return Ok(()),
+        };
+
+        let decoded = engine.decode(s).map_err(|err| {
+            ValidationError::new(
+                &path,
+                ValidationErrorKind::EncodingViolation,
+                format!("{} is not valid {}: {}", val, name, err),
+            )
+        })?;
+
+        let valtree = match &self.json_content {
+            Some(valtree) => valtree,
+            None => return Ok(()),
+        };
+
+        let content = serde_json::from_slice::<JsonValue>(&decoded).map_err(|err| {
+            ValidationError::new(
+                &path,
+                ValidationErrorKind::ContentMediaTypeViolation,
+                format!("decoded content of {} is not valid JSON: {}", val, err),
+            )
+        })?;
+
+        valtree.validate_inner(&content, path)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{super::tests::*, *};
+
+    #[test]
+    fn base64_validation() {
+        let v = ContentEncoding::new(Encoding::Base64);
+
+        valid_vs_invalid!(
+            v,
+            &[
+                &json!("c3RyaW5n"),
+                &json!("YT8+"),
+                &json!(""),
+                &INTEGER,
+                &NULL
+            ],
+            &[&json!("not base64!"), &json!("YT8-"), &json!("c3RyaW5")],
+        );
+
+        let v = ContentEncoding::new(Encoding::Base64Url);
+
+        valid_vs_invalid!(v, &[&json!("YT8-")], &[&json!("YT8+")]);
+
+        let v = ContentEncoding::new(Encoding::Hex);
+
+        valid_vs_invalid!(v, &[&json!("not hex")], &[]);
+    }
+}
@@ -0,0 +1,259 @@
+//! Validation of the `enum` values declared in schemas against their other constraints.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use url::Url;
+
+use super::{
+    defaults::visit_inline_schemas, Path, ValidationError, ValidationErrorKind, ValidationTree,
+};
+use crate::{
+    spec::{is_email, KnownFormat, ObjectOrReference},
+    Schema, Spec,
+};
+
+static RE_DATE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\d{4}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])$").unwrap());
+
+static RE_DATE_TIME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"^\d{4}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])",
+        r"[Tt]([01]\d|2[0-3]):[0-5]\d:([0-5]\d|60)(\.\d+)?",
+        r"([Zz]|[+-]([01]\d|2[0-3]):[0-5]\d)$",
+    ))
+    .unwrap()
+});
+
+static RE_UUID: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[0-9a-fA-F]{8}-([0-9a-fA-F]{4}-){3}[0-9a-fA-F]{12}$").unwrap());
+
+/// An `enum` value in a component schema that does not satisfy the schema's other constraints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumValidationError {
+    /// Name of the component schema.
+    pub schema: String,
+
+    /// JSON Pointer, relative to the component schema, to the (sub)schema declaring the value,
+    /// e.g. `/properties/status`.
+    pub pointer: String,
+
+    /// The invalid enum value.
+    pub value: JsonValue,
+
+    /// Constraints the value violates.
+    pub errors: Vec<ValidationError>,
+}
+
+impl Spec {
+    /// Validates the `enum` values of all component schemas, including those of their inline
+    /// subschemas, against the `type`, `format`, `pattern`, `minimum`/`maximum` and
+    /// `minLength`/`maxLength` of the schemas declaring them.
+    ///
+    /// Only the `int32`, `int64`, `byte`, `date`, `date-time`, `uuid`, `email`, `uri`, `ipv4`
+    /// and `ipv6` formats are checked.
+    pub fn validate_enum_values(&self) -> Vec<EnumValidationError> {
+        let mut errors = vec![];
+
+        let schemas = self
+            .components
+            .iter()
+            .flat_map(|components| &components.schemas);
+
+        for (name, oor) in schemas {
+            if let ObjectOrReference::Object(schema)
+            | ObjectOrReference::SchemaRef {
+                overrides: schema, ..
+            } = oor
+            {
+                visit_inline_schemas(schema, String::new(), &mut |schema, pointer| {
+                    collect_enum_errors(self, name, schema, pointer, &mut errors)
+                });
+            }
+        }
+
+        errors
+    }
+}
+
+fn collect_enum_errors(
+    spec: &Spec,
+    name: &str,
+    schema: &Schema,
+    pointer: &str,
+    errors: &mut Vec<EnumValidationError>,
+) {
+    if schema.enum_values.is_empty() {
+        return;
+    }
+
+    let constraints = Schema {
+        schema_type: schema.schema_type,
+        pattern: schema.pattern.clone(),
+        minimum: schema.minimum,
+        maximum: schema.maximum,
+        exclusive_minimum: schema.exclusive_minimum,
+        exclusive_maximum: schema.exclusive_maximum,
+        min_length: schema.min_length,
+        max_length: schema.max_length,
+        ..Schema::default()
+    };
+
+    let valtree = match ValidationTree::from_schema(&constraints, spec) {
+        Ok(valtree) => valtree,
+        Err(_) => return,
+    };
+
+    for value in &schema.enum_values {
+        // the validator does not implement OpenAPI 3.0 `nullable`
+        if value.is_null() && schema.is_nullable() {
+            continue;
+        }
+
+        let mut errs = match valtree.validate(value) {
+            Ok(_) => vec![],
+            Err(err) => err.into_validation_errors(),
+        };
+
+        if let Some(detail) = schema
+            .format
+            .as_ref()
+            .and_then(|format| format_violation(format, value))
+        {
+            errs.push(ValidationError::new(
+                &Path::default(),
+                ValidationErrorKind::FormatViolation,
+                detail,
+            ));
+        }
+
+        if !errs.is_empty() {
+            errors.push(EnumValidationError {
+                schema: name.to_owned(),
+                pointer: pointer.to_owned(),
+                value: value.clone(),
+                errors: errs,
+            });
+        }
+    }
+}
+
+/// Describes how a value violates a format, if it does. Values of a type the format does not
+/// apply to are ignored.
+fn format_violation(format: &KnownFormat, value: &JsonValue) -> Option<String> {
+    let valid = match (format, value) {
+        (KnownFormat::Int32, JsonValue::Number(num)) => {
+            num.as_i64().map_or(false, |num| i32::try_from(num).is_ok())
+        }
+        (KnownFormat::Int64, JsonValue::Number(num)) => num.is_i64(),
+        (KnownFormat::Byte, JsonValue::String(s)) => STANDARD.decode(s).is_ok(),
+        (KnownFormat::Date, JsonValue::String(s)) => RE_DATE.is_match(s),
+        (KnownFormat::DateTime, JsonValue::String(s)) => RE_DATE_TIME.is_match(s),
+        (KnownFormat::Uuid, JsonValue::String(s)) => RE_UUID.is_match(s),
+        (KnownFormat::Email, JsonValue::String(s)) => is_email(s),
+        (KnownFormat::Uri, JsonValue::String(s)) => Url::parse(s).is_ok(),
+        (KnownFormat::Ipv4, JsonValue::String(s)) => s.parse::<Ipv4Addr>().is_ok(),
+        (KnownFormat::Ipv6, JsonValue::String(s)) => s.parse::<Ipv6Addr>().is_ok(),
+        _ => true,
+    };
+
+    (!valid).then(|| format!("{} is not a valid {}", value, format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enum_values_against_constraints() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.1.0"
+info: { title: Enums, version: "1" }
+paths: {}
+components:
+  schemas:
+    Count:
+      type: integer
+      enum: [1, "two", 3]
+    Order:
+      type: object
+      properties:
+        status: { type: string, pattern: "^[a-z]+$", maxLength: 8, enum: [placed, Shipped, delivered] }
+        size: { type: integer, format: int32, minimum: 1, enum: [1, 2, 3000000000] }
+        day: { type: string, format: date, enum: ["2024-01-01", "2024-13-01"] }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let errors = spec
+            .validate_enum_values()
+            .into_iter()
+            .map(|err| {
+                let kinds = err.errors.iter().map(|err| err.kind).collect::<Vec<_>>();
+                (err.schema, err.pointer, err.value, kinds)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            [
+                (
+                    "Count".to_owned(),
+                    String::new(),
+                    "two".into(),
+                    vec![ValidationErrorKind::TypeMismatch],
+                ),
+                (
+                    "Order".to_owned(),
+                    "/properties/day".to_owned(),
+                    "2024-13-01".into(),
+                    vec![ValidationErrorKind::FormatViolation],
+                ),
+                (
+                    "Order".to_owned(),
+                    "/properties/size".to_owned(),
+                    3000000000_u64.into(),
+                    vec![ValidationErrorKind::FormatViolation],
+                ),
+                (
+                    "Order".to_owned(),
+                    "/properties/status".to_owned(),
+                    "Shipped".into(),
+                    vec![ValidationErrorKind::PatternMismatch],
+                ),
+                (
+                    "Order".to_owned(),
+                    "/properties/status".to_owned(),
+                    "delivered".into(),
+                    vec![ValidationErrorKind::MaxLengthViolation],
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn nullable_enum_values() {
+        let spec = crate::from_reader(
+            r#"openapi: "3.0.3"
+info: { title: Enums, version: "1" }
+paths: {}
+components:
+  schemas:
+    Color: { type: string, nullable: true, enum: [red, null] }
+    Size: { type: string, enum: [small, null] }
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let errors = spec.validate_enum_values();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].schema, "Size");
+        assert_eq!(errors[0].value, JsonValue::Null);
+        assert_eq!(errors[0].errors[0].kind, ValidationErrorKind::InvalidNull);
+    }
+}
@@ -0,0 +1,51 @@
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate, ValidationError, ValidationErrorKind};
+
+/// Checks that values are one of a fixed set.
+#[derive(Debug, Clone)]
+pub struct EnumValues {
+    values: Vec<JsonValue>,
+}
+
+impl EnumValues {
+    pub fn new(values: Vec<JsonValue>) -> Self {
+        Self { values }
+    }
+}
+
+impl Validate for EnumValues {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        if self.values.contains(val) {
+            return Ok(());
+        }
+
+        let allowed = self
+            .values
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Err(ValidationError::new(
+            &path,
+            ValidationErrorKind::EnumMismatch,
+            format!("{} is not one of [{}]", val, allowed),
+        )
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{super::tests::*, *};
+
+    #[test]
+    fn enum_validation() {
+        let v = EnumValues::new(vec![json!(1), json!("im a string")]);
+
+        valid_vs_invalid!(v, &[&INTEGER, &STRING], &[&NULL, &FLOAT, &json!("1")]);
+    }
+}
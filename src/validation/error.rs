@@ -5,7 +5,7 @@ use http::{Method, StatusCode};
 use serde_json::Value as JsonValue;
 
 use super::Path;
-use crate::spec::{Error as SchemaError, SchemaType};
+use crate::spec::Error as SchemaError;
 
 #[derive(Debug)]
 pub struct AggregateError {
@@ -24,6 +24,18 @@ impl AggregateError {
     pub fn push(&mut self, err: Error) {
         self.errors.push(err)
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
 }
 
 impl fmt::Display for AggregateError {
@@ -39,6 +51,110 @@ impl fmt::Display for AggregateError {
     }
 }
 
+/// Category of a schema validation failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum ValidationErrorKind {
+    #[display(fmt = "type mismatch")]
+    TypeMismatch,
+
+    #[display(fmt = "invalid null")]
+    InvalidNull,
+
+    #[display(fmt = "required field missing")]
+    RequiredFieldMissing,
+
+    #[display(fmt = "undocumented field")]
+    UndocumentedField,
+
+    #[display(fmt = "minimum violation")]
+    MinimumViolation,
+
+    #[display(fmt = "maximum violation")]
+    MaximumViolation,
+
+    #[display(fmt = "minimum length violation")]
+    MinLengthViolation,
+
+    #[display(fmt = "maximum length violation")]
+    MaxLengthViolation,
+
+    #[display(fmt = "pattern mismatch")]
+    PatternMismatch,
+
+    #[display(fmt = "enum mismatch")]
+    EnumMismatch,
+
+    #[display(fmt = "not violation")]
+    NotViolation,
+
+    #[display(fmt = "encoding violation")]
+    EncodingViolation,
+
+    #[display(fmt = "content media type violation")]
+    ContentMediaTypeViolation,
+
+    #[display(fmt = "read-only violation")]
+    ReadOnlyViolation,
+
+    #[display(fmt = "write-only violation")]
+    WriteOnlyViolation,
+
+    #[display(fmt = "composition mismatch")]
+    CompositionMismatch,
+
+    #[display(fmt = "format violation")]
+    FormatViolation,
+}
+
+/// A single failure found while validating a value against a schema.
+///
+/// Displays as `{path}: {detail}`, the instance location followed by the message, which is the
+/// basic output format used by most JSON Schema validators.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub struct ValidationError {
+    /// JSON Pointer to the offending value (e.g., `/address/zipCode`). Empty for the root value.
+    pub path: String,
+
+    /// Category of the failure.
+    pub kind: ValidationErrorKind,
+
+    /// Human-readable explanation of the failure.
+    pub detail: String,
+}
+
+impl ValidationError {
+    pub fn new(path: &Path, kind: ValidationErrorKind, detail: impl Into<String>) -> Self {
+        Self {
+            path: path.to_json_pointer(),
+            kind,
+            detail: detail.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.detail)
+        } else {
+            write!(f, "{}: {}", self.path, self.detail)
+        }
+    }
+}
+
+/// Returns the JSON Schema type name of a value, for use in error messages.
+pub(crate) fn json_type_name(val: &JsonValue) -> &'static str {
+    match val {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(num) if num.is_i64() || num.is_u64() => "integer",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    }
+}
+
 /// Validation Errors
 #[derive(Debug, Display, Error)]
 pub enum Error {
@@ -54,26 +170,27 @@ pub enum Error {
     #[display(fmt = "Not JSON")]
     NotJson,
 
-    #[display(fmt = "{} is not a {:?}", _0, _1)]
-    TypeMismatch(Path, SchemaType),
-
-    #[display(fmt = "Array item type mismatch: {}", _0)]
-    ArrayItemTypeMismatch(JsonValue, #[error(source)] Box<Error>),
-
-    #[display(fmt = "Undocumented field: {}", _0)]
-    UndocumentedField(#[error(not(source))] String),
+    #[display(fmt = "{}", _0)]
+    Invalid(#[error(not(source))] ValidationError),
 
     #[display(fmt = "Status mismatch: expected {}; got {}", _0, _1)]
     StatusMismatch(StatusCode, StatusCode),
 
-    #[display(fmt = "Required field missing: {}", _0)]
-    RequiredFieldMissing(#[error(not(source))] Path),
-
-    #[display(fmt = "Type did not match any `anyOf` variant: {}\n{}", _0, _1)]
+    #[display(fmt = "Type did not match any `oneOf`/`anyOf` variant: {}\n{}", _0, _1)]
     OneOfNoMatch(Path, AggregateError),
 
-    #[display(fmt = "Non-nullable field was null: {}", _0)]
-    InvalidNull(#[error(not(source))] Path),
+    #[display(fmt = "Type did not match all `allOf` variants: {}\n{}", _0, _1)]
+    AllOfMismatch(Path, AggregateError),
+
+    #[display(fmt = "Type matched more than one `oneOf` variant: {} {:?}", _0, _1)]
+    MultipleMatchingBranches(Path, Vec<usize>),
+
+    #[display(fmt = "{}[{}]: {}", keyword, index, source)]
+    Branch {
+        keyword: &'static str,
+        index: usize,
+        source: Box<Error>,
+    },
 
     #[display(fmt = "Operation not found: {} {}", _0, _1)]
     OperationNotFound(Method, String),
@@ -86,4 +203,84 @@ pub enum Error {
 
     #[display(fmt = "Invalid parameter location: {}", _0)]
     InvalidParameterLocation(#[error(not(source))] String),
+
+    /// The schema uses a keyword whose semantics are not implemented, so values cannot be
+    /// validated against it.
+    #[display(fmt = "Unsupported keyword: {}", _0)]
+    UnsupportedKeyword(#[error(not(source))] &'static str),
+}
+
+impl Error {
+    /// Returns the structured schema validation failure, if this is one.
+    pub fn as_invalid(&self) -> Option<&ValidationError> {
+        match self {
+            Self::Invalid(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Flattens this error into structured validation failures.
+    ///
+    /// The failures of every `allOf` branch are included. `oneOf` and `anyOf` failures are
+    /// reported as a single [`ValidationErrorKind::CompositionMismatch`], as are any other
+    /// errors.
+    pub fn into_validation_errors(self) -> Vec<ValidationError> {
+        match self {
+            Self::Invalid(err) => vec![err],
+            Self::Branch { source, .. } => source.into_validation_errors(),
+            Self::AllOfMismatch(_, errors) => errors
+                .errors
+                .into_iter()
+                .flat_map(Error::into_validation_errors)
+                .collect(),
+            Self::OneOfNoMatch(ref path, _) | Self::MultipleMatchingBranches(ref path, _) => {
+                vec![ValidationError::new(
+                    path,
+                    ValidationErrorKind::CompositionMismatch,
+                    self.to_string(),
+                )]
+            }
+            err => vec![ValidationError::new(
+                &Path::default(),
+                ValidationErrorKind::CompositionMismatch,
+                err.to_string(),
+            )],
+        }
+    }
+}
+
+impl From<ValidationError> for Error {
+    fn from(err: ValidationError) -> Self {
+        Self::Invalid(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation_error_display() {
+        let path = Path::default().extend("address").extend("zipCode");
+        let err = ValidationError::new(
+            &path,
+            ValidationErrorKind::TypeMismatch,
+            "expected integer, got string",
+        );
+
+        assert_eq!(err.path, "/address/zipCode");
+        assert_eq!(
+            err.to_string(),
+            "/address/zipCode: expected integer, got string"
+        );
+
+        let err = ValidationError::new(
+            &Path::default(),
+            ValidationErrorKind::TypeMismatch,
+            "expected object, got null",
+        );
+
+        assert_eq!(err.path, "");
+        assert_eq!(err.to_string(), "expected object, got null");
+    }
 }
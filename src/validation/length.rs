@@ -0,0 +1,70 @@
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate, ValidationError, ValidationErrorKind};
+
+/// Checks the character length of string values. Non-string values are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct StringLength {
+    min_length: Option<u64>,
+    max_length: Option<u64>,
+}
+
+impl StringLength {
+    pub fn new(min_length: Option<u64>, max_length: Option<u64>) -> Self {
+        Self {
+            min_length,
+            max_length,
+        }
+    }
+}
+
+impl Validate for StringLength {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        let len = match val.as_str() {
+            Some(s) => s.chars().count() as u64,
+            None => return Ok(()),
+        };
+
+        if let Some(min) = self.min_length {
+            if len < min {
+                return Err(ValidationError::new(
+                    &path,
+                    ValidationErrorKind::MinLengthViolation,
+                    format!("{} is shorter than {} characters", val, min),
+                )
+                .into());
+            }
+        }
+
+        if let Some(max) = self.max_length {
+            if len > max {
+                return Err(ValidationError::new(
+                    &path,
+                    ValidationErrorKind::MaxLengthViolation,
+                    format!("{} is longer than {} characters", val, max),
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{super::tests::*, *};
+
+    #[test]
+    fn string_length_validation() {
+        let v = StringLength::new(Some(2), Some(11));
+
+        valid_vs_invalid!(
+            v,
+            &[&STRING, &json!("ab"), &INTEGER],
+            &[&json!("a"), &json!("im a longer string")],
+        );
+    }
+}
@@ -8,16 +8,38 @@ use serde_json::Value as JsonValue;
 #[macro_use]
 mod test_macros;
 
+mod access;
+mod defaults;
+mod encoding;
+mod enum_values;
+mod enumeration;
 mod error;
+mod length;
+mod not;
 mod path;
+mod pattern;
+mod range;
 mod required;
+mod success;
 mod r#type;
+mod unsupported;
 mod validator;
 
+pub use access::*;
+pub use defaults::*;
+pub use encoding::*;
+pub use enum_values::*;
+pub use enumeration::*;
 pub use error::*;
+pub use length::*;
+pub use not::*;
 pub use path::Path;
+pub use pattern::*;
 pub use r#type::*;
+pub use range::*;
 pub use required::*;
+pub use success::*;
+pub use unsupported::*;
 pub use validator::*;
 
 pub trait Validate: Debug {
@@ -0,0 +1,31 @@
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate, ValidationError, ValidationErrorKind, ValidationTree};
+
+/// Checks that values do _not_ match a schema.
+#[derive(Debug)]
+pub struct Not {
+    title: Option<String>,
+    valtree: ValidationTree,
+}
+
+impl Not {
+    pub fn new(title: Option<String>, valtree: ValidationTree) -> Self {
+        Self { title, valtree }
+    }
+}
+
+impl Validate for Not {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        if self.valtree.validate_inner(val, path.clone()).is_err() {
+            return Ok(());
+        }
+
+        let detail = match &self.title {
+            Some(title) => format!("{} unexpectedly matched `not` schema '{}'", val, title),
+            None => format!("{} unexpectedly matched `not` schema", val),
+        };
+
+        Err(ValidationError::new(&path, ValidationErrorKind::NotViolation, detail).into())
+    }
+}
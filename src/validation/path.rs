@@ -14,6 +14,11 @@ impl Path {
         }
     }
 
+    /// Returns an empty path that displays its parts joined by `sep`.
+    pub fn with_separator(sep: char) -> Self {
+        Self::new(sep)
+    }
+
     pub fn is_root(&self) -> bool {
         self.parts.is_empty()
     }
@@ -34,6 +39,40 @@ impl Path {
         new.parts.push(part.into());
         new
     }
+
+    /// Returns the path parts.
+    pub fn segments(&self) -> &[String] {
+        &self.parts
+    }
+
+    /// Returns the path part at `index`, if there is one.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.parts.get(index).map(String::as_str)
+    }
+
+    /// Returns the first path part, if there is one.
+    pub fn first(&self) -> Option<&str> {
+        self.parts.first().map(String::as_str)
+    }
+
+    /// Returns the last path part, if there is one.
+    pub fn last(&self) -> Option<&str> {
+        self.parts.last().map(String::as_str)
+    }
+
+    /// Iterates over the path parts.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().map(String::as_str)
+    }
+
+    /// Formats the path as a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901), regardless
+    /// of separator. The root path is formatted as an empty string.
+    pub fn to_json_pointer(&self) -> String {
+        self.parts
+            .iter()
+            .map(|part| format!("/{}", part.replace('~', "~0").replace('/', "~1")))
+            .collect()
+    }
 }
 
 impl Default for Path {
@@ -45,6 +84,30 @@ impl Default for Path {
     }
 }
 
+impl FromIterator<String> for Path {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        Self {
+            parts: iter.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<Vec<String>> for Path {
+    fn from(parts: Vec<String>) -> Self {
+        Self {
+            parts,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<Path> for Vec<String> {
+    fn from(path: Path) -> Self {
+        path.parts
+    }
+}
+
 impl fmt::Display for Path {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.is_root() {
@@ -61,3 +124,46 @@ impl PartialEq for Path {
         self.parts == other.parts
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_pointer_escapes_parts() {
+        assert_eq!(Path::new('.').to_json_pointer(), "");
+
+        let path = Path::new('.').extend("items").extend("0").extend("a/b~c");
+        assert_eq!(path.to_string(), "items.0.a/b~c");
+        assert_eq!(path.to_json_pointer(), "/items/0/a~1b~0c");
+    }
+
+    #[test]
+    fn segment_accessors() {
+        let path = Path::new('.').extend("items").extend("a.b");
+
+        assert_eq!(path.segments(), ["items", "a.b"]);
+        assert_eq!(path.get(1), Some("a.b"));
+        assert_eq!(path.get(2), None);
+        assert_eq!(path.first(), Some("items"));
+        assert_eq!(path.last(), Some("a.b"));
+        assert_eq!(path.iter().collect::<Vec<_>>(), ["items", "a.b"]);
+
+        assert_eq!(Path::default().first(), None);
+    }
+
+    #[test]
+    fn conversions() {
+        let parts = vec!["a".to_owned(), "b".to_owned()];
+
+        let path = Path::from(parts.clone());
+        assert_eq!(path.to_string(), "a/b");
+        assert_eq!(path, parts.iter().cloned().collect::<Path>());
+        assert_eq!(Vec::from(path), parts);
+
+        let mut path = Path::with_separator('.');
+        path.push("a");
+        path.push("b");
+        assert_eq!(path.to_string(), "a.b");
+    }
+}
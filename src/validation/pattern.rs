@@ -0,0 +1,58 @@
+use regex::Regex;
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate, ValidationError, ValidationErrorKind};
+use crate::spec::{pattern::compile_ecma_regex, SchemaError};
+
+/// Checks string values against an ECMA-262 regular expression. Non-string values are ignored.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pattern: String,
+    regex: Regex,
+}
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Result<Self, SchemaError> {
+        let regex = compile_ecma_regex(pattern)
+            .map_err(|_| SchemaError::InvalidPattern(pattern.to_owned()))?;
+
+        Ok(Self {
+            pattern: pattern.to_owned(),
+            regex,
+        })
+    }
+}
+
+impl Validate for Pattern {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        match val.as_str() {
+            Some(s) if !self.regex.is_match(s) => Err(ValidationError::new(
+                &path,
+                ValidationErrorKind::PatternMismatch,
+                format!("{} does not match pattern \"{}\"", val, self.pattern),
+            )
+            .into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{super::tests::*, *};
+
+    #[test]
+    fn pattern_validation() {
+        let v = Pattern::new("^[a-z ]+$").unwrap();
+
+        valid_vs_invalid!(
+            v,
+            &[&STRING, &INTEGER, &NULL],
+            &[&json!("Im a string"), &json!("")],
+        );
+
+        assert!(Pattern::new("(unclosed").is_err());
+    }
+}
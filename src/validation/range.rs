@@ -0,0 +1,142 @@
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate, ValidationError, ValidationErrorKind};
+use crate::spec::NumericBound;
+
+/// Checks numeric values against inclusive and exclusive bounds. Non-numeric values are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct NumericRange {
+    minimum: Option<NumericBound>,
+    maximum: Option<NumericBound>,
+    exclusive_minimum: Option<NumericBound>,
+    exclusive_maximum: Option<NumericBound>,
+}
+
+impl NumericRange {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn minimum(self, minimum: impl Into<NumericBound>) -> Self {
+        Self {
+            minimum: Some(minimum.into()),
+            ..self
+        }
+    }
+
+    pub fn maximum(self, maximum: impl Into<NumericBound>) -> Self {
+        Self {
+            maximum: Some(maximum.into()),
+            ..self
+        }
+    }
+
+    pub fn exclusive_minimum(self, exclusive_minimum: impl Into<NumericBound>) -> Self {
+        Self {
+            exclusive_minimum: Some(exclusive_minimum.into()),
+            ..self
+        }
+    }
+
+    pub fn exclusive_maximum(self, exclusive_maximum: impl Into<NumericBound>) -> Self {
+        Self {
+            exclusive_maximum: Some(exclusive_maximum.into()),
+            ..self
+        }
+    }
+}
+
+impl Validate for NumericRange {
+    fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+        let num = match val.as_number().and_then(NumericBound::from_number) {
+            Some(num) => num,
+            None => return Ok(()),
+        };
+
+        if let Some(min) = self.minimum {
+            if num < min {
+                return Err(ValidationError::new(
+                    &path,
+                    ValidationErrorKind::MinimumViolation,
+                    format!("{} is less than the minimum of {}", val, min),
+                )
+                .into());
+            }
+        }
+
+        if let Some(min) = self.exclusive_minimum {
+            if num <= min {
+                return Err(ValidationError::new(
+                    &path,
+                    ValidationErrorKind::MinimumViolation,
+                    format!(
+                        "{} is less than or equal to the exclusive minimum of {}",
+                        val, min
+                    ),
+                )
+                .into());
+            }
+        }
+
+        if let Some(max) = self.maximum {
+            if num > max {
+                return Err(ValidationError::new(
+                    &path,
+                    ValidationErrorKind::MaximumViolation,
+                    format!("{} is greater than the maximum of {}", val, max),
+                )
+                .into());
+            }
+        }
+
+        if let Some(max) = self.exclusive_maximum {
+            if num >= max {
+                return Err(ValidationError::new(
+                    &path,
+                    ValidationErrorKind::MaximumViolation,
+                    format!(
+                        "{} is greater than or equal to the exclusive maximum of {}",
+                        val, max
+                    ),
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{super::tests::*, *};
+
+    #[test]
+    fn inclusive_range_validation() {
+        let v = NumericRange::new().minimum(1.0).maximum(1.1);
+
+        valid_vs_invalid!(
+            v,
+            &[&INTEGER, &FLOAT, &STRING, &NULL],
+            &[&json!(0), &json!(1.2)],
+        );
+    }
+
+    #[test]
+    fn exclusive_range_validation() {
+        let v = NumericRange::new()
+            .exclusive_minimum(1.0)
+            .exclusive_maximum(2.0);
+
+        valid_vs_invalid!(v, &[&FLOAT, &json!(1.9)], &[&INTEGER, &json!(2)]);
+    }
+
+    #[test]
+    fn integer_range_validation() {
+        let v = NumericRange::new().maximum(i64::MAX - 1);
+
+        valid_vs_invalid!(v, &[&json!(i64::MAX - 1), &FLOAT], &[&json!(i64::MAX)]);
+    }
+}
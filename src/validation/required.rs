@@ -1,6 +1,6 @@
 use serde_json::Value as JsonValue;
 
-use super::{Error, Path, Validate};
+use super::{json_type_name, Error, Path, Validate, ValidationError, ValidationErrorKind};
 use crate::spec::SchemaType;
 
 #[derive(Debug, Clone)]
@@ -16,15 +16,26 @@ impl RequiredFields {
 
 impl Validate for RequiredFields {
     fn validate(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
-        let obj = val
-            .as_object()
-            .ok_or_else(|| Error::TypeMismatch(path.clone(), SchemaType::Object))?;
+        let obj = val.as_object().ok_or_else(|| {
+            ValidationError::new(
+                &path,
+                ValidationErrorKind::TypeMismatch,
+                format!(
+                    "expected {}, got {}",
+                    SchemaType::Object,
+                    json_type_name(val)
+                ),
+            )
+        })?;
 
         for field in &self.fields {
-            let path = path.extend(field);
-
             if obj.get(&field[..]).is_none() {
-                return Err(Error::RequiredFieldMissing(path));
+                return Err(ValidationError::new(
+                    &path.extend(field),
+                    ValidationErrorKind::RequiredFieldMissing,
+                    format!("required property \"{}\" is missing", field),
+                )
+                .into());
             }
         }
 
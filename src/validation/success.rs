@@ -0,0 +1,31 @@
+use std::collections::BTreeMap;
+
+use super::Path;
+
+/// Details of a successful validation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationSuccess {
+    /// Index of the matching `oneOf` branch, keyed by the JSON Pointer of the validated value.
+    pub one_of_branches: BTreeMap<String, usize>,
+}
+
+impl ValidationSuccess {
+    /// Returns the index of the `oneOf` branch that matched the root value, if any.
+    pub fn matched_branch(&self) -> Option<usize> {
+        self.one_of_branch("")
+    }
+
+    /// Returns the index of the `oneOf` branch that matched the value at a JSON Pointer, if any.
+    pub fn one_of_branch(&self, pointer: &str) -> Option<usize> {
+        self.one_of_branches.get(pointer).copied()
+    }
+
+    pub(crate) fn with_one_of_branch(mut self, path: &Path, index: usize) -> Self {
+        self.one_of_branches.insert(path.to_json_pointer(), index);
+        self
+    }
+
+    pub(crate) fn merge(&mut self, other: ValidationSuccess) {
+        self.one_of_branches.extend(other.one_of_branches);
+    }
+}
@@ -1,12 +1,13 @@
 use serde_json::Value as JsonValue;
 
-use super::{Error, Path, Validate};
+use super::{json_type_name, Error, Path, Validate, ValidationError, ValidationErrorKind};
 use crate::spec::SchemaType;
 
 #[derive(Debug, Clone)]
 pub struct DataType {
     r#type: SchemaType,
     nullable: bool,
+    title: Option<String>,
 }
 
 impl DataType {
@@ -14,6 +15,7 @@ impl DataType {
         Self {
             r#type: type_,
             nullable: false,
+            title: None,
         }
     }
 
@@ -46,6 +48,21 @@ impl DataType {
             ..self
         }
     }
+
+    /// Sets the title of the schema, which is included in error messages.
+    pub fn set_title(self, title: Option<String>) -> Self {
+        Self { title, ..self }
+    }
+
+    fn mismatch_detail(&self, actual: &str) -> String {
+        match &self.title {
+            Some(title) => format!(
+                "does not match schema '{}': expected {}, got {}",
+                title, self.r#type, actual
+            ),
+            None => format!("expected {}, got {}", self.r#type, actual),
+        }
+    }
 }
 
 impl Validate for DataType {
@@ -69,7 +86,14 @@ impl Validate for DataType {
             JsonValue::Object(_) => SchemaType::Object,
 
             // already checked and returned
-            JsonValue::Null => return Err(Error::InvalidNull(path)),
+            JsonValue::Null => {
+                return Err(ValidationError::new(
+                    &path,
+                    ValidationErrorKind::InvalidNull,
+                    self.mismatch_detail("null"),
+                )
+                .into())
+            }
         };
 
         // check type equality
@@ -79,7 +103,12 @@ impl Validate for DataType {
                 return Ok(());
             }
 
-            return Err(Error::TypeMismatch(path, self.r#type));
+            return Err(ValidationError::new(
+                &path,
+                ValidationErrorKind::TypeMismatch,
+                self.mismatch_detail(json_type_name(val)),
+            )
+            .into());
         }
 
         Ok(())
@@ -174,4 +203,22 @@ mod tests {
             &[&NULL, &INTEGER, &FLOAT, &STRING, &ARRAY_INTS],
         );
     }
+
+    #[test]
+    fn titled_mismatch_detail() {
+        let v = DataType::integer().set_title(Some("User Age".to_owned()));
+
+        let err = v
+            .validate(&STRING, Path::from(vec!["age".to_owned()]))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "/age: does not match schema 'User Age': expected integer, got string"
+        );
+
+        let err = DataType::integer()
+            .validate(&STRING, Path::default())
+            .unwrap_err();
+        assert_eq!(err.to_string(), "expected integer, got string");
+    }
 }
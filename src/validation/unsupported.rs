@@ -0,0 +1,21 @@
+use serde_json::Value as JsonValue;
+
+use super::{Error, Path, Validate};
+
+/// Rejects all values, for schema keywords whose semantics are not implemented.
+#[derive(Debug)]
+pub struct UnsupportedKeyword {
+    keyword: &'static str,
+}
+
+impl UnsupportedKeyword {
+    pub fn new(keyword: &'static str) -> Self {
+        Self { keyword }
+    }
+}
+
+impl Validate for UnsupportedKeyword {
+    fn validate(&self, _val: &JsonValue, _path: Path) -> Result<(), Error> {
+        Err(Error::UnsupportedKeyword(self.keyword))
+    }
+}
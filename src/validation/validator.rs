@@ -1,8 +1,15 @@
 use std::{collections::BTreeMap, fmt};
 
-use super::{AggregateError, DataType, Error, Path, RequiredFields, Validate};
+use super::{
+    json_type_name, AccessMode, AggregateError, ContentEncoding, DataType, EnumValues, Error, Not,
+    NumericRange, Path, Pattern, RequiredFields, StringLength, UnsupportedKeyword, Validate,
+    ValidationContext, ValidationError, ValidationErrorKind, ValidationMode, ValidationSuccess,
+};
 use crate::{
-    spec::{Error as SchemaError, SchemaType},
+    spec::{
+        pattern::compile_ecma_regex_cached, Discriminator, Error as SchemaError, ObjectOrReference,
+        Ref, RefError, RefResolver, SchemaType, SpecRefResolver,
+    },
     Schema, Spec,
 };
 
@@ -13,12 +20,85 @@ use serde_json::Value as JsonValue;
 pub enum ValidationBranch {
     Leaf,
     Array(Box<ValidationTree>),
-    Object(BTreeMap<String, ValidationTree>),
+    /// Arrays whose leading items are validated by position (`prefixItems`) and whose remaining
+    /// items are validated by the `items` tree, if any.
+    Tuple(Vec<ValidationTree>, Option<Box<ValidationTree>>),
+    /// Objects whose properties are validated by name and, if they match one of its patterns,
+    /// by the `patternProperties` trees.
+    Object(BTreeMap<String, ValidationTree>, Option<PatternProperties>),
     AllOf(Vec<ValidationTree>),
-    OneOf(Vec<ValidationTree>),
+    OneOf(Vec<ValidationTree>, Option<DiscriminatorSelector>),
     AnyOf(Vec<ValidationTree>),
 }
 
+/// Maps the values of a `oneOf` discriminator property to branch indexes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscriminatorSelector {
+    pub property_name: String,
+    pub mapping: BTreeMap<String, usize>,
+}
+
+impl DiscriminatorSelector {
+    /// Builds a selector for the given `oneOf` branches.
+    ///
+    /// Explicit `mapping` entries may name a schema or give its full reference. Branches that
+    /// reference a component schema are also selected by the component's name.
+    pub fn new(discriminator: &Discriminator, branches: &[ObjectOrReference<Schema>]) -> Self {
+        let mut mapping = BTreeMap::new();
+
+        for (index, branch) in branches.iter().enumerate() {
+            let ref_path = match branch.ref_path() {
+                Some(ref_path) => ref_path,
+                None => continue,
+            };
+
+            let name = match ref_path.parse::<Ref>() {
+                Ok(r) => r.name,
+                Err(_) => continue,
+            };
+
+            for (value, target) in &discriminator.mapping {
+                if target == ref_path || *target == name {
+                    mapping.insert(value.clone(), index);
+                }
+            }
+
+            mapping.entry(name).or_insert(index);
+        }
+
+        Self {
+            property_name: discriminator.property_name.clone(),
+            mapping,
+        }
+    }
+
+    /// Returns the branch index selected by a value's discriminator property, if any.
+    pub fn select(&self, val: &JsonValue) -> Option<usize> {
+        let value = val.get(&self.property_name)?.as_str()?;
+        self.mapping.get(value).copied()
+    }
+}
+
+/// Validation trees for an object's `patternProperties`, keyed by pattern.
+#[derive(Debug)]
+pub struct PatternProperties {
+    schema: Box<Schema>,
+    valtrees: BTreeMap<String, ValidationTree>,
+}
+
+impl PatternProperties {
+    /// Returns the trees of the patterns matching a property name.
+    pub fn matching(&self, prop: &str) -> Vec<&ValidationTree> {
+        // patterns are compiled when the tree is built, so matching cannot fail here
+        self.schema
+            .matching_pattern_properties(prop)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(pattern, _)| self.valtrees.get(pattern))
+            .collect()
+    }
+}
+
 pub struct ValidationTree {
     pub validators: Vec<Box<dyn Validate>>,
     pub branch: ValidationBranch,
@@ -26,6 +106,68 @@ pub struct ValidationTree {
 
 impl ValidationTree {
     pub fn from_schema(schema: &Schema, spec: &Spec) -> Result<ValidationTree, SchemaError> {
+        Self::from_schema_with_resolver(schema, &SpecRefResolver::new(spec))
+    }
+
+    /// Builds a validation tree, resolving schema references with a custom [`RefResolver`].
+    pub fn from_schema_with_resolver(
+        schema: &Schema,
+        resolver: &dyn RefResolver,
+    ) -> Result<ValidationTree, SchemaError> {
+        Self::from_schema_with_context(schema, resolver, ValidationContext::default())
+    }
+
+    /// Builds a validation tree for a [`ValidationContext`].
+    ///
+    /// If the context has a mode, properties that are `readOnly` (in requests) or `writeOnly`
+    /// (in responses) are rejected and are no longer considered required.
+    ///
+    /// Recursive schemas are not supported: they fail with [`RefError::Circular`] since trees
+    /// are built eagerly.
+    ///
+    /// [`RefError::Circular`]: crate::spec::RefError::Circular
+    pub fn from_schema_with_context(
+        schema: &Schema,
+        resolver: &dyn RefResolver,
+        ctx: ValidationContext,
+    ) -> Result<ValidationTree, SchemaError> {
+        Self::build(schema, resolver, ctx, &mut vec![])
+    }
+
+    /// Resolves and builds the tree of a subschema, failing if its reference is already being
+    /// built.
+    fn build_ref(
+        schema_ref: &ObjectOrReference<Schema>,
+        resolver: &dyn RefResolver,
+        ctx: ValidationContext,
+        visiting: &mut Vec<String>,
+    ) -> Result<(Schema, ValidationTree), SchemaError> {
+        let schema = schema_ref.resolve_with(resolver)?;
+
+        let valtree = match schema_ref.ref_path() {
+            Some(ref_path) => {
+                if visiting.iter().any(|visited| visited == ref_path) {
+                    return Err(RefError::Circular(ref_path.to_owned()).into());
+                }
+
+                visiting.push(ref_path.to_owned());
+                let valtree = Self::build(&schema, resolver, ctx, visiting);
+                visiting.pop();
+                valtree?
+            }
+            None => Self::build(&schema, resolver, ctx, visiting)?,
+        };
+
+        Ok((schema, valtree))
+    }
+
+    /// `visiting` holds the references being built, to detect recursive schemas.
+    fn build(
+        schema: &Schema,
+        resolver: &dyn RefResolver,
+        ctx: ValidationContext,
+        visiting: &mut Vec<String>,
+    ) -> Result<ValidationTree, SchemaError> {
         trace!(
             "creating validation tree from schema: {}",
             &schema.title.as_deref().unwrap_or("_unnamed_")
@@ -36,18 +178,112 @@ impl ValidationTree {
             branch: ValidationBranch::Leaf,
         };
 
+        if schema.is_empty() {
+            trace!("schema is empty; accepting any value");
+            return Ok(valtree);
+        }
+
+        let unsupported = [
+            ("$dynamicRef", schema.dynamic_ref.is_some()),
+            ("if", schema.if_schema.is_some()),
+        ];
+
+        for (keyword, used) in unsupported {
+            if used {
+                trace!("rejecting unsupported {}", keyword);
+
+                valtree
+                    .validators
+                    .push(Box::new(UnsupportedKeyword::new(keyword)));
+            }
+        }
+
         if let Some(type_) = schema.schema_type {
             trace!("restricting data type: {:?}", type_);
 
-            let type_val = if type_ == SchemaType::Null {
-                DataType::new(type_).set_nullable(true)
-            } else {
-                DataType::new(type_)
-            };
+            let type_val = DataType::new(type_)
+                .set_nullable(type_ == SchemaType::Null)
+                .set_title(schema.title.clone());
 
             valtree.validators.push(Box::new(type_val));
         }
 
+        if !schema.enum_values.is_empty() {
+            trace!("restricting enum values: {:?}", &schema.enum_values);
+
+            let enum_val = EnumValues::new(schema.enum_values.clone());
+            valtree.validators.push(Box::new(enum_val));
+        }
+
+        if let Some(value) = &schema.const_value {
+            trace!("restricting const value: {:?}", value);
+
+            let const_val = EnumValues::new(vec![value.clone()]);
+            valtree.validators.push(Box::new(const_val));
+        }
+
+        let (minimum, exclusive_minimum) = schema.minimum_bounds();
+        let (maximum, exclusive_maximum) = schema.maximum_bounds();
+        let bounds = [minimum, maximum, exclusive_minimum, exclusive_maximum];
+
+        if bounds.iter().any(|bound| bound.is_some()) {
+            let mut range = NumericRange::new();
+
+            if let Some(min) = minimum {
+                range = range.minimum(min);
+            }
+            if let Some(max) = maximum {
+                range = range.maximum(max);
+            }
+            if let Some(min) = exclusive_minimum {
+                range = range.exclusive_minimum(min);
+            }
+            if let Some(max) = exclusive_maximum {
+                range = range.exclusive_maximum(max);
+            }
+
+            valtree.validators.push(Box::new(range));
+        }
+
+        if schema.min_length.is_some() || schema.max_length.is_some() {
+            let length = StringLength::new(schema.min_length, schema.max_length);
+            valtree.validators.push(Box::new(length));
+        }
+
+        if let Some(pattern) = schema.pattern.as_deref() {
+            trace!("restricting pattern: {}", pattern);
+
+            valtree.validators.push(Box::new(Pattern::new(pattern)?));
+        }
+
+        if let Some(encoding) = schema.content_encoding.as_ref() {
+            trace!("restricting content encoding: {:?}", encoding);
+
+            let mut content = ContentEncoding::new(encoding.clone());
+
+            let is_json = schema
+                .content_media_type
+                .as_deref()
+                .map_or(false, is_json_media_type);
+
+            if let (true, Some(schema_ref)) = (is_json, schema.content_schema.as_ref()) {
+                trace!("adding content schema validator");
+
+                let (_, content_valtree) = Self::build_ref(schema_ref, resolver, ctx, visiting)?;
+                content = content.with_json_content(content_valtree);
+            }
+
+            valtree.validators.push(Box::new(content));
+        }
+
+        if let Some(schema_ref) = schema.not.as_ref() {
+            trace!("adding not validator");
+
+            let (sub_schema, not_valtree) = Self::build_ref(schema_ref, resolver, ctx, visiting)?;
+            let not_val = Not::new(sub_schema.title.clone(), not_valtree);
+            valtree.validators.push(Box::new(not_val));
+        }
+
         match schema.schema_type {
             Some(SchemaType::Object) => {
                 trace!(
@@ -60,34 +296,105 @@ impl ValidationTree {
                         .join(",")
                 );
 
-                let vls = schema
-                    .properties
-                    .iter()
-                    .map(|(prop, schema)| {
-                        let sub_schema = schema.resolve(spec).unwrap();
-                        let valtree = ValidationTree::from_schema(&sub_schema, spec).unwrap();
-                        (prop.clone(), valtree)
-                    })
-                    .collect();
+                let mut forbidden = vec![];
+
+                let mut vls = BTreeMap::new();
+
+                for (prop, schema) in &schema.properties {
+                    let (sub_schema, valtree) = Self::build_ref(schema, resolver, ctx, visiting)?;
+
+                    let is_forbidden = match ctx.mode {
+                        Some(ValidationMode::Request) => sub_schema.read_only == Some(true),
+                        Some(ValidationMode::Response) => sub_schema.write_only == Some(true),
+                        None => false,
+                    };
 
-                valtree.branch = ValidationBranch::Object(vls);
+                    if is_forbidden {
+                        forbidden.push(prop.clone());
+                    }
+
+                    vls.insert(prop.clone(), valtree);
+                }
+
+                let patterns = if schema.pattern_properties.is_empty() {
+                    None
+                } else {
+                    let mut valtrees = BTreeMap::new();
+
+                    for (pattern, schema_ref) in &schema.pattern_properties {
+                        trace!("adding pattern property validator: {}", pattern);
+
+                        compile_ecma_regex_cached(pattern).map_err(|_| {
+                            crate::spec::SchemaError::InvalidPattern(pattern.clone())
+                        })?;
+
+                        let (_, vls) = Self::build_ref(schema_ref, resolver, ctx, visiting)?;
+                        valtrees.insert(pattern.clone(), vls);
+                    }
+
+                    let schema = Box::new(Schema {
+                        pattern_properties: schema.pattern_properties.clone(),
+                        ..Schema::default()
+                    });
+
+                    Some(PatternProperties { schema, valtrees })
+                };
+
+                valtree.branch = ValidationBranch::Object(vls, patterns);
 
-                if !schema.required.is_empty() {
-                    trace!("required fields: {:?}", &schema.required);
+                // forbidden properties are only required in the other mode
+                let required = schema
+                    .required
+                    .iter()
+                    .filter(|field| !forbidden.contains(field))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                if !required.is_empty() {
+                    trace!("required fields: {:?}", &required);
 
-                    let req_fields = RequiredFields::new(schema.required.clone());
+                    let req_fields = RequiredFields::new(required);
                     valtree.validators.push(Box::new(req_fields));
                 }
+
+                if let (Some(mode), false) = (ctx.mode, forbidden.is_empty()) {
+                    trace!("forbidden fields: {:?}", &forbidden);
+
+                    valtree
+                        .validators
+                        .push(Box::new(AccessMode::new(mode, forbidden)));
+                }
             }
 
             Some(SchemaType::Array) => {
                 trace!("adding array validators");
 
-                if let Some(schema_ref) = schema.items.as_ref() {
-                    let sub_schema = schema_ref.resolve(spec).unwrap();
-                    let vls = ValidationTree::from_schema(&sub_schema, spec).unwrap();
+                let items = match schema.items.as_ref() {
+                    Some(schema_ref) => {
+                        let (_, vls) = Self::build_ref(schema_ref, resolver, ctx, visiting)?;
+                        Some(Box::new(vls))
+                    }
+                    None => None,
+                };
+
+                if !schema.prefix_items.is_empty() {
+                    trace!(
+                        "adding {} prefix item validators",
+                        schema.prefix_items.len()
+                    );
 
-                    valtree.branch = ValidationBranch::Array(Box::new(vls))
+                    let prefix = schema
+                        .prefix_items
+                        .iter()
+                        .map(|schema_ref| {
+                            Self::build_ref(schema_ref, resolver, ctx, visiting)
+                                .map(|(_, valtree)| valtree)
+                        })
+                        .collect::<Result<_, _>>()?;
+
+                    valtree.branch = ValidationBranch::Tuple(prefix, items)
+                } else if let Some(items) = items {
+                    valtree.branch = ValidationBranch::Array(items)
                 }
             }
 
@@ -99,9 +406,11 @@ impl ValidationTree {
                     let vs = schema
                         .all_of
                         .iter()
-                        .map(|schema_ref| schema_ref.resolve(spec).unwrap())
-                        .map(|schema| ValidationTree::from_schema(&schema, spec).unwrap())
-                        .collect();
+                        .map(|schema_ref| {
+                            Self::build_ref(schema_ref, resolver, ctx, visiting)
+                                .map(|(_, valtree)| valtree)
+                        })
+                        .collect::<Result<_, _>>()?;
 
                     valtree.branch = ValidationBranch::AllOf(vs)
                 }
@@ -111,9 +420,11 @@ impl ValidationTree {
                     let vs = schema
                         .any_of
                         .iter()
-                        .map(|schema_ref| schema_ref.resolve(spec).unwrap())
-                        .map(|schema| ValidationTree::from_schema(&schema, spec).unwrap())
-                        .collect();
+                        .map(|schema_ref| {
+                            Self::build_ref(schema_ref, resolver, ctx, visiting)
+                                .map(|(_, valtree)| valtree)
+                        })
+                        .collect::<Result<_, _>>()?;
 
                     valtree.branch = ValidationBranch::AnyOf(vs)
                 }
@@ -123,11 +434,17 @@ impl ValidationTree {
                     let vs = schema
                         .one_of
                         .iter()
-                        .map(|schema_ref| schema_ref.resolve(spec).unwrap())
-                        .map(|schema| ValidationTree::from_schema(&schema, spec).unwrap())
-                        .collect();
+                        .map(|schema_ref| {
+                            Self::build_ref(schema_ref, resolver, ctx, visiting)
+                                .map(|(_, valtree)| valtree)
+                        })
+                        .collect::<Result<_, _>>()?;
 
-                    valtree.branch = ValidationBranch::OneOf(vs)
+                    let selector = schema.discriminator.as_ref().map(|discriminator| {
+                        DiscriminatorSelector::new(discriminator, &schema.one_of)
+                    });
+
+                    valtree.branch = ValidationBranch::OneOf(vs, selector)
                 }
             }
         }
@@ -138,7 +455,7 @@ impl ValidationTree {
     #[allow(dead_code)]
     fn first_noncomposite_type_is_object(&self) -> bool {
         match &self.branch {
-            ValidationBranch::Object(_) => true,
+            ValidationBranch::Object(..) => true,
             ValidationBranch::AllOf(vs) => {
                 for v in vs {
                     if !v.first_noncomposite_type_is_object() {
@@ -148,7 +465,7 @@ impl ValidationTree {
 
                 true
             }
-            ValidationBranch::OneOf(_) | ValidationBranch::AnyOf(_) => {
+            ValidationBranch::OneOf(..) | ValidationBranch::AnyOf(_) => {
                 panic!("TODO: decide if (any|one)Of is allowed as direct composite child of allOf")
             }
             _ => false,
@@ -156,13 +473,154 @@ impl ValidationTree {
     }
 
     /// top level validation entry-point
-    pub fn validate(&self, val: &JsonValue) -> Result<(), Error> {
+    ///
+    /// On success, reports which `oneOf` branches matched.
+    pub fn validate(&self, val: &JsonValue) -> Result<ValidationSuccess, Error> {
         let path = Path::new('.');
         self.validate_inner(val, path)
     }
 
+    /// Returns true if the value is valid, like [`validate`](Self::validate) but stopping at the
+    /// first failure without collecting errors.
+    ///
+    /// This is the fast path for validating many values: build the tree once and reuse it, since
+    /// building it compiles patterns and resolves references.
+    pub fn is_valid(&self, val: &JsonValue) -> bool {
+        self.check(val).is_ok()
+    }
+
+    /// Fast-path counterpart to `validate_inner`, which must accept the same values.
+    fn check(&self, val: &JsonValue) -> Result<(), Failure> {
+        match &self.branch {
+            ValidationBranch::AllOf(vs) => {
+                for v in vs {
+                    match v.check(val) {
+                        Ok(()) | Err(Failure::UndocumentedField) => {}
+                        Err(Failure::Other) => return Err(Failure::Other),
+                    }
+                }
+
+                Ok(())
+            }
+
+            ValidationBranch::OneOf(vs, selector) => {
+                if let Some(index) = selector.as_ref().and_then(|sel| sel.select(val)) {
+                    return vs[index].check(val).map_err(|_| Failure::Other);
+                }
+
+                let mut matched = 0;
+
+                for v in vs {
+                    if v.check(val).is_ok() {
+                        matched += 1;
+
+                        if matched > 1 {
+                            return Err(Failure::Other);
+                        }
+                    }
+                }
+
+                if matched == 1 {
+                    Ok(())
+                } else {
+                    Err(Failure::Other)
+                }
+            }
+
+            ValidationBranch::AnyOf(vs) => {
+                if vs.iter().any(|v| v.check(val).is_ok()) {
+                    Ok(())
+                } else {
+                    Err(Failure::Other)
+                }
+            }
+
+            ValidationBranch::Array(v) => {
+                self.check_validators(val)?;
+
+                match val {
+                    JsonValue::Array(items) => items.iter().try_for_each(|item| v.check(item)),
+                    _ => Err(Failure::Other),
+                }
+            }
+
+            ValidationBranch::Tuple(prefix, rest) => {
+                self.check_validators(val)?;
+
+                let items = match val {
+                    JsonValue::Array(items) => items,
+                    _ => return Err(Failure::Other),
+                };
+
+                for (i, item) in items.iter().enumerate() {
+                    match prefix.get(i).or(rest.as_deref()) {
+                        Some(v) => v.check(item)?,
+                        None => break,
+                    }
+                }
+
+                Ok(())
+            }
+
+            ValidationBranch::Object(validator_map, patterns) => {
+                self.check_validators(val)?;
+
+                let items = match val {
+                    JsonValue::Object(items) => items,
+                    _ => return Err(Failure::Other),
+                };
+
+                let mut undocumented = false;
+
+                for (prop, val) in items {
+                    let matching = patterns
+                        .as_ref()
+                        .map(|patterns| patterns.matching(prop))
+                        .unwrap_or_default();
+
+                    for validator in &matching {
+                        validator.check(val)?;
+                    }
+
+                    match validator_map.get(prop) {
+                        Some(validator) => validator.check(val)?,
+                        None if matching.is_empty() => undocumented = true,
+                        None => {}
+                    }
+                }
+
+                if undocumented {
+                    Err(Failure::UndocumentedField)
+                } else {
+                    Ok(())
+                }
+            }
+
+            ValidationBranch::Leaf => self.check_validators(val),
+        }
+    }
+
+    fn check_validators(&self, val: &JsonValue) -> Result<(), Failure> {
+        for v in &self.validators {
+            match v.validate(val, Path::default()) {
+                Ok(()) => {}
+                Err(Error::Invalid(ValidationError {
+                    kind: ValidationErrorKind::UndocumentedField,
+                    ..
+                })) => return Err(Failure::UndocumentedField),
+                Err(_) => return Err(Failure::Other),
+            }
+        }
+
+        Ok(())
+    }
+
     /// trigger sub-valtrees validation
-    fn validate_inner(&self, val: &JsonValue, path: Path) -> Result<(), Error> {
+    pub(crate) fn validate_inner(
+        &self,
+        val: &JsonValue,
+        path: Path,
+    ) -> Result<ValidationSuccess, Error> {
         match &self.branch {
             ValidationBranch::AllOf(vs) => {
                 // TODO: error if any self validations
@@ -177,7 +635,12 @@ impl ValidationTree {
                 //     .as_object()
                 //     .ok_or_else(|| Error::TypeMismatch(path.clone(), SchemaType::Object))?;
 
-                for v in vs {
+                // match this val against every sub-valtree, collecting the errors of all
+                // failing branches rather than stopping at the first one
+                let mut success = ValidationSuccess::default();
+                let mut errors = AggregateError::empty();
+
+                for (index, v) in vs.iter().enumerate() {
                     // ~each sub-valtree must be object type~
                     // if !v.first_noncomposite_type_is_object() {
                     //     // TODO: error variant
@@ -189,39 +652,85 @@ impl ValidationTree {
 
                     match v.validate_inner(val, path.clone()) {
                         // TODO: in allOf schemas extraneous fields should be evaluated as a whole
-                        Ok(_) | Err(Error::UndocumentedField(_)) => continue,
-                        Err(err) => return Err(err),
+                        Ok(branch_success) => success.merge(branch_success),
+                        Err(Error::Invalid(ValidationError {
+                            kind: ValidationErrorKind::UndocumentedField,
+                            ..
+                        })) => {}
+                        Err(err) => errors.push(Error::Branch {
+                            keyword: "allOf",
+                            index,
+                            source: Box::new(err),
+                        }),
                     }
                 }
 
-                Ok(())
+                if errors.is_empty() {
+                    Ok(success)
+                } else {
+                    Err(Error::AllOfMismatch(path, errors))
+                }
             }
 
-            // TODO: implement subtle differences in anyOf and oneOf
-            ValidationBranch::OneOf(vs) | ValidationBranch::AnyOf(vs) => {
+            ValidationBranch::OneOf(vs, selector) => {
                 // TODO: error if any self validations
 
-                // match this val against sub-valtrees
-                // error if more than one match
+                // a discriminator picks the only branch worth trying
+                if let Some(index) = selector.as_ref().and_then(|sel| sel.select(val)) {
+                    return match vs[index].validate_inner(val, path.clone()) {
+                        Ok(success) => Ok(success.with_one_of_branch(&path, index)),
+                        Err(err) => {
+                            let err = Error::Branch {
+                                keyword: "oneOf",
+                                index,
+                                source: Box::new(err),
+                            };
+
+                            Err(Error::OneOfNoMatch(path, AggregateError::new(vec![err])))
+                        }
+                    };
+                }
 
-                let mut matched = false;
+                // match this val against all sub-valtrees, exactly one must match
+                let mut matched = vec![];
+                let mut success = ValidationSuccess::default();
                 let mut errors = AggregateError::empty();
 
-                for v in vs {
+                for (index, v) in vs.iter().enumerate() {
                     match v.validate_inner(val, path.clone()) {
-                        Ok(_) => {
-                            matched = true;
-                            break;
+                        Ok(branch_success) => {
+                            matched.push(index);
+                            success = branch_success;
                         }
-                        Err(err) => errors.push(err),
+                        Err(err) => errors.push(Error::Branch {
+                            keyword: "oneOf",
+                            index,
+                            source: Box::new(err),
+                        }),
                     }
                 }
 
-                if matched {
-                    Ok(())
-                } else {
-                    Err(Error::OneOfNoMatch(path, errors))
+                match matched[..] {
+                    [] => Err(Error::OneOfNoMatch(path, errors)),
+                    [index] => Ok(success.with_one_of_branch(&path, index)),
+                    _ => Err(Error::MultipleMatchingBranches(path, matched)),
+                }
+            }
+
+            ValidationBranch::AnyOf(vs) => {
+                // TODO: error if any self validations
+
+                // match this val against sub-valtrees, stopping at the first match
+                let mut errors = AggregateError::empty();
+
+                for v in vs {
+                    match v.validate_inner(val, path.clone()) {
+                        Ok(success) => return Ok(success),
+                        Err(err) => errors.push(err),
+                    }
                 }
+
+                Err(Error::OneOfNoMatch(path, errors))
             }
 
             ValidationBranch::Array(v) => {
@@ -230,41 +739,94 @@ impl ValidationTree {
                     v.validate(val, path.clone())?
                 }
 
+                let mut success = ValidationSuccess::default();
+
                 match val {
                     JsonValue::Array(items) => {
                         for (i, item) in items.iter().enumerate() {
-                            let child_path = path.extend(format!("[{}]", i));
-                            v.validate_inner(item, child_path)?;
+                            let child_path = path.extend(i.to_string());
+                            success.merge(v.validate_inner(item, child_path)?);
                         }
                     }
-                    _ => return Err(Error::TypeMismatch(path, SchemaType::Array)),
+                    _ => return Err(type_mismatch(&path, SchemaType::Array, val)),
                 }
 
-                Ok(())
+                Ok(success)
+            }
+
+            ValidationBranch::Tuple(prefix, rest) => {
+                // validate own valtree level and throw any errors
+                for v in &self.validators {
+                    v.validate(val, path.clone())?
+                }
+
+                let items = match val {
+                    JsonValue::Array(items) => items,
+                    _ => return Err(type_mismatch(&path, SchemaType::Array, val)),
+                };
+
+                let mut success = ValidationSuccess::default();
+
+                // items beyond `prefixItems` are accepted if there is no `items` schema
+                for (i, item) in items.iter().enumerate() {
+                    let v = match prefix.get(i).or(rest.as_deref()) {
+                        Some(v) => v,
+                        None => break,
+                    };
+
+                    let child_path = path.extend(i.to_string());
+                    success.merge(v.validate_inner(item, child_path)?);
+                }
+
+                Ok(success)
             }
 
-            ValidationBranch::Object(validator_map) => {
+            ValidationBranch::Object(validator_map, patterns) => {
                 // validate own valtree level and throw any errors
                 for v in &self.validators {
                     v.validate(val, path.clone())?
                 }
 
+                let mut success = ValidationSuccess::default();
+
                 match val {
                     JsonValue::Object(items) => {
+                        // documented fields are validated first so that undocumented field
+                        // errors, which are ignored by allOf, do not hide other errors
+                        let mut undocumented = None;
+
                         for (prop, val) in items {
                             let child_path = path.extend(prop);
 
+                            let matching = patterns
+                                .as_ref()
+                                .map(|patterns| patterns.matching(prop))
+                                .unwrap_or_default();
+
+                            for validator in &matching {
+                                success.merge(validator.validate_inner(val, child_path.clone())?);
+                            }
+
                             if let Some(validator) = validator_map.get(prop) {
-                                validator.validate_inner(val, child_path)?;
-                            } else {
-                                return Err(Error::UndocumentedField(child_path.to_string()));
+                                success.merge(validator.validate_inner(val, child_path)?);
+                            } else if matching.is_empty() && undocumented.is_none() {
+                                undocumented = Some((prop, child_path));
                             }
                         }
+
+                        if let Some((prop, child_path)) = undocumented {
+                            return Err(ValidationError::new(
+                                &child_path,
+                                ValidationErrorKind::UndocumentedField,
+                                format!("property \"{}\" is not documented", prop),
+                            )
+                            .into());
+                        }
                     }
-                    _ => return Err(Error::TypeMismatch(path, SchemaType::Object)),
+                    _ => return Err(type_mismatch(&path, SchemaType::Object, val)),
                 }
 
-                Ok(())
+                Ok(success)
             }
 
             ValidationBranch::Leaf => {
@@ -273,12 +835,82 @@ impl ValidationTree {
                     v.validate(val, path.clone())?
                 }
 
-                Ok(())
+                Ok(ValidationSuccess::default())
             }
         }
     }
 }
 
+impl Schema {
+    /// Validates a value against this schema, resolving references with `resolver`.
+    pub fn validate(
+        &self,
+        val: &JsonValue,
+        resolver: &dyn RefResolver,
+    ) -> Result<ValidationSuccess, Error> {
+        self.validate_with_context(val, resolver, ValidationContext::default())
+    }
+
+    /// Validates a value against this schema in a [`ValidationContext`], e.g. to reject
+    /// `readOnly` properties in requests.
+    pub fn validate_with_context(
+        &self,
+        val: &JsonValue,
+        resolver: &dyn RefResolver,
+        ctx: ValidationContext,
+    ) -> Result<ValidationSuccess, Error> {
+        ValidationTree::from_schema_with_context(self, resolver, ctx)
+            .map_err(Error::Schema)?
+            .validate(val)
+    }
+
+    /// Returns true if the value is valid against this schema, stopping at the first failure
+    /// without collecting errors.
+    ///
+    /// A [`ValidationTree`] is built on each call; to validate many values against the same
+    /// schema, build one and reuse [`ValidationTree::is_valid`] instead.
+    ///
+    /// Returns false if validators cannot be built for the schema (e.g., due to an invalid
+    /// `pattern`).
+    pub fn strict_validates(&self, val: &JsonValue, spec: &Spec) -> bool {
+        self.validates_all(std::slice::from_ref(val), spec)
+    }
+
+    /// Returns true if any of the values is valid against this schema. See
+    /// [`Schema::strict_validates`].
+    pub fn validates_any(&self, vals: &[JsonValue], spec: &Spec) -> bool {
+        match ValidationTree::from_schema(self, spec) {
+            Ok(valtree) => vals.iter().any(|val| valtree.is_valid(val)),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns true if all of the values are valid against this schema. See
+    /// [`Schema::strict_validates`].
+    pub fn validates_all(&self, vals: &[JsonValue], spec: &Spec) -> bool {
+        match ValidationTree::from_schema(self, spec) {
+            Ok(valtree) => vals.iter().all(|val| valtree.is_valid(val)),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Why a value failed a [`ValidationTree::is_valid`] check.
+enum Failure {
+    /// An undocumented field, which `allOf` branches tolerate.
+    UndocumentedField,
+    Other,
+}
+
+fn type_mismatch(path: &Path, expected: SchemaType, val: &JsonValue) -> Error {
+    ValidationError::new(
+        path,
+        ValidationErrorKind::TypeMismatch,
+        format!("expected {}, got {}", expected, json_type_name(val)),
+    )
+    .into()
+}
+
 impl fmt::Debug for ValidationTree {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ValidationTree")
@@ -291,6 +923,13 @@ impl fmt::Debug for ValidationTree {
     }
 }
 
+/// Checks whether a media type, ignoring parameters, is JSON (e.g., `application/json` or
+/// `application/problem+json`).
+fn is_json_media_type(media_type: &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    essence.eq_ignore_ascii_case("application/json") || essence.ends_with("+json")
+}
+
 #[cfg(test)]
 mod tests {
     use maplit::btreemap;
@@ -326,12 +965,15 @@ mod tests {
     fn valtree_check_first_noncomposite_type() {
         let vt = ValidationTree {
             validators: vec![],
-            branch: ValidationBranch::Object(btreemap! {
-                s("product") => ValidationTree {
-                    validators: vec![],
-                    branch: ValidationBranch::Leaf,
-                }
-            }),
+            branch: ValidationBranch::Object(
+                btreemap! {
+                    s("product") => ValidationTree {
+                        validators: vec![],
+                        branch: ValidationBranch::Leaf,
+                    }
+                },
+                None,
+            ),
         };
 
         assert!(vt.first_noncomposite_type_is_object());
@@ -374,7 +1016,7 @@ mod tests {
 
     #[test]
     fn object_from_schema() {
-        let spec_str = r#"openapi: "3"
+        let spec_str = r#"openapi: "3.1.0"
 paths: {}
 info:
   title: Test API
@@ -395,7 +1037,7 @@ components:
         let schema = get_schema(&spec, "data");
         let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
         assert_eq!(valtree.validators.len(), 2);
-        assert!(matches!(valtree.branch, ValidationBranch::Object(_)));
+        assert!(matches!(valtree.branch, ValidationBranch::Object(..)));
 
         let test = json!({ "size": 123 });
         valtree.validate(&test).unwrap();
@@ -415,7 +1057,7 @@ components:
 
     #[test]
     fn array_from_schema() {
-        let spec_str = r#"openapi: "3"
+        let spec_str = r#"openapi: "3.1.0"
 paths: {}
 info:
   title: Test API
@@ -441,7 +1083,7 @@ components:
 
     #[test]
     fn all_of_from_schema() {
-        let spec_str = r#"openapi: "3"
+        let spec_str = r#"openapi: "3.1.0"
 paths: {}
 info:
   title: Test API
@@ -479,38 +1121,372 @@ components:
     }
 
     #[test]
-    fn any_of_from_schema() {
-        let spec_str = r#"openapi: "3"
+    fn constraints_from_schema() {
+        let spec_str = r#"openapi: "3.1.0"
 paths: {}
 info:
   title: Test API
   version: "0.1"
 components:
   schemas:
-    data:
-      title: Data
-      anyOf: [{ type: number }, { type: string }]
-    list:
-      title: Data List
-      type: array
-      items:
-        anyOf: [{ type: number }, { type: string }]
+    address:
+      type: object
+      properties:
+        zipCode: { type: string, pattern: '^[0-9]{5}$' }
+        floor: { type: integer, minimum: 0, maximum: 100 }
+        share: { type: number, minimum: 0, exclusiveMinimum: true }
+        kind: { type: string, enum: [home, work] }
+        country: { const: US }
+        unit: { const: null }
 "#;
 
         let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
 
-        let schema = get_schema(&spec, "data");
+        let schema = get_schema(&spec, "address");
         let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
-        assert_eq!(valtree.validators.len(), 0);
-        assert!(matches!(valtree.branch, ValidationBranch::AnyOf(_)));
 
-        let test = json!("123");
+        let test = json!({ "zipCode": "12345", "floor": 3, "kind": "home", "country": "US" });
         valtree.validate(&test).unwrap();
+        valtree
+            .validate(&json!({ "unit": null, "share": 0.5 }))
+            .unwrap();
+
+        let err = valtree.validate(&json!({ "zipCode": "1234" })).unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/zipCode");
+        assert_eq!(err.kind, ValidationErrorKind::PatternMismatch);
+
+        let err = valtree.validate(&json!({ "floor": -1 })).unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/floor");
+        assert_eq!(err.kind, ValidationErrorKind::MinimumViolation);
+
+        let err = valtree.validate(&json!({ "share": 0 })).unwrap_err();
+        assert_eq!(err.as_invalid().unwrap().path, "/share");
+
+        let err = valtree.validate(&json!({ "kind": "other" })).unwrap_err();
+        assert_eq!(
+            err.as_invalid().unwrap().kind,
+            ValidationErrorKind::EnumMismatch
+        );
 
-        let test = json!(123);
-        valtree.validate(&test).unwrap();
+        for test in [json!({ "country": "CA" }), json!({ "unit": "m" })] {
+            let err = valtree.validate(&test).unwrap_err();
+            assert_eq!(
+                err.as_invalid().unwrap().kind,
+                ValidationErrorKind::EnumMismatch
+            );
+        }
 
-        valtree.validate(&NULL).unwrap_err();
+        let err = valtree.validate(&json!({ "floor": "3" })).unwrap_err();
+        assert_eq!(err.to_string(), "/floor: expected integer, got string");
+    }
+
+    #[test]
+    fn encoded_json_content_from_schema() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    message:
+      type: object
+      properties:
+        payload:
+          type: string
+          contentEncoding: base64
+          contentMediaType: application/json
+          contentSchema:
+            type: object
+            properties: { id: { type: integer } }
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let schema = get_schema(&spec, "message");
+        let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
+
+        valtree
+            .validate(&json!({ "payload": "eyJpZCI6MX0=" }))
+            .unwrap();
+
+        let err = valtree.validate(&json!({ "payload": "@@" })).unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/payload");
+        assert_eq!(err.kind, ValidationErrorKind::EncodingViolation);
+
+        let err = valtree
+            .validate(&json!({ "payload": "bm90IGpzb24=" }))
+            .unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/payload");
+        assert_eq!(err.kind, ValidationErrorKind::ContentMediaTypeViolation);
+
+        let err = valtree
+            .validate(&json!({ "payload": "eyJpZCI6IngifQ==" }))
+            .unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/payload/id");
+        assert_eq!(err.kind, ValidationErrorKind::TypeMismatch);
+    }
+
+    #[test]
+    fn read_only_and_write_only_by_mode() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    user:
+      type: object
+      required: [id, name, password]
+      properties:
+        id: { type: integer, readOnly: true }
+        name: { type: string }
+        password: { type: string, writeOnly: true }
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+        let resolver = SpecRefResolver::new(&spec);
+        let schema = get_schema(&spec, "user");
+
+        let request = ValidationContext::request();
+        let response = ValidationContext::response();
+
+        schema
+            .validate_with_context(&json!({ "name": "a", "password": "p" }), &resolver, request)
+            .unwrap();
+        let err = schema
+            .validate_with_context(
+                &json!({ "id": 1, "name": "a", "password": "p" }),
+                &resolver,
+                request,
+            )
+            .unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/id");
+        assert_eq!(err.kind, ValidationErrorKind::ReadOnlyViolation);
+
+        schema
+            .validate_with_context(&json!({ "id": 1, "name": "a" }), &resolver, response)
+            .unwrap();
+        let err = schema
+            .validate_with_context(
+                &json!({ "id": 1, "name": "a", "password": "p" }),
+                &resolver,
+                response,
+            )
+            .unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/password");
+        assert_eq!(err.kind, ValidationErrorKind::WriteOnlyViolation);
+
+        // without a mode, both are allowed and required
+        schema
+            .validate(&json!({ "id": 1, "name": "a", "password": "p" }), &resolver)
+            .unwrap();
+        schema
+            .validate(&json!({ "name": "a", "password": "p" }), &resolver)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn required_access_mode_combinations() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    id:
+      type: integer
+    account:
+      type: object
+      required: [id, secret, created, owner]
+      properties:
+        id: { $ref: '#/components/schemas/id', readOnly: true }
+        secret: { type: string, readOnly: true, writeOnly: true }
+        created: { type: string, readOnly: true }
+        owner:
+          type: object
+          required: [name, token]
+          properties:
+            name: { type: string }
+            token: { type: string, writeOnly: true }
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+        let resolver = SpecRefResolver::new(&spec);
+        let schema = get_schema(&spec, "account");
+
+        let request = ValidationContext::request();
+        let response = ValidationContext::response();
+
+        // read-only fields, including referenced and nested ones, are not required in requests
+        schema
+            .validate_with_context(
+                &json!({ "owner": { "name": "a", "token": "t" } }),
+                &resolver,
+                request,
+            )
+            .unwrap();
+        let err = schema
+            .validate_with_context(&json!({ "owner": { "name": "a" } }), &resolver, request)
+            .unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/owner/token");
+        assert_eq!(err.kind, ValidationErrorKind::RequiredFieldMissing);
+
+        // write-only fields, including nested ones, are not required in responses
+        schema
+            .validate_with_context(
+                &json!({ "id": 1, "created": "today", "owner": { "name": "a" } }),
+                &resolver,
+                response,
+            )
+            .unwrap();
+        let err = schema
+            .validate_with_context(
+                &json!({ "id": 1, "owner": { "name": "a" } }),
+                &resolver,
+                response,
+            )
+            .unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/created");
+        assert_eq!(err.kind, ValidationErrorKind::RequiredFieldMissing);
+
+        // a field that is both read-only and write-only is never allowed
+        let err = schema
+            .validate_with_context(
+                &json!({ "secret": "s", "owner": { "name": "a", "token": "t" } }),
+                &resolver,
+                request,
+            )
+            .unwrap_err();
+        assert_eq!(err.as_invalid().unwrap().path, "/secret");
+        let err = schema
+            .validate_with_context(
+                &json!({ "id": 1, "created": "today", "secret": "s", "owner": { "name": "a" } }),
+                &resolver,
+                response,
+            )
+            .unwrap_err();
+        assert_eq!(err.as_invalid().unwrap().path, "/secret");
+    }
+
+    #[test]
+    fn all_of_collects_all_branch_errors() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    data:
+      allOf:
+      - { type: object, properties: { size: { type: integer } }, required: [size] }
+      - { type: object, properties: { meta: { type: string } }, required: [meta] }
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let schema = get_schema(&spec, "data");
+        let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
+
+        let err = valtree
+            .validate(&json!({ "size": "big", "meta": 1 }))
+            .unwrap_err();
+
+        let errors = match &err {
+            Error::AllOfMismatch(_, errors) => errors,
+            err => panic!("unexpected error: {}", err),
+        };
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors.errors()[0].to_string(),
+            "allOf[0]: /size: expected integer, got string"
+        );
+        assert_eq!(
+            errors.errors()[1].to_string(),
+            "allOf[1]: /meta: expected string, got integer"
+        );
+
+        // only failing branches are reported
+        let err = valtree.validate(&json!({ "size": 1 })).unwrap_err();
+        assert!(matches!(err, Error::AllOfMismatch(_, errors) if errors.len() == 1));
+    }
+
+    #[test]
+    fn not_from_schema() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    data:
+      type: string
+      not: { title: Empty, enum: [""] }
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let schema = get_schema(&spec, "data");
+        let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
+
+        valtree.validate(&json!("full")).unwrap();
+
+        let err = valtree.validate(&json!("")).unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.kind, ValidationErrorKind::NotViolation);
+        assert_eq!(
+            err.detail,
+            r#""" unexpectedly matched `not` schema 'Empty'"#
+        );
+    }
+
+    #[test]
+    fn any_of_from_schema() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    data:
+      title: Data
+      anyOf: [{ type: number }, { type: string }]
+    list:
+      title: Data List
+      type: array
+      items:
+        anyOf: [{ type: number }, { type: string }]
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let schema = get_schema(&spec, "data");
+        let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
+        assert_eq!(valtree.validators.len(), 0);
+        assert!(matches!(valtree.branch, ValidationBranch::AnyOf(_)));
+
+        let test = json!("123");
+        valtree.validate(&test).unwrap();
+
+        let test = json!(123);
+        valtree.validate(&test).unwrap();
+
+        valtree.validate(&NULL).unwrap_err();
 
         let schema = get_schema(&spec, "list");
         let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
@@ -526,4 +1502,555 @@ components:
         let test = json!([123, null, 789]);
         valtree.validate(&test).unwrap_err();
     }
+
+    #[test]
+    fn one_of_reports_matched_branch() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    pet:
+      oneOf:
+      - { $ref: '#/components/schemas/cat' }
+      - { $ref: '#/components/schemas/dog' }
+    pet_by_kind:
+      oneOf:
+      - { $ref: '#/components/schemas/cat' }
+      - { $ref: '#/components/schemas/dog' }
+      discriminator:
+        propertyName: kind
+        mapping:
+          woof: '#/components/schemas/dog'
+    cat:
+      type: object
+      properties:
+        kind: { type: string }
+        lives: { type: integer }
+    dog:
+      type: object
+      properties:
+        kind: { type: string }
+        bark: { type: string }
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let schema = get_schema(&spec, "pet");
+        let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
+
+        let success = valtree.validate(&json!({ "lives": 9 })).unwrap();
+        assert_eq!(success.matched_branch(), Some(0));
+
+        let success = valtree.validate(&json!({ "bark": "loud" })).unwrap();
+        assert_eq!(success.matched_branch(), Some(1));
+
+        let err = valtree.validate(&json!({ "kind": 1 })).unwrap_err();
+        assert!(matches!(err, Error::OneOfNoMatch(_, errors) if errors.len() == 2));
+
+        let err = valtree.validate(&json!({})).unwrap_err();
+        assert!(matches!(err, Error::MultipleMatchingBranches(_, matched) if matched == [0, 1]));
+
+        let schema = get_schema(&spec, "pet_by_kind");
+        let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
+
+        let success = valtree
+            .validate(&json!({ "kind": "cat", "lives": 9 }))
+            .unwrap();
+        assert_eq!(success.matched_branch(), Some(0));
+
+        let success = valtree
+            .validate(&json!({ "kind": "woof", "bark": "loud" }))
+            .unwrap();
+        assert_eq!(success.matched_branch(), Some(1));
+
+        // the discriminator short-circuits to a single branch
+        let err = valtree
+            .validate(&json!({ "kind": "cat", "bark": "loud" }))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Type did not match any `oneOf`/`anyOf` variant: {ROOT}\n  => oneOf[0]: /bark: property \"bark\" is not documented"
+        );
+    }
+
+    #[test]
+    fn validate_with_custom_resolver() {
+        #[derive(Debug)]
+        struct MockResolver;
+
+        impl RefResolver for MockResolver {
+            fn resolve_schema(&self, path: &str) -> Result<Schema, crate::spec::RefError> {
+                assert_eq!(path, "ints.yml");
+
+                Ok(Schema {
+                    schema_type: Some(SchemaType::Integer),
+                    ..Schema::default()
+                })
+            }
+        }
+
+        let schema = Schema {
+            schema_type: Some(SchemaType::Array),
+            items: Some(Box::new(ObjectOrReference::Ref {
+                ref_path: "ints.yml".to_owned(),
+            })),
+            ..Schema::default()
+        };
+
+        schema.validate(&ARRAY_INTS, &MockResolver).unwrap();
+        schema.validate(&ARRAY_STRS, &MockResolver).unwrap_err();
+    }
+
+    #[test]
+    fn ref_with_sibling_keywords() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    name:
+      type: string
+      minLength: 2
+    short_name:
+      $ref: '#/components/schemas/name'
+      maxLength: 4
+    pet:
+      type: object
+      properties:
+        name: { $ref: '#/components/schemas/name', maxLength: 4 }
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+        let schema = spec.components.as_ref().unwrap().schemas["short_name"]
+            .resolve(&spec)
+            .unwrap();
+        let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
+
+        valtree.validate(&json!("Rex")).unwrap();
+        valtree.validate(&json!("R")).unwrap_err();
+        valtree.validate(&json!("Rexford")).unwrap_err();
+        valtree.validate(&json!(3)).unwrap_err();
+
+        // subschemas are resolved when building the tree
+        let schema = get_schema(&spec, "pet");
+        let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
+        valtree.validate(&json!({ "name": "Rex" })).unwrap();
+        valtree.validate(&json!({ "name": "R" })).unwrap_err();
+        valtree.validate(&json!({ "name": "Rexford" })).unwrap_err();
+    }
+
+    #[test]
+    fn strict_validates_matches_validate() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    pet:
+      type: object
+      required: [name]
+      properties:
+        name: { type: string, minLength: 2 }
+        tags: { type: array, items: { type: string } }
+    named:
+      allOf:
+      - { type: object, properties: { name: { type: string } } }
+      - { type: object, properties: { age: { type: integer } } }
+    id:
+      oneOf: [{ type: integer }, { type: number }]
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let schema = get_schema(&spec, "pet");
+        let valid = json!({ "name": "Rex", "tags": ["good"] });
+        let invalid = json!({ "name": "Rex", "tags": ["good", 1] });
+        let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
+
+        for val in [
+            &valid,
+            &invalid,
+            &json!({}),
+            &json!({ "name": "Rex", "age": 3 }),
+            &NULL,
+        ] {
+            assert_eq!(
+                schema.strict_validates(val, &spec),
+                valtree.validate(val).is_ok(),
+                "{}",
+                val
+            );
+        }
+
+        assert!(schema.validates_any(&[invalid.clone(), valid.clone()], &spec));
+        assert!(!schema.validates_all(&[invalid.clone(), valid.clone()], &spec));
+        assert!(schema.validates_all(&[valid.clone(), valid], &spec));
+        assert!(!schema.validates_any(&[invalid], &spec));
+        assert!(!schema.validates_any(&[], &spec));
+
+        // allOf branches tolerate each other's properties
+        let schema = get_schema(&spec, "named");
+        assert!(schema.strict_validates(&json!({ "name": "Rex", "age": 3 }), &spec));
+        assert!(!schema.strict_validates(&json!({ "name": "Rex", "age": "3" }), &spec));
+
+        // integers match both oneOf branches
+        let schema = get_schema(&spec, "id");
+        assert!(schema.strict_validates(&json!(1.5), &spec));
+        assert!(!schema.strict_validates(&json!(1), &spec));
+    }
+
+    #[test]
+    fn dynamic_ref_is_unsupported() {
+        let spec_str = r##"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    tree:
+      $dynamicAnchor: node
+      type: object
+      properties:
+        children: { type: array, items: { $dynamicRef: "#node" } }
+"##;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let schema = get_schema(&spec, "tree");
+        let valtree = ValidationTree::from_schema(&schema, &spec).unwrap();
+
+        valtree.validate(&json!({ "children": [] })).unwrap();
+
+        let err = valtree.validate(&json!({ "children": [{}] })).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedKeyword("$dynamicRef")));
+        assert_eq!(err.to_string(), "Unsupported keyword: $dynamicRef");
+        assert!(!valtree.is_valid(&json!({ "children": [{}] })));
+    }
+
+    #[test]
+    fn pattern_properties_from_schema() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    label: { type: string, maxLength: 8 }
+    labels:
+      type: object
+      properties:
+        x-id: { type: integer }
+      patternProperties:
+        "^x-": { $ref: '#/components/schemas/label' }
+        "^x-[a-z]+$": { type: string, minLength: 2 }
+    invalid:
+      type: object
+      patternProperties:
+        "(unclosed": { type: string }
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let valtree = ValidationTree::from_schema(&get_schema(&spec, "labels"), &spec).unwrap();
+
+        let test = json!({ "x-team": "pets", "x-1": "a" });
+        valtree.validate(&test).unwrap();
+        assert!(valtree.is_valid(&test));
+
+        for (test, path, kind) in [
+            (
+                json!({ "x-team": "far too long" }),
+                "/x-team",
+                ValidationErrorKind::MaxLengthViolation,
+            ),
+            (
+                json!({ "x-a": "a" }),
+                "/x-a",
+                ValidationErrorKind::MinLengthViolation,
+            ),
+            (
+                json!({ "x-id": 1 }),
+                "/x-id",
+                ValidationErrorKind::TypeMismatch,
+            ),
+            (
+                json!({ "team": "pets" }),
+                "/team",
+                ValidationErrorKind::UndocumentedField,
+            ),
+        ] {
+            let err = valtree.validate(&test).unwrap_err();
+            let err = err.as_invalid().unwrap();
+            assert_eq!((err.path.as_str(), err.kind), (path, kind));
+            assert!(!valtree.is_valid(&test));
+        }
+
+        let err = ValidationTree::from_schema(&get_schema(&spec, "invalid"), &spec).unwrap_err();
+        assert!(matches!(err, SchemaError::Schema(_)));
+    }
+
+    #[test]
+    fn conditionals_are_unsupported() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    shipping:
+      if: { properties: { country: { const: US } } }
+      then: { required: [zip] }
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let valtree = ValidationTree::from_schema(&get_schema(&spec, "shipping"), &spec).unwrap();
+
+        let err = valtree.validate(&json!({})).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedKeyword(kw) if kw == "if"));
+        assert!(!valtree.is_valid(&json!({})));
+    }
+
+    #[test]
+    fn prefix_items_from_schema() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    point:
+      type: array
+      prefixItems: [{ type: number }, { type: number }, { type: string }]
+    flags:
+      type: array
+      prefixItems: [{ type: string }, { type: integer }]
+      items: { type: boolean }
+    closed_point:
+      type: array
+      prefixItems: [{ type: number }, { type: number }, { type: string }]
+      items: { not: {} }
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let valtree = |name| ValidationTree::from_schema(&get_schema(&spec, name), &spec).unwrap();
+
+        // additional items allowed
+        let point = valtree("point");
+        assert!(matches!(point.branch, ValidationBranch::Tuple(_, None)));
+        point.validate(&json!([1, 2.5, "a"])).unwrap();
+        point.validate(&json!([1, 2])).unwrap();
+        point.validate(&json!([])).unwrap();
+        point.validate(&json!([1, 2, "a", null, {}])).unwrap();
+        let err = point.validate(&json!([1, "2", "a"])).unwrap_err();
+        assert_eq!(err.as_invalid().unwrap().path, "/1");
+        assert!(!point.is_valid(&json!([1, 2, 3])));
+        assert!(!point.is_valid(&json!({ "0": 1 })));
+
+        // additional items validated by `items`
+        let flags = valtree("flags");
+        flags.validate(&json!(["a", 1, true, false])).unwrap();
+        let err = flags.validate(&json!(["a", 1, true, "b"])).unwrap_err();
+        assert_eq!(err.as_invalid().unwrap().path, "/3");
+        assert!(!flags.is_valid(&json!(["a", true])));
+
+        // additional items forbidden
+        let closed_point = valtree("closed_point");
+        closed_point.validate(&json!([1, 2, "a"])).unwrap();
+        closed_point.validate(&json!([1])).unwrap();
+        let err = closed_point.validate(&json!([1, 2, "a", 3])).unwrap_err();
+        let err = err.as_invalid().unwrap();
+        assert_eq!(err.path, "/3");
+        assert_eq!(err.kind, ValidationErrorKind::NotViolation);
+        assert!(closed_point.is_valid(&json!([1, 2, "a"])));
+        assert!(!closed_point.is_valid(&json!([1, 2, "a", 3])));
+    }
+
+    #[test]
+    fn unresolvable_refs_are_errors() {
+        let resolver = crate::spec::FileRefResolver::new("data/oas-samples");
+
+        let schema = |value| serde_json::from_value::<Schema>(value).unwrap();
+        let missing = json!({ "$ref": "missing.yml#/components/schemas/Pet" });
+
+        for schema in [
+            schema(json!({ "type": "object", "properties": { "pet": missing } })),
+            schema(json!({ "type": "array", "items": missing })),
+            schema(json!({ "type": "array", "prefixItems": [{ "type": "string" }, missing] })),
+            schema(json!({ "not": missing })),
+            schema(json!({ "anyOf": [{ "type": "string" }, missing] })),
+            schema(json!({
+                "type": "object",
+                "properties": {
+                    "pet": { "$ref": "missing.yml#/components/schemas/Pet", "description": "Pet" },
+                },
+            })),
+        ] {
+            let err = schema.validate(&json!({}), &resolver).unwrap_err();
+            assert!(
+                matches!(err, Error::Schema(SchemaError::Ref(_))),
+                "{:?}",
+                err
+            );
+
+            ValidationTree::from_schema_with_resolver(&schema, &resolver).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn invalid_prefix_items_are_errors() {
+        let schema = Schema {
+            schema_type: Some(SchemaType::Array),
+            prefix_items: vec![ObjectOrReference::Object(Schema {
+                pattern: Some("(".to_owned()),
+                ..Schema::default()
+            })],
+            ..Schema::default()
+        };
+
+        let spec = crate::from_reader(
+            r#"{ "openapi": "3.1.0", "info": { "title": "t", "version": "1" }, "paths": {} }"#
+                .as_bytes(),
+        )
+        .unwrap();
+
+        ValidationTree::from_schema(&schema, &spec).unwrap_err();
+    }
+
+    #[test]
+    fn recursive_schemas_are_errors() {
+        let spec_str = r#"openapi: "3.1.0"
+info: { title: Recursive, version: "1" }
+paths: {}
+components:
+  schemas:
+    Node:
+      type: object
+      properties:
+        children: { type: array, items: { $ref: '#/components/schemas/Node' } }
+    Even:
+      anyOf: [{ type: integer }, { $ref: '#/components/schemas/Odd' }]
+    Odd:
+      $ref: '#/components/schemas/Even'
+      description: Odd.
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        for name in ["Node", "Even", "Odd"] {
+            let err = ValidationTree::from_schema(&get_schema(&spec, name), &spec)
+                .map(|_| ())
+                .unwrap_err();
+            assert!(
+                matches!(err, SchemaError::Ref(RefError::Circular(_))),
+                "{:?}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn fast_path_agrees_with_validate() {
+        let spec_str = r#"openapi: "3.1.0"
+paths: {}
+info:
+  title: Test API
+  version: "0.1"
+components:
+  schemas:
+    leaf: { type: string, minLength: 2 }
+    list: { type: array, items: { type: integer } }
+    tuple: { type: array, prefixItems: [{ type: string }], items: { type: integer } }
+    object:
+      type: object
+      required: [name]
+      properties: { name: { type: string }, age: { type: integer } }
+    all:
+      allOf:
+      - { type: object, properties: { name: { type: string } } }
+      - { type: object, properties: { age: { type: integer } } }
+    one:
+      oneOf: [{ type: integer }, { type: number }, { type: string }]
+    tagged:
+      oneOf:
+      - $ref: '#/components/schemas/cat'
+      - $ref: '#/components/schemas/dog'
+      discriminator: { propertyName: kind }
+    cat:
+      type: object
+      properties: { kind: { type: string }, lives: { type: integer } }
+    dog:
+      type: object
+      properties: { kind: { type: string }, barks: { type: boolean } }
+    any:
+      anyOf: [{ type: integer }, { type: string, maxLength: 1 }]
+"#;
+
+        let spec = crate::from_reader(spec_str.as_bytes()).unwrap();
+
+        let values = [
+            json!(null),
+            json!(1),
+            json!(1.5),
+            json!("a"),
+            json!("ab"),
+            json!([]),
+            json!([1, 2]),
+            json!(["a", 1]),
+            json!(["a", "b"]),
+            json!([1, "a"]),
+            json!({}),
+            json!({ "name": "Rex" }),
+            json!({ "name": "Rex", "age": 3 }),
+            json!({ "name": "Rex", "age": "3" }),
+            json!({ "name": "Rex", "owner": "Ann" }),
+            json!({ "kind": "cat", "lives": 9 }),
+            json!({ "kind": "cat", "barks": true }),
+            json!({ "kind": "dog", "barks": true }),
+            json!({ "kind": "dog", "barks": 1 }),
+            json!({ "kind": "fish" }),
+        ];
+
+        let mut kinds = std::collections::BTreeSet::new();
+
+        for name in [
+            "leaf", "list", "tuple", "object", "all", "one", "tagged", "any",
+        ] {
+            let valtree = ValidationTree::from_schema(&get_schema(&spec, name), &spec).unwrap();
+
+            kinds.insert(match valtree.branch {
+                ValidationBranch::Leaf => "leaf",
+                ValidationBranch::Array(_) => "array",
+                ValidationBranch::Tuple(..) => "tuple",
+                ValidationBranch::Object(..) => "object",
+                ValidationBranch::AllOf(_) => "allOf",
+                ValidationBranch::OneOf(..) => "oneOf",
+                ValidationBranch::AnyOf(_) => "anyOf",
+            });
+
+            for val in &values {
+                assert_eq!(
+                    valtree.is_valid(val),
+                    valtree.validate(val).is_ok(),
+                    "{} against {}",
+                    val,
+                    name
+                );
+            }
+        }
+
+        assert_eq!(kinds.len(), 7);
+    }
 }